@@ -18,6 +18,21 @@ defmodule EppoSdk.Client do
   - poll_interval_seconds: Interval between config polls in seconds (default: 30)
   - poll_jitter_seconds: Random jitter added to poll interval (default: 3)
   - base_url: Base URL for the Eppo API (default: https://fscdn.eppo.cloud/api)
+  - configuration_cache_directory: Directory to persist fetched configuration to and load it from on start (default: nil)
+  - assignment_cache_size: Number of recent assignments to remember to avoid logging duplicate events (default: nil, disabled)
+  - assignment_cache_ttl_seconds: How long to remember logged assignments (default: nil, until evicted)
+  - connect_timeout_seconds: Timeout for connecting to Eppo servers (default: 10.0)
+  - read_timeout_seconds: Timeout for each read from Eppo servers (default: 60.0)
+  - proxy: URL of the proxy to send requests through (default: nil, system proxy settings)
+  - root_certificates: Additional PEM-encoded root certificates to trust (default: [])
+  - headers: Additional headers to send with every request (default: %{})
+  - user_agent: Value of the User-Agent header (default: nil)
+  - flags_configuration_file: Load flags configuration from this file instead of Eppo servers (default: nil)
+  - bandits_configuration_file: Load bandits configuration from this file (default: nil)
+  - watch_configuration_files: Reload configuration files when they change (default: false)
+  - max_configuration_age_seconds: Consider configuration stale once it's older than this (default: nil, disabled)
+  - stale_configuration_policy: `:warn` or `:return_default` when configuration is stale (default: :warn)
+  - configuration_history_size: Number of recent configurations kept for rollback (default: 5)
 
   ## Usage
 
@@ -74,6 +89,36 @@ defmodule EppoSdk.Client do
       - poll_interval_seconds: Interval between config polls in seconds (default: 30)
       - poll_jitter_seconds: Random jitter added to poll interval (default: 3)
       - base_url: Base URL for the Eppo API (default: https://fscdn.eppo.cloud/api)
+      - configuration_cache_directory: Directory to persist fetched configuration to and load it
+        from on start, so the client can serve the last known configuration if Eppo servers are
+        unreachable (default: nil, disabled)
+      - assignment_cache_size: Number of recent assignments and bandit actions to remember, so
+        that duplicate events are not sent to the assignment logger (default: nil, disabled)
+      - assignment_cache_ttl_seconds: How long to remember logged assignments (default: nil,
+        until evicted)
+      - connect_timeout_seconds: Timeout for connecting to Eppo servers, nil to disable
+        (default: 10.0)
+      - read_timeout_seconds: Timeout for each read from Eppo servers, nil to disable
+        (default: 60.0)
+      - proxy: URL of the proxy to send requests through, credentials may be included in the URL
+        (default: nil, uses HTTP_PROXY/HTTPS_PROXY environment variables)
+      - root_certificates: List of additional PEM-encoded root certificates to trust, e.g. a
+        corporate CA (default: [])
+      - headers: Map of additional headers to send with every request (default: %{})
+      - user_agent: Value of the User-Agent header (default: nil)
+      - flags_configuration_file: Path to flags configuration (UFC JSON). If set, the client runs
+        in offline mode: configuration is loaded from files and never fetched from Eppo servers
+        (default: nil)
+      - bandits_configuration_file: Path to bandits configuration JSON, requires
+        flags_configuration_file (default: nil)
+      - watch_configuration_files: Reload configuration files when they change (default: false)
+      - max_configuration_age_seconds: Consider configuration stale once it was last fetched or
+        confirmed unchanged longer than this ago (default: nil, disabled)
+      - stale_configuration_policy: What to do when evaluating against stale configuration:
+        `:warn` logs a warning and evaluates as usual, `:return_default` returns default values
+        (default: :warn)
+      - configuration_history_size: Number of recent configurations to keep, so that the client
+        can be rolled back to one of them with `rollback_configuration/2` (default: 5)
     """
     defstruct [
       :api_key,
@@ -81,7 +126,22 @@ defmodule EppoSdk.Client do
       is_graceful_mode: true,
       poll_interval_seconds: 30,
       poll_jitter_seconds: 3,
-      base_url: "https://fscdn.eppo.cloud/api"
+      base_url: "https://fscdn.eppo.cloud/api",
+      configuration_cache_directory: nil,
+      assignment_cache_size: nil,
+      assignment_cache_ttl_seconds: nil,
+      connect_timeout_seconds: 10.0,
+      read_timeout_seconds: 60.0,
+      proxy: nil,
+      root_certificates: [],
+      headers: %{},
+      user_agent: nil,
+      flags_configuration_file: nil,
+      bandits_configuration_file: nil,
+      watch_configuration_files: false,
+      max_configuration_age_seconds: nil,
+      stale_configuration_policy: :warn,
+      configuration_history_size: 5
     ]
   end
 
@@ -102,7 +162,22 @@ defmodule EppoSdk.Client do
           base_url: config.base_url,
           is_graceful_mode: config.is_graceful_mode,
           poll_interval_seconds: config.poll_interval_seconds,
-          poll_jitter_seconds: config.poll_jitter_seconds
+          poll_jitter_seconds: config.poll_jitter_seconds,
+          configuration_cache_directory: config.configuration_cache_directory,
+          assignment_cache_size: config.assignment_cache_size,
+          assignment_cache_ttl_seconds: config.assignment_cache_ttl_seconds,
+          connect_timeout_seconds: config.connect_timeout_seconds,
+          read_timeout_seconds: config.read_timeout_seconds,
+          proxy: config.proxy,
+          root_certificates: config.root_certificates,
+          headers: config.headers,
+          user_agent: config.user_agent,
+          flags_configuration_file: config.flags_configuration_file,
+          bandits_configuration_file: config.bandits_configuration_file,
+          watch_configuration_files: config.watch_configuration_files,
+          max_configuration_age_seconds: config.max_configuration_age_seconds,
+          stale_configuration_policy: to_string(config.stale_configuration_policy),
+          configuration_history_size: config.configuration_history_size
         })
 
       {:ok,
@@ -136,6 +211,130 @@ defmodule EppoSdk.Client do
     :ok
   end
 
+  @doc """
+  Returns configuration poller health, e.g., for readiness probes, or `nil` if the poller is
+  disabled (offline mode).
+
+  The returned map contains:
+    - running: Whether the poller is running
+    - last_success_at: When configuration was last fetched successfully
+    - last_error: The most recent error message
+    - last_error_at: When the most recent error occurred
+    - consecutive_failures: Failed fetches since the last successful one
+    - configuration_fetched_at: When the active configuration was fetched
+    - configuration_created_at: When the active configuration was created on the server
+    - configuration_age_seconds: Seconds since configuration was last fetched or confirmed unchanged
+
+  Timestamps are `DateTime` structs in UTC or `nil`.
+
+  ## Usage
+  ```elixir
+  case EppoSdk.Client.poller_status(client) do
+    %{running: true, configuration_age_seconds: age} when age < 600 -> :ready
+    _ -> :not_ready
+  end
+  ```
+  """
+  def poller_status(%__MODULE__{} = client) do
+    case EppoSdk.Core.poller_status(client.client_ref) do
+      nil ->
+        nil
+
+      status ->
+        [:last_success_at, :last_error_at, :configuration_fetched_at, :configuration_created_at]
+        |> Enum.reduce(status, fn key, status -> Map.update!(status, key, &parse_timestamp/1) end)
+    end
+  end
+
+  @doc """
+  Returns a list of flags, allocations, variations, and conditions in the active configuration
+  that failed to parse or compile. Returns an empty list if all flags are valid or there is no
+  configuration yet.
+
+  Each entry is a map with:
+    - flag_key: Key of the affected flag
+    - allocation_key: Key of the affected allocation or `nil`
+    - variation_key: Key of the affected variation or `nil`
+    - rule_index: Index of the rule within the allocation or `nil`
+    - condition_index: Index of the condition within the rule or `nil`
+    - reason: Human-readable description of the problem
+    - raw: JSON fragment of the affected part of configuration (string)
+
+  ## Usage
+  ```elixir
+  for diagnostic <- EppoSdk.Client.configuration_diagnostics(client) do
+    Logger.warning("invalid flag #{diagnostic.flag_key}: #{diagnostic.reason}")
+  end
+  ```
+  """
+  def configuration_diagnostics(%__MODULE__{} = client) do
+    EppoSdk.Core.configuration_diagnostics(client.client_ref)
+  end
+
+  @doc """
+  Returns recently activated configurations, newest first.
+
+  Each entry is a map with the following keys:
+    - version: Version number to pass to `rollback_configuration/2`
+    - active: Whether this configuration is currently active
+    - fetched_at: When the configuration was fetched (`DateTime`)
+    - created_at: When the configuration was created on the server (`DateTime`)
+
+  The number of kept configurations is controlled by `Config.configuration_history_size`.
+  """
+  def configuration_history(%__MODULE__{} = client) do
+    for entry <- EppoSdk.Core.configuration_history(client.client_ref) do
+      %{
+        entry
+        | fetched_at: parse_timestamp(entry.fetched_at),
+          created_at: parse_timestamp(entry.created_at)
+      }
+    end
+  end
+
+  @doc """
+  Keeps the currently active configuration, ignoring new configurations until
+  `unpin_configuration/1` is called. New configurations are still recorded in history.
+  """
+  def pin_configuration(%__MODULE__{} = client) do
+    EppoSdk.Core.pin_configuration(client.client_ref)
+    :ok
+  end
+
+  @doc """
+  Activates configuration `version` from `configuration_history/1` and pins it.
+
+  Returns `:ok` on success or `{:error, :not_found}` if the version is no longer in history.
+
+  ## Usage
+  ```elixir
+  [_latest, previous | _] = EppoSdk.Client.configuration_history(client)
+  :ok = EppoSdk.Client.rollback_configuration(client, previous.version)
+  ```
+  """
+  def rollback_configuration(%__MODULE__{} = client, version) when is_integer(version) do
+    if EppoSdk.Core.rollback_configuration(client.client_ref, version) do
+      :ok
+    else
+      {:error, :not_found}
+    end
+  end
+
+  @doc """
+  Resumes applying new configurations and activates the latest one.
+  """
+  def unpin_configuration(%__MODULE__{} = client) do
+    EppoSdk.Core.unpin_configuration(client.client_ref)
+    :ok
+  end
+
+  @doc """
+  Returns whether configuration is pinned by `pin_configuration/1` or `rollback_configuration/2`.
+  """
+  def configuration_pinned?(%__MODULE__{} = client) do
+    EppoSdk.Core.configuration_pinned(client.client_ref)
+  end
+
   @doc """
   Assigns a string variant based on the provided flag configuration.
 
@@ -468,6 +667,13 @@ defmodule EppoSdk.Client do
     logger.log_assignment(event)
   end
 
+  defp parse_timestamp(nil), do: nil
+
+  defp parse_timestamp(timestamp) do
+    {:ok, datetime, _offset} = DateTime.from_iso8601(timestamp)
+    datetime
+  end
+
   defp decode_value(nil), do: nil
 
   defp decode_value(value_json) do
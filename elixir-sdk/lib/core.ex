@@ -13,7 +13,22 @@ defmodule EppoSdk.Core do
               base_url: "",
               is_graceful_mode: true,
               poll_interval_seconds: 30,
-              poll_jitter_seconds: 3
+              poll_jitter_seconds: 3,
+              configuration_cache_directory: nil,
+              assignment_cache_size: nil,
+              assignment_cache_ttl_seconds: nil,
+              connect_timeout_seconds: 10.0,
+              read_timeout_seconds: 60.0,
+              proxy: nil,
+              root_certificates: [],
+              headers: %{},
+              user_agent: nil,
+              flags_configuration_file: nil,
+              bandits_configuration_file: nil,
+              watch_configuration_files: false,
+              max_configuration_age_seconds: nil,
+              stale_configuration_policy: "warn",
+              configuration_history_size: 5
   end
 
   @opaque client :: reference()
@@ -34,6 +49,20 @@ defmodule EppoSdk.Core do
       
   def wait_for_initialization(_client, _timeout_secs \\ 1.0), do: error()
 
+  def poller_status(_client), do: error()
+
+  def configuration_diagnostics(_client), do: error()
+
+  def configuration_history(_client), do: error()
+
+  def pin_configuration(_client), do: error()
+
+  def rollback_configuration(_client, _version), do: error()
+
+  def unpin_configuration(_client), do: error()
+
+  def configuration_pinned(_client), do: error()
+
   # Helper function for NIF not loaded errors
   defp error, do: :erlang.nif_error(:nif_not_loaded)
 end
@@ -1,3 +1,9 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use eppo_core::configuration_fetcher::HttpClientConfig;
+use eppo_core::configuration_file::ConfigurationFiles;
+use eppo_core::eval::StaleConfigurationPolicy;
 use rustler::NifStruct;
 
 #[derive(NifStruct)]
@@ -8,6 +14,21 @@ pub struct Config {
     pub is_graceful_mode: bool,
     pub poll_interval_seconds: Option<u64>,
     pub poll_jitter_seconds: u64,
+    pub configuration_cache_directory: Option<String>,
+    pub assignment_cache_size: Option<usize>,
+    pub assignment_cache_ttl_seconds: Option<f64>,
+    pub connect_timeout_seconds: Option<f64>,
+    pub read_timeout_seconds: Option<f64>,
+    pub proxy: Option<String>,
+    pub root_certificates: Vec<String>,
+    pub headers: HashMap<String, String>,
+    pub user_agent: Option<String>,
+    pub flags_configuration_file: Option<String>,
+    pub bandits_configuration_file: Option<String>,
+    pub watch_configuration_files: bool,
+    pub max_configuration_age_seconds: Option<f64>,
+    pub stale_configuration_policy: String,
+    pub configuration_history_size: usize,
 }
 
 impl Config {
@@ -15,6 +36,114 @@ impl Config {
         if self.api_key.is_empty() {
             return Err("Invalid value for api_key: cannot be blank".to_string());
         }
+        if self
+            .assignment_cache_ttl_seconds
+            .is_some_and(|ttl| !ttl.is_finite() || ttl < 0.0)
+        {
+            return Err(
+                "Invalid value for assignment_cache_ttl_seconds: must be a non-negative number"
+                    .to_string(),
+            );
+        }
+        for (name, timeout) in [
+            ("connect_timeout_seconds", self.connect_timeout_seconds),
+            ("read_timeout_seconds", self.read_timeout_seconds),
+        ] {
+            if timeout.is_some_and(|timeout| !timeout.is_finite() || timeout <= 0.0) {
+                return Err(format!(
+                    "Invalid value for {name}: must be a positive number"
+                ));
+            }
+        }
+        if self.flags_configuration_file.is_none()
+            && (self.bandits_configuration_file.is_some() || self.watch_configuration_files)
+        {
+            return Err("flags_configuration_file is required for bandits_configuration_file and watch_configuration_files".to_string());
+        }
+        if self
+            .max_configuration_age_seconds
+            .is_some_and(|age| !age.is_finite() || age < 0.0)
+        {
+            return Err(
+                "Invalid value for max_configuration_age_seconds: must be a non-negative number"
+                    .to_string(),
+            );
+        }
+        self.stale_configuration_policy()?;
+        if self.configuration_history_size == 0 {
+            return Err(
+                "Invalid value for configuration_history_size: must be a positive integer"
+                    .to_string(),
+            );
+        }
         Ok(())
     }
-}
\ No newline at end of file
+
+    pub fn stale_configuration_policy(&self) -> Result<StaleConfigurationPolicy, String> {
+        match self.stale_configuration_policy.as_str() {
+            "warn" => Ok(StaleConfigurationPolicy::Warn),
+            "return_default" => Ok(StaleConfigurationPolicy::ReturnDefault),
+            other => Err(format!(
+                "Invalid value for stale_configuration_policy: {other:?} (expected :warn or :return_default)"
+            )),
+        }
+    }
+
+    /// Configuration files for offline mode.
+    pub fn configuration_files(&self) -> Option<ConfigurationFiles> {
+        let files = ConfigurationFiles::new(self.flags_configuration_file.as_ref()?);
+        Some(match &self.bandits_configuration_file {
+            Some(bandits) => files.with_bandits(bandits),
+            None => files,
+        })
+    }
+
+    /// How long to remember logged assignments for.
+    pub fn assignment_cache_ttl(&self) -> Result<Option<Duration>, String> {
+        seconds_to_duration(
+            "assignment_cache_ttl_seconds",
+            self.assignment_cache_ttl_seconds,
+        )
+    }
+
+    /// Age after which configuration is considered stale.
+    pub fn max_configuration_age(&self) -> Result<Option<Duration>, String> {
+        seconds_to_duration(
+            "max_configuration_age_seconds",
+            self.max_configuration_age_seconds,
+        )
+    }
+
+    pub fn http_client_config(&self) -> Result<HttpClientConfig, String> {
+        Ok(HttpClientConfig {
+            connect_timeout: seconds_to_duration(
+                "connect_timeout_seconds",
+                self.connect_timeout_seconds,
+            )?,
+            read_timeout: seconds_to_duration("read_timeout_seconds", self.read_timeout_seconds)?,
+            proxy: self.proxy.clone(),
+            root_certificates: self
+                .root_certificates
+                .iter()
+                .map(|pem| pem.as_bytes().to_vec())
+                .collect(),
+            headers: self
+                .headers
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect(),
+            user_agent: self.user_agent.clone(),
+        })
+    }
+}
+
+/// Convert optional `seconds` setting called `name` into `Duration`, failing if the value is
+/// negative, not finite, or too large.
+fn seconds_to_duration(name: &str, seconds: Option<f64>) -> Result<Option<Duration>, String> {
+    seconds
+        .map(|seconds| {
+            Duration::try_from_secs_f64(seconds)
+                .map_err(|err| format!("Invalid value for {name}: {err}"))
+        })
+        .transpose()
+}
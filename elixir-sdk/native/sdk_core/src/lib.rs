@@ -6,10 +6,14 @@ use crate::config::Config;
 use crate::conversion::{convert_attributes, convert_value_term, convert_event_term};
 use crate::assignment::{get_assignment_inner, get_assignment_details_inner};
 use eppo_core::{
+    assignment_cache::AssignmentCache,
+    configuration_cache::ConfigurationCache,
     configuration_fetcher::{ConfigurationFetcher, ConfigurationFetcherConfig},
+    configuration_file::{start_configuration_file_watcher, ConfigurationFileWatcherConfig},
     configuration_poller::{start_configuration_poller, ConfigurationPollerConfig, ConfigurationPoller},
     configuration_store::ConfigurationStore,
     eval::{Evaluator, EvaluatorConfig},
+    timestamp::Timestamp,
     ufc::VariationType,
     SdkMetadata,
     background::BackgroundThread,
@@ -28,9 +32,11 @@ const SDK_METADATA: SdkMetadata = SdkMetadata {
 };
 
 pub struct EppoClient {
+    configuration_store: Arc<ConfigurationStore>,
     evaluator: Evaluator,
     background_thread: BackgroundThread,
-    configuration_poller: ConfigurationPoller,
+    /// `None` in offline mode.
+    configuration_poller: Option<ConfigurationPoller>,
 }
 
 #[rustler::resource_impl]
@@ -43,41 +49,100 @@ impl UnwindSafe for EppoClient {}
 fn init(config: Config) -> NifResult<ResourceArc<EppoClient>> {
     config.validate().map_err(|e| rustler::Error::Term(Box::new(e)))?;
 
-    let store = Arc::new(ConfigurationStore::new());
-    
-    let fetcher_config = ConfigurationFetcherConfig {
-        base_url: config.base_url.clone(),
-        api_key: config.api_key.clone(),
-        sdk_metadata: SDK_METADATA,
-    };
+    let store = Arc::new(ConfigurationStore::with_history_size(
+        config.configuration_history_size,
+    ));
 
-    let fetcher = ConfigurationFetcher::new(fetcher_config);
+    let configuration_files = config.configuration_files();
+
+    let cache = config
+        .configuration_cache_directory
+        .as_ref()
+        .map(ConfigurationCache::new);
+    if let Some(files) = &configuration_files {
+        let configuration = files.load(SDK_METADATA).map_err(|e| {
+            rustler::Error::Term(Box::new(format!("Failed to load configuration files: {}", e)))
+        })?;
+        store.set_configuration(Arc::new(configuration));
+    } else if let Some(cache) = &cache {
+        if let Ok(Some(configuration)) = cache.load(SDK_METADATA) {
+            store.set_configuration(Arc::new(configuration));
+        }
+    }
 
     let background_thread = BackgroundThread::start()
         .map_err(|e| rustler::Error::Term(Box::new(
             format!("Failed to start background thread: {}", e)
         )))?;
 
-    let poller_config = ConfigurationPollerConfig::new()
-        .with_interval(std::time::Duration::from_secs(
-            config.poll_interval_seconds.unwrap_or(30),
-        ))
-        .with_jitter(std::time::Duration::from_secs(config.poll_jitter_seconds));
+    let poller = if let Some(files) = configuration_files {
+        // Offline mode never fetches configuration from the server.
+        if config.watch_configuration_files {
+            start_configuration_file_watcher(
+                background_thread.runtime(),
+                files,
+                store.clone(),
+                SDK_METADATA,
+                ConfigurationFileWatcherConfig::default(),
+            );
+        }
+        None
+    } else {
+        let fetcher_config = ConfigurationFetcherConfig {
+            base_url: config.base_url.clone(),
+            api_key: config.api_key.clone(),
+            sdk_metadata: SDK_METADATA,
+            http_client: config
+                .http_client_config()
+                .map_err(|e| rustler::Error::Term(Box::new(e)))?,
+        };
+
+        let fetcher = ConfigurationFetcher::new(fetcher_config)
+            .map_err(|e| rustler::Error::Term(Box::new(e.to_string())))?;
 
-    let poller = start_configuration_poller(
-        background_thread.runtime(),
-        fetcher,
-        store.clone(),
-        poller_config,
-    );
+        let mut poller_config = ConfigurationPollerConfig::new()
+            .with_interval(std::time::Duration::from_secs(
+                config.poll_interval_seconds.unwrap_or(30),
+            ))
+            .with_jitter(std::time::Duration::from_secs(config.poll_jitter_seconds));
+        if let Some(cache) = cache {
+            poller_config = poller_config.with_cache(cache);
+        }
+
+        Some(start_configuration_poller(
+            background_thread.runtime(),
+            fetcher,
+            store.clone(),
+            poller_config,
+        ))
+    };
 
+    let assignment_cache_ttl = config
+        .assignment_cache_ttl()
+        .map_err(|e| rustler::Error::Term(Box::new(e)))?;
+    let assignment_cache = config.assignment_cache_size.map(|capacity| {
+        let cache = AssignmentCache::new(capacity);
+        let cache = match assignment_cache_ttl {
+            Some(ttl) => cache.with_ttl(ttl),
+            None => cache,
+        };
+        Arc::new(cache)
+    });
 
     let evaluator = Evaluator::new(EvaluatorConfig {
-        configuration_store: store,
+        configuration_store: store.clone(),
         sdk_metadata: SDK_METADATA,
+        assignment_cache,
+        max_configuration_age: config
+            .max_configuration_age()
+            .map_err(|e| rustler::Error::Term(Box::new(e)))?,
+        stale_configuration_policy: config
+            .stale_configuration_policy()
+            .map_err(|e| rustler::Error::Term(Box::new(e)))?,
     });
 
     let client = ResourceArc::new(EppoClient {
+        configuration_store: store,
         evaluator,
         background_thread,
         configuration_poller: poller,
@@ -159,6 +224,11 @@ fn wait_for_initialization(
     client: ResourceArc<EppoClient>,
     timeout_secs: f64,
 ) -> NifResult<()> {
+    let Some(configuration_poller) = &client.configuration_poller else {
+        // Configuration is loaded from files synchronously in offline mode.
+        return Ok(());
+    };
+
     log::info!(target: "eppo", "waiting for initialization");
     
     let _ = client
@@ -168,7 +238,7 @@ fn wait_for_initialization(
         .block_on(async {
                 tokio::time::timeout(
                     Duration::from_secs_f64(timeout_secs),
-                    client.configuration_poller.wait_for_configuration(),
+                    configuration_poller.wait_for_configuration(),
                 )
                 .await
             })
@@ -179,4 +249,111 @@ fn wait_for_initialization(
     Ok(())
 }
 
+/// Configuration poller health, returned to Elixir as a map. Timestamps are ISO 8601 strings.
+#[derive(rustler::NifMap)]
+struct PollerStatus {
+    running: bool,
+    last_success_at: Option<String>,
+    last_error: Option<String>,
+    last_error_at: Option<String>,
+    consecutive_failures: u32,
+    configuration_fetched_at: Option<String>,
+    configuration_created_at: Option<String>,
+    configuration_age_seconds: Option<f64>,
+}
+
+#[rustler::nif]
+fn poller_status(client: ResourceArc<EppoClient>) -> Option<PollerStatus> {
+    let status = client.configuration_poller.as_ref()?.status();
+    let iso8601 = |timestamp: Option<Timestamp>| timestamp.map(|it| it.to_rfc3339());
+    Some(PollerStatus {
+        running: status.running,
+        last_success_at: iso8601(status.last_success_at),
+        last_error: status.last_error.as_ref().map(ToString::to_string),
+        last_error_at: iso8601(status.last_error_at),
+        consecutive_failures: status.consecutive_failures,
+        configuration_fetched_at: iso8601(status.configuration_fetched_at),
+        configuration_created_at: iso8601(status.configuration_created_at),
+        configuration_age_seconds: status.configuration_age().map(|age| age.as_secs_f64()),
+    })
+}
+
+/// A part of flags configuration that failed to parse or compile, returned to Elixir as a map.
+/// `raw` is the JSON fragment of the affected part of configuration.
+#[derive(rustler::NifMap)]
+struct ConfigurationDiagnostic {
+    flag_key: String,
+    allocation_key: Option<String>,
+    variation_key: Option<String>,
+    rule_index: Option<usize>,
+    condition_index: Option<usize>,
+    reason: String,
+    raw: String,
+}
+
+#[rustler::nif]
+fn configuration_diagnostics(client: ResourceArc<EppoClient>) -> Vec<ConfigurationDiagnostic> {
+    let Some(configuration) = client.configuration_store.get_configuration() else {
+        return Vec::new();
+    };
+    configuration
+        .diagnostics()
+        .into_iter()
+        .map(|diagnostic| ConfigurationDiagnostic {
+            flag_key: diagnostic.flag_key.to_string(),
+            allocation_key: diagnostic.allocation_key.map(|it| it.to_string()),
+            variation_key: diagnostic.variation_key.map(|it| it.to_string()),
+            rule_index: diagnostic.rule_index,
+            condition_index: diagnostic.condition_index,
+            reason: diagnostic.reason,
+            raw: diagnostic.raw.to_string(),
+        })
+        .collect()
+}
+
+/// An entry of configuration history, returned to Elixir as a map. Timestamps are ISO 8601
+/// strings.
+#[derive(rustler::NifMap)]
+struct ConfigurationHistoryEntry {
+    version: u64,
+    active: bool,
+    fetched_at: String,
+    created_at: String,
+}
+
+#[rustler::nif]
+fn configuration_history(client: ResourceArc<EppoClient>) -> Vec<ConfigurationHistoryEntry> {
+    client
+        .configuration_store
+        .history()
+        .into_iter()
+        .map(|entry| ConfigurationHistoryEntry {
+            version: entry.version,
+            active: entry.active,
+            fetched_at: entry.configuration.fetched_at().to_rfc3339(),
+            created_at: entry.configuration.created_at().to_rfc3339(),
+        })
+        .collect()
+}
+
+#[rustler::nif]
+fn pin_configuration(client: ResourceArc<EppoClient>) {
+    client.configuration_store.pin();
+}
+
+#[rustler::nif]
+fn rollback_configuration(client: ResourceArc<EppoClient>, version: u64) -> bool {
+    client.configuration_store.rollback(version)
+}
+
+#[rustler::nif]
+fn unpin_configuration(client: ResourceArc<EppoClient>) {
+    client.configuration_store.unpin();
+}
+
+#[rustler::nif]
+fn configuration_pinned(client: ResourceArc<EppoClient>) -> bool {
+    client.configuration_store.is_pinned()
+}
+
 rustler::init!("Elixir.EppoSdk.Core"); 
@@ -50,4 +50,112 @@ defmodule EppoSdk.ClientTest do
 
     assert log =~ "warning"
   end
+
+  test "poller_status reports poller health" do
+    client = Server.get_instance()
+
+    status = Client.poller_status(client)
+
+    assert status.running
+    assert status.consecutive_failures == 0
+    assert %DateTime{} = status.last_success_at
+    assert %DateTime{} = status.configuration_created_at
+    assert status.configuration_age_seconds < 60
+  end
+
+  test "stale configuration returns default values with :return_default policy" do
+    {:ok, client} =
+      Client.new(%Client.Config{
+        api_key: "test-api-key",
+        assignment_logger: EppoSdk.AssignmentLogger,
+        flags_configuration_file: "../sdk-test-data/ufc/flags-v1.json",
+        max_configuration_age_seconds: 0.0,
+        stale_configuration_policy: :return_default
+      })
+
+    {assignment, details} =
+      Client.get_string_assignment_details(client, "new-user-onboarding", "alice", %{}, "default")
+
+    assert assignment == "default"
+    assert details["flagEvaluationCode"] == "CONFIGURATION_STALE"
+  end
+
+  @tag :tmp_dir
+  test "configuration_diagnostics reports invalid conditions", %{tmp_dir: tmp_dir} do
+    condition = %{"attribute" => "email", "operator" => "MATCHES", "value" => "(unclosed"}
+
+    flags_file = Path.join(tmp_dir, "flags.json")
+
+    File.write!(
+      flags_file,
+      Jason.encode!(%{
+        "createdAt" => "2024-09-09T10:18:15.988Z",
+        "environment" => %{"name" => "test"},
+        "flags" => %{
+          "flag" => %{
+            "key" => "flag",
+            "enabled" => true,
+            "variationType" => "BOOLEAN",
+            "variations" => %{"on" => %{"key" => "on", "value" => true}},
+            "allocations" => [
+              %{
+                "key" => "allocation",
+                "rules" => [%{"conditions" => [condition]}],
+                "splits" => [%{"shards" => [], "variationKey" => "on"}]
+              }
+            ],
+            "totalShards" => 10000
+          }
+        }
+      })
+    )
+
+    {:ok, client} =
+      Client.new(%Client.Config{
+        api_key: "test-api-key",
+        assignment_logger: EppoSdk.AssignmentLogger,
+        flags_configuration_file: flags_file
+      })
+
+    assert [diagnostic] = Client.configuration_diagnostics(client)
+    assert diagnostic.flag_key == "flag"
+    assert diagnostic.allocation_key == "allocation"
+    assert diagnostic.rule_index == 0
+    assert diagnostic.condition_index == 0
+    assert diagnostic.reason =~ "regex"
+    assert Jason.decode!(diagnostic.raw) == condition
+  end
+
+  @tag :tmp_dir
+  test "configuration can be pinned and rolled back", %{tmp_dir: tmp_dir} do
+    flags_file = Path.join(tmp_dir, "flags.json")
+
+    File.write!(
+      flags_file,
+      Jason.encode!(%{
+        "createdAt" => "2024-09-09T10:18:15.988Z",
+        "environment" => %{"name" => "test"},
+        "flags" => %{}
+      })
+    )
+
+    {:ok, client} =
+      Client.new(%Client.Config{
+        api_key: "test-api-key",
+        assignment_logger: EppoSdk.AssignmentLogger,
+        flags_configuration_file: flags_file
+      })
+
+    assert [entry] = Client.configuration_history(client)
+    assert entry.active
+    assert %DateTime{} = entry.created_at
+    refute Client.configuration_pinned?(client)
+
+    assert :ok = Client.rollback_configuration(client, entry.version)
+    assert Client.configuration_pinned?(client)
+    assert {:error, :not_found} = Client.rollback_configuration(client, entry.version + 1)
+
+    assert :ok = Client.unpin_configuration(client)
+    refute Client.configuration_pinned?(client)
+  end
 end
@@ -0,0 +1,136 @@
+//! Multi-threaded throughput of configuration reads.
+//!
+//! `rwlock` is the previous `ConfigurationStore` implementation (`RwLock<Option<Arc<_>>>`) and is
+//! kept here as a baseline to compare the current lock-free store against.
+use std::{
+    hint::black_box,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+#[cfg(feature = "ahash")]
+use eppo_core::ahash::{HashMap, HashMapExt};
+#[cfg(not(feature = "ahash"))]
+use std::collections::HashMap;
+
+use eppo_core::{
+    configuration_store::ConfigurationStore,
+    eval::{Evaluator, EvaluatorConfig, StaleConfigurationPolicy},
+    ufc::UniversalFlagConfig,
+    Configuration, SdkMetadata,
+};
+
+const SDK_METADATA: SdkMetadata = SdkMetadata {
+    name: "test",
+    version: "0.1.0",
+};
+
+const THREADS: [u64; 4] = [1, 2, 4, 8];
+
+/// Run `f` `iters` times split across `threads` threads and return the wall-clock time.
+fn run_threads(threads: u64, iters: u64, f: impl Fn() + Sync) -> Duration {
+    let per_thread = iters.div_ceil(threads);
+    let start = Instant::now();
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                for _ in 0..per_thread {
+                    f();
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let flags = UniversalFlagConfig::from_json(
+        SDK_METADATA,
+        std::fs::read("../sdk-test-data/ufc/flags-v1.json").unwrap(),
+    )
+    .unwrap();
+    let configuration = Arc::new(Configuration::from_server_response(flags, None));
+
+    {
+        let mut group = c.benchmark_group("get_configuration");
+        group.throughput(Throughput::Elements(1));
+
+        let rwlock = RwLock::new(Some(configuration.clone()));
+        let store = ConfigurationStore::new();
+        store.set_configuration(configuration.clone());
+
+        for threads in THREADS {
+            group.bench_with_input(
+                BenchmarkId::new("rwlock", threads),
+                &threads,
+                |b, &threads| {
+                    b.iter_custom(|iters| {
+                        run_threads(threads, iters, || {
+                            black_box(rwlock.read().unwrap().clone());
+                        })
+                    })
+                },
+            );
+            group.bench_with_input(
+                BenchmarkId::new("configuration_store", threads),
+                &threads,
+                |b, &threads| {
+                    b.iter_custom(|iters| {
+                        run_threads(threads, iters, || {
+                            black_box(store.get_configuration());
+                        })
+                    })
+                },
+            );
+        }
+        group.finish();
+    }
+
+    {
+        let mut group = c.benchmark_group("evaluator");
+        group.throughput(Throughput::Elements(1));
+
+        let store = Arc::new(ConfigurationStore::new());
+        store.set_configuration(configuration.clone());
+        let evaluator = Evaluator::new(EvaluatorConfig {
+            configuration_store: store,
+            sdk_metadata: SDK_METADATA,
+            assignment_cache: None,
+            max_configuration_age: None,
+            stale_configuration_policy: StaleConfigurationPolicy::Warn,
+        });
+        let attributes = {
+            let mut map = HashMap::new();
+            map.insert("country".into(), "US".into());
+            Arc::new(map)
+        };
+
+        for threads in THREADS {
+            group.bench_with_input(
+                BenchmarkId::new("get_assignment", threads),
+                &threads,
+                |b, &threads| {
+                    b.iter_custom(|iters| {
+                        run_threads(threads, iters, || {
+                            let _ = black_box(evaluator.get_assignment(
+                                black_box("new-user-onboarding"),
+                                black_box(&"subject1".into()),
+                                black_box(&attributes),
+                                black_box(None),
+                            ));
+                        })
+                    })
+                },
+            );
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default().noise_threshold(0.02);
+    targets = criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,229 @@
+//! Deduplication of assignment and bandit events.
+//!
+//! Evaluating the same flag for the same subject usually produces identical assignment events.
+//! Logging all of them is wasteful, so [`AssignmentCache`] remembers recently logged assignments
+//! and suppresses events that were already logged.
+//!
+//! Assignments are keyed on flag and subject. An event is considered a duplicate if the last
+//! logged event for the same flag and subject had the same allocation and variation (or the same
+//! bandit and action for bandit events). When the assigned variation changes, the new event is
+//! logged and replaces the cached one.
+use std::{num::NonZeroUsize, sync::Mutex, time::Duration};
+
+use lru::LruCache;
+
+use crate::{
+    events::{AssignmentEvent, BanditEvent},
+    timestamp::{self, Timestamp},
+    Str,
+};
+
+/// A bounded LRU cache of logged assignment and bandit events.
+///
+/// The cache is thread-safe and is normally shared by [`Evaluator`](crate::eval::Evaluator).
+pub struct AssignmentCache {
+    ttl: Option<chrono::Duration>,
+    assignments: Mutex<LruCache<CacheKey, CacheEntry>>,
+    bandits: Mutex<LruCache<CacheKey, CacheEntry>>,
+}
+
+/// (flag key, subject key)
+type CacheKey = (Str, Str);
+
+struct CacheEntry {
+    /// (allocation, variation) for assignments, (bandit key, action) for bandits.
+    value: (Str, Str),
+    expires_at: Option<Timestamp>,
+}
+
+impl AssignmentCache {
+    /// Default number of entries to keep for each of assignments and bandit actions.
+    pub const DEFAULT_CAPACITY: usize = 10_000;
+
+    /// Create a new cache that holds up to `capacity` assignments and `capacity` bandit actions.
+    /// Least recently used entries are evicted first.
+    pub fn new(capacity: usize) -> AssignmentCache {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        AssignmentCache {
+            ttl: None,
+            assignments: Mutex::new(LruCache::new(capacity)),
+            bandits: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    /// Expire cached entries after `ttl`, so that events are logged again periodically.
+    pub fn with_ttl(mut self, ttl: Duration) -> AssignmentCache {
+        self.ttl = chrono::Duration::from_std(ttl).ok();
+        self
+    }
+
+    /// Check whether the assignment event should be logged and remember it if so.
+    ///
+    /// Returns `false` if an identical assignment was logged recently.
+    pub fn should_log_assignment(&self, event: &AssignmentEvent) -> bool {
+        self.check_and_insert(
+            &self.assignments,
+            (event.base.feature_flag.clone(), event.subject.clone()),
+            (event.base.allocation.clone(), event.base.variation.clone()),
+        )
+    }
+
+    /// Check whether the bandit event should be logged and remember it if so.
+    ///
+    /// Returns `false` if the same action was logged recently.
+    pub fn should_log_bandit_action(&self, event: &BanditEvent) -> bool {
+        self.check_and_insert(
+            &self.bandits,
+            (event.flag_key.clone(), event.subject.clone()),
+            (event.bandit_key.clone(), event.action.clone()),
+        )
+    }
+
+    /// Forget all cached assignments and bandit actions.
+    pub fn clear(&self) {
+        // Our code should never panic while holding the lock, so it's safe to unwrap.
+        self.assignments.lock().unwrap().clear();
+        self.bandits.lock().unwrap().clear();
+    }
+
+    fn check_and_insert(
+        &self,
+        cache: &Mutex<LruCache<CacheKey, CacheEntry>>,
+        key: CacheKey,
+        value: (Str, Str),
+    ) -> bool {
+        let now = timestamp::now();
+        let mut cache = cache.lock().unwrap();
+
+        if let Some(entry) = cache.get(&key) {
+            let expired = entry.expires_at.is_some_and(|expires_at| expires_at <= now);
+            if entry.value == value && !expired {
+                return false;
+            }
+        }
+
+        cache.put(
+            key,
+            CacheEntry {
+                value,
+                expires_at: self.ttl.map(|ttl| now + ttl),
+            },
+        );
+        true
+    }
+}
+
+impl std::fmt::Debug for AssignmentCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AssignmentCache")
+            .field("ttl", &self.ttl)
+            .field("capacity", &self.assignments.lock().unwrap().cap())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use chrono::Utc;
+
+    use crate::{
+        events::{AssignmentEvent, AssignmentEventBase, BanditEvent, EventMetaData},
+        Str,
+    };
+
+    use super::AssignmentCache;
+
+    fn assignment_event(flag: &str, subject: &str, variation: &str) -> AssignmentEvent {
+        AssignmentEvent {
+            base: Arc::new(AssignmentEventBase {
+                feature_flag: flag.into(),
+                allocation: "allocation".into(),
+                experiment: format!("{flag}-allocation"),
+                variation: variation.into(),
+                meta_data: EventMetaData {
+                    sdk_name: "test",
+                    sdk_version: "0.1.0",
+                    core_version: "0.1.0",
+                },
+                extra_logging: Default::default(),
+            }),
+            subject: subject.into(),
+            subject_attributes: Arc::default(),
+            timestamp: Utc::now(),
+            evaluation_details: None,
+        }
+    }
+
+    fn bandit_event(subject: &str, action: &str) -> BanditEvent {
+        BanditEvent {
+            flag_key: "flag".into(),
+            bandit_key: "bandit".into(),
+            subject: Str::from(subject),
+            action: action.into(),
+            action_probability: 1.0,
+            optimality_gap: 0.0,
+            model_version: "v1".into(),
+            timestamp: Utc::now().to_rfc3339(),
+            subject_numeric_attributes: Arc::default(),
+            subject_categorical_attributes: Arc::default(),
+            action_numeric_attributes: Arc::default(),
+            action_categorical_attributes: Arc::default(),
+            meta_data: EventMetaData {
+                sdk_name: "test",
+                sdk_version: "0.1.0",
+                core_version: "0.1.0",
+            },
+        }
+    }
+
+    #[test]
+    fn deduplicates_assignments() {
+        let cache = AssignmentCache::new(10);
+
+        assert!(cache.should_log_assignment(&assignment_event("flag", "alice", "a")));
+        assert!(!cache.should_log_assignment(&assignment_event("flag", "alice", "a")));
+        assert!(cache.should_log_assignment(&assignment_event("flag", "bob", "a")));
+        assert!(cache.should_log_assignment(&assignment_event("other", "alice", "a")));
+
+        // Variation change invalidates the cache.
+        assert!(cache.should_log_assignment(&assignment_event("flag", "alice", "b")));
+        assert!(!cache.should_log_assignment(&assignment_event("flag", "alice", "b")));
+        assert!(cache.should_log_assignment(&assignment_event("flag", "alice", "a")));
+
+        cache.clear();
+        assert!(cache.should_log_assignment(&assignment_event("flag", "alice", "a")));
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = AssignmentCache::new(2);
+
+        assert!(cache.should_log_assignment(&assignment_event("flag", "alice", "a")));
+        assert!(cache.should_log_assignment(&assignment_event("flag", "bob", "a")));
+        assert!(cache.should_log_assignment(&assignment_event("flag", "carol", "a")));
+
+        // alice was evicted.
+        assert!(cache.should_log_assignment(&assignment_event("flag", "alice", "a")));
+        assert!(!cache.should_log_assignment(&assignment_event("flag", "carol", "a")));
+    }
+
+    #[test]
+    fn expires_entries() {
+        let cache = AssignmentCache::new(10).with_ttl(Duration::ZERO);
+
+        assert!(cache.should_log_assignment(&assignment_event("flag", "alice", "a")));
+        assert!(cache.should_log_assignment(&assignment_event("flag", "alice", "a")));
+    }
+
+    #[test]
+    fn deduplicates_bandit_actions() {
+        let cache = AssignmentCache::new(10);
+
+        assert!(cache.should_log_bandit_action(&bandit_event("alice", "nike")));
+        assert!(!cache.should_log_bandit_action(&bandit_event("alice", "nike")));
+        assert!(cache.should_log_bandit_action(&bandit_event("alice", "adidas")));
+        assert!(cache.should_log_bandit_action(&bandit_event("bob", "adidas")));
+    }
+}
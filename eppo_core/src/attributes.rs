@@ -1,4 +1,4 @@
-use std::{borrow::Cow, sync::Arc};
+use std::{borrow::Cow, collections::BTreeMap, sync::Arc};
 
 use serde::{Deserialize, Serialize};
 
@@ -29,6 +29,15 @@ pub type Attributes = HashMap<Str, AttributeValue>;
 /// categorical). Storing kind is helpful to make `Attributes` ↔ `ContextAttributes` conversion
 /// isomorphic.
 ///
+/// Attribute can also be a list of categorical values (e.g., user roles), which can be targeted
+/// with `CONTAINS_ANY`, `CONTAINS_ALL`, and `CONTAINS_NONE` conditions. Other conditions never
+/// match list attributes, and bandits ignore them.
+///
+/// Attribute can also be a nested object (e.g., `{"device": {"os": {"version": "17.2"}}}`), whose
+/// values are addressed in conditions by a dotted path (`device.os.version`) or a JSON pointer
+/// (`/device/os/version`). Conditions never match object attributes directly. Bandits flatten
+/// nested objects into dotted keys (`device.os.version`).
+///
 /// Note that attribute kind is stripped during serialization, so Attribute → JSON → Attribute
 /// conversion is lossy.
 #[derive(Debug, Clone, PartialEq, PartialOrd, derive_more::From, Serialize, Deserialize)]
@@ -42,6 +51,10 @@ enum AttributeValueImpl {
     #[from(CategoricalAttribute, Str, bool, String, &str, Arc<str>, Arc<String>, Cow<'_, str>)]
     Categorical(CategoricalAttribute),
     #[from(ignore)]
+    List(Arc<[CategoricalAttribute]>),
+    #[from(ignore)]
+    Object(Arc<BTreeMap<Str, AttributeValue>>),
+    #[from(ignore)]
     Null,
 }
 
@@ -57,11 +70,19 @@ impl serde::Serialize for AttributeValueImpl {
             AttributeValueImpl::Categorical(categorical_attribute) => {
                 categorical_attribute.serialize(serializer)
             }
+            AttributeValueImpl::List(values) => values.serialize(serializer),
+            AttributeValueImpl::Object(values) => values.serialize(serializer),
             AttributeValueImpl::Null => serializer.serialize_none(),
         }
     }
 }
 
+impl<T: Into<CategoricalAttribute>> From<Vec<T>> for AttributeValue {
+    fn from(values: Vec<T>) -> AttributeValue {
+        AttributeValue::list(values)
+    }
+}
+
 impl AttributeValue {
     /// Create a numeric attribute.
     #[inline]
@@ -75,6 +96,27 @@ impl AttributeValue {
         AttributeValue(AttributeValueImpl::Categorical(value.into()))
     }
 
+    /// Create a list attribute.
+    pub fn list(
+        values: impl IntoIterator<Item = impl Into<CategoricalAttribute>>,
+    ) -> AttributeValue {
+        AttributeValue(AttributeValueImpl::List(
+            values.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    /// Create a nested object attribute.
+    pub fn object(
+        values: impl IntoIterator<Item = (impl Into<Str>, impl Into<AttributeValue>)>,
+    ) -> AttributeValue {
+        AttributeValue(AttributeValueImpl::Object(Arc::new(
+            values
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        )))
+    }
+
     #[inline]
     pub const fn null() -> AttributeValue {
         AttributeValue(AttributeValueImpl::Null)
@@ -116,6 +158,25 @@ impl AttributeValue {
         }
     }
 
+    /// Returns items of a list attribute. Scalar attributes are treated as a single-item list.
+    /// Returns `None` for null attributes.
+    pub(crate) fn coerce_to_string_list(&self) -> Option<Vec<Cow<'_, str>>> {
+        match self {
+            AttributeValue(AttributeValueImpl::List(values)) => {
+                Some(values.iter().map(|it| it.to_str()).collect())
+            }
+            _ => self.coerce_to_string().map(|it| vec![it]),
+        }
+    }
+
+    /// Returns value of `key` if this is an object attribute.
+    pub(crate) fn get(&self, key: &str) -> Option<&AttributeValue> {
+        match self {
+            AttributeValue(AttributeValueImpl::Object(values)) => values.get(key),
+            _ => None,
+        }
+    }
+
     fn as_attribute_value<'a>(&'a self) -> Option<AttributeValueRef<'a>> {
         self.into()
     }
@@ -193,7 +254,9 @@ impl<'a> From<&'a AttributeValue> for Option<AttributeValueRef<'a>> {
             AttributeValue(AttributeValueImpl::Categorical(categorical)) => {
                 Some(AttributeValueRef::from(categorical))
             }
-            AttributeValue(AttributeValueImpl::Null) => None,
+            AttributeValue(AttributeValueImpl::List(_))
+            | AttributeValue(AttributeValueImpl::Object(_))
+            | AttributeValue(AttributeValueImpl::Null) => None,
         }
     }
 }
@@ -241,6 +304,19 @@ mod pyo3_impl {
                 AttributeValue(AttributeValueImpl::Categorical(categorical)) => {
                     categorical.into_pyobject(py).map(|it| it.into_any())
                 }
+                AttributeValue(AttributeValueImpl::List(values)) => {
+                    Ok(PyList::new(py, values.iter())
+                        .expect("categorical attributes should always convert to Python")
+                        .into_any())
+                }
+                AttributeValue(AttributeValueImpl::Object(values)) => {
+                    let dict = PyDict::new(py);
+                    for (key, value) in values.iter() {
+                        dict.set_item(key, value)
+                            .expect("attributes should always convert to Python");
+                    }
+                    Ok(dict.into_any())
+                }
                 AttributeValue(AttributeValueImpl::Null) => Ok(py.None().into_bound(py).into_any()),
             }
         }
@@ -298,6 +374,24 @@ mod pyo3_impl {
             if value.is_none() {
                 return Ok(AttributeValue::null());
             }
+            if value.is_instance_of::<PyList>()
+                || value.is_instance_of::<PyTuple>()
+                || value.is_instance_of::<PySet>()
+                || value.is_instance_of::<PyFrozenSet>()
+            {
+                let values = value
+                    .try_iter()?
+                    .map(|item| item?.extract::<CategoricalAttribute>())
+                    .collect::<PyResult<Vec<_>>>()?;
+                return Ok(AttributeValue::list(values));
+            }
+            if let Ok(dict) = value.cast::<PyDict>() {
+                let values = dict
+                    .iter()
+                    .map(|(key, value)| Ok((key.extract::<Str>()?, value.extract()?)))
+                    .collect::<PyResult<Vec<(Str, AttributeValue)>>>()?;
+                return Ok(AttributeValue::object(values));
+            }
             Err(PyTypeError::new_err(
                 "invalid type for subject attribute value",
             ))
@@ -338,7 +432,9 @@ mod pyo3_impl {
 
 #[cfg(feature = "magnus")]
 mod magnus_impl {
-    use magnus::{value::ReprValue, RString, Ruby, TryConvert};
+    use magnus::{value::ReprValue, RArray, RHash, RString, Ruby, TryConvert};
+
+    use std::sync::Arc;
 
     use crate::{AttributeValue, CategoricalAttribute, NumericAttribute};
 
@@ -351,6 +447,17 @@ mod magnus_impl {
                     CategoricalAttribute::try_convert(val)
                         .map(|it| Self(AttributeValueImpl::Categorical(it)))
                 })
+                .or_else(|err| match RArray::from_value(val) {
+                    Some(array) => array
+                        .to_vec::<CategoricalAttribute>()
+                        .map(AttributeValue::list),
+                    None => Err(err),
+                })
+                .or_else(|err| match RHash::from_value(val) {
+                    Some(hash) => serde_magnus::deserialize(&Ruby::get_with(val), hash)
+                        .map(|values| Self(AttributeValueImpl::Object(Arc::new(values)))),
+                    None => Err(err),
+                })
                 .or_else(|_|
                 // Return null attribute as a fallback
                 Ok(Self(AttributeValueImpl::Null)))
@@ -400,6 +507,15 @@ mod rustler_impl {
                     CategoricalAttributeImpl::Number(n) => n.encode(env),
                     CategoricalAttributeImpl::Boolean(b) => b.encode(env),
                 },
+                AttributeValueImpl::List(values) => values
+                    .iter()
+                    .map(|value| AttributeValue::categorical(value.clone()))
+                    .collect::<Vec<_>>()
+                    .encode(env),
+                AttributeValueImpl::Object(values) => {
+                    let pairs = values.iter().collect::<Vec<_>>();
+                    Term::map_from_pairs(env, &pairs).expect("object attribute keys are unique")
+                }
                 AttributeValueImpl::Null => rustler::types::atom::nil().encode(env),
             }
         }
@@ -419,6 +535,30 @@ mod rustler_impl {
             if let Ok(s) = String::decode(term) {
                 return Ok(AttributeValue::categorical(s));
             }
+            if let Ok(items) = Vec::<Term>::decode(term) {
+                let values = items
+                    .into_iter()
+                    .map(|item| match AttributeValue::decode(item)? {
+                        AttributeValue(AttributeValueImpl::Categorical(value)) => Ok(value),
+                        AttributeValue(AttributeValueImpl::Numeric(value)) => Ok(value.0.into()),
+                        _ => Err(rustler::Error::BadArg),
+                    })
+                    .collect::<NifResult<Vec<_>>>()?;
+                return Ok(AttributeValue::list(values));
+            }
+            if let Some(entries) = rustler::types::map::MapIterator::new(term) {
+                let values = entries
+                    .map(|(key, value)| {
+                        // Keys may be either strings or atoms (e.g., `%{os: "ios"}`).
+                        let key = match Str::decode(key) {
+                            Ok(key) => key,
+                            Err(_) => key.atom_to_string()?.into(),
+                        };
+                        Ok((key, AttributeValue::decode(value)?))
+                    })
+                    .collect::<NifResult<Vec<_>>>()?;
+                return Ok(AttributeValue::object(values));
+            }
             Ok(AttributeValue::null())
         }
     }
@@ -437,3 +577,101 @@ mod rustler_impl {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{AttributeValue, Attributes, CategoricalAttribute, ContextAttributes};
+
+    #[test]
+    fn list_attributes_round_trip_through_json() {
+        let attributes: Attributes =
+            serde_json::from_str(r#"{"roles": ["admin", 42, true], "age": 30}"#).unwrap();
+        assert_eq!(
+            attributes["roles"],
+            AttributeValue::list([
+                CategoricalAttribute::from("admin"),
+                42.0.into(),
+                true.into()
+            ])
+        );
+        assert_eq!(
+            serde_json::to_value(&attributes["roles"]).unwrap(),
+            serde_json::json!(["admin", 42.0, true])
+        );
+
+        let context = ContextAttributes::from(attributes);
+        assert!(!context.categorical.contains_key("roles"));
+        assert!(context.numeric.contains_key("age"));
+    }
+
+    #[test]
+    fn object_attributes_round_trip_through_json() {
+        let attributes: Attributes = serde_json::from_str(
+            r#"{"device": {"os": {"name": "ios", "version": 17}, "tags": ["beta"], "model": null}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            attributes["device"],
+            AttributeValue::object([
+                (
+                    "os",
+                    AttributeValue::object([
+                        ("name", AttributeValue::from("ios")),
+                        ("version", 17.0.into())
+                    ])
+                ),
+                ("tags", vec!["beta"].into()),
+                ("model", AttributeValue::null()),
+            ])
+        );
+        assert_eq!(
+            serde_json::to_value(&attributes["device"]).unwrap(),
+            serde_json::json!({
+                "os": {"name": "ios", "version": 17.0},
+                "tags": ["beta"],
+                "model": null,
+            })
+        );
+
+        let context = ContextAttributes::from(attributes);
+        assert_eq!(
+            context.categorical.get("device.os.name"),
+            Some(&CategoricalAttribute::from("ios"))
+        );
+        assert!(context.numeric.contains_key("device.os.version"));
+        assert!(!context.categorical.contains_key("device"));
+        assert!(!context.categorical.contains_key("device.tags"));
+        assert!(!context.categorical.contains_key("device.model"));
+    }
+
+    #[test]
+    fn flat_keys_win_over_flattened_objects() {
+        let flat = ("device.os", AttributeValue::from("flat"));
+        let nested = (
+            "device",
+            AttributeValue::object([
+                ("os", AttributeValue::from("nested")),
+                ("version", 17.0.into()),
+            ]),
+        );
+
+        for context in [
+            ContextAttributes::from_iter([flat.clone(), nested.clone()]),
+            ContextAttributes::from_iter([nested, flat]),
+        ] {
+            assert_eq!(
+                context.categorical.get("device.os"),
+                Some(&CategoricalAttribute::from("flat"))
+            );
+            assert!(context.numeric.contains_key("device.version"));
+        }
+
+        // Numeric flat keys win too.
+        let context = ContextAttributes::from_iter([
+            ("device", AttributeValue::object([("os", "nested")])),
+            ("device.os", 1.0.into()),
+        ]);
+        assert!(!context.categorical.contains_key("device.os"));
+        assert!(context.numeric.contains_key("device.os"));
+    }
+}
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use serde::{Deserialize, Serialize};
 
@@ -9,6 +9,10 @@ use super::{
 };
 
 /// `ContextAttributes` are subject or action attributes split by their semantics.
+///
+/// List attributes are ignored when converting [`Attributes`] to `ContextAttributes`. Nested
+/// object attributes are flattened into dotted keys, so `{"device": {"os": "ios"}}` becomes a
+/// categorical `device.os` attribute.
 // TODO(oleksii): I think we should hide fields of this type and maybe the whole type itself. Now
 // with `Attributes` being able to faithfully represent numeric and categorical attributes, there's
 // little reason for users of eppo_core to know about `ContextAttributes`, so it makes sense to hide
@@ -41,23 +45,24 @@ where
     V: Into<AttributeValue>,
 {
     fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
-        let (categorical, numeric) = iter.into_iter().fold(
-            (HashMap::new(), HashMap::new()),
-            |(mut categorical, mut numeric), (key, value)| {
-                match value.into() {
-                    AttributeValue(AttributeValueImpl::Categorical(value)) => {
-                        categorical.insert(key.into(), value);
-                    }
-                    AttributeValue(AttributeValueImpl::Numeric(value)) => {
-                        numeric.insert(key.into(), value);
-                    }
-                    AttributeValue(AttributeValueImpl::Null) => {
-                        // Nulls are missing values and are ignored.
-                    }
+        let mut categorical = HashMap::new();
+        let mut numeric = HashMap::new();
+        let mut objects = Vec::new();
+        for (key, value) in iter {
+            match value.into() {
+                AttributeValue(AttributeValueImpl::Object(values)) => {
+                    objects.push((key.into(), values))
                 }
-                (categorical, numeric)
-            },
-        );
+                value => insert(&mut categorical, &mut numeric, key.into(), value),
+            }
+        }
+        // Flattened keys never replace flat keys with the same name, e.g., `"device.os"` takes
+        // precedence over `"device": {"os": ...}`. Objects are flattened in key order, so that
+        // collisions between nested keys are resolved deterministically.
+        objects.sort_by(|(a, _), (b, _): &(Str, _)| a.cmp(b));
+        for (key, values) in objects {
+            insert_flattened(&mut categorical, &mut numeric, &key, &values);
+        }
         ContextAttributes {
             numeric: Arc::new(numeric),
             categorical: Arc::new(categorical),
@@ -65,6 +70,47 @@ where
     }
 }
 
+fn insert(
+    categorical: &mut HashMap<Str, CategoricalAttribute>,
+    numeric: &mut HashMap<Str, NumericAttribute>,
+    key: Str,
+    value: AttributeValue,
+) {
+    match value {
+        AttributeValue(AttributeValueImpl::Categorical(value)) => {
+            categorical.insert(key, value);
+        }
+        AttributeValue(AttributeValueImpl::Numeric(value)) => {
+            numeric.insert(key, value);
+        }
+        AttributeValue(AttributeValueImpl::Null) => {
+            // Nulls are missing values and are ignored.
+        }
+        AttributeValue(AttributeValueImpl::List(_)) => {
+            // Bandit models have no coefficients for lists, so list attributes are ignored.
+        }
+        AttributeValue(AttributeValueImpl::Object(values)) => {
+            insert_flattened(categorical, numeric, &key, &values);
+        }
+    }
+}
+
+/// Insert values of an object attribute under dot-separated keys (`{key}.{nested_key}`). Keys that
+/// are already present are not replaced.
+fn insert_flattened(
+    categorical: &mut HashMap<Str, CategoricalAttribute>,
+    numeric: &mut HashMap<Str, NumericAttribute>,
+    key: &str,
+    values: &BTreeMap<Str, AttributeValue>,
+) {
+    for (nested_key, value) in values {
+        let key: Str = format!("{key}.{nested_key}").into();
+        if !categorical.contains_key(&key) && !numeric.contains_key(&key) {
+            insert(categorical, numeric, key, value.clone());
+        }
+    }
+}
+
 impl ContextAttributes {
     /// Convert contextual attributes to generic `Attributes`.
     pub fn to_generic_attributes(&self) -> Attributes {
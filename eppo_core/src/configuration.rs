@@ -1,11 +1,11 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, sync::Arc};
 
 use chrono::{DateTime, Utc};
 
 use crate::{
     bandits::{BanditConfiguration, BanditResponse},
     ufc::UniversalFlagConfig,
-    Str,
+    Error, EvaluationError, Result, SdkMetadata, Str,
 };
 
 /// Hashing algorithm to use for bandit evaluation.
@@ -16,14 +16,19 @@ pub(crate) enum BanditHashingAlgorithm {
 }
 
 /// Remote configuration for the eppo client. It's a central piece that defines client behavior.
-#[derive(Debug)]
+///
+/// Cloning is cheap as flags and bandits configurations are reference-counted.
+#[derive(Debug, Clone)]
 pub struct Configuration {
     /// Timestamp when configuration was fetched by the SDK.
     pub(crate) fetched_at: DateTime<Utc>,
     /// Flags configuration.
-    pub(crate) flags: UniversalFlagConfig,
+    ///
+    /// Flags and bandits are reference-counted, so that a new configuration can reuse parts that
+    /// have not changed since the previous fetch.
+    pub(crate) flags: Arc<UniversalFlagConfig>,
     /// Bandits configuration.
-    pub(crate) bandits: Option<BanditResponse>,
+    pub(crate) bandits: Option<Arc<BanditResponse>>,
     /// Hashing algorithm for bandit evaluation.
     pub(crate) bandit_hashing_algorithm: BanditHashingAlgorithm,
 }
@@ -33,6 +38,33 @@ impl Configuration {
     pub fn from_server_response(
         config: UniversalFlagConfig,
         bandits: Option<BanditResponse>,
+    ) -> Configuration {
+        Configuration::from_parts(Arc::new(config), bandits.map(Arc::new))
+    }
+
+    /// Parse configuration from flags and (optional) bandits JSON in the format served by Eppo
+    /// servers.
+    pub fn from_json(
+        sdk_metadata: SdkMetadata,
+        flags: Vec<u8>,
+        bandits: Option<&[u8]>,
+    ) -> Result<Configuration> {
+        let flags = UniversalFlagConfig::from_json(sdk_metadata, flags)?;
+        let bandits = bandits
+            .map(serde_json::from_slice::<BanditResponse>)
+            .transpose()
+            .map_err(|err| {
+                log::warn!(target: "eppo", "failed to parse bandits configuration: {err:?}");
+                Error::EvaluationError(EvaluationError::UnexpectedConfigurationParseError)
+            })?;
+        Ok(Configuration::from_server_response(flags, bandits))
+    }
+
+    /// Create a new configuration from already parsed (and possibly shared) flags and bandits
+    /// configurations.
+    pub(crate) fn from_parts(
+        flags: Arc<UniversalFlagConfig>,
+        bandits: Option<Arc<BanditResponse>>,
     ) -> Configuration {
         let now = Utc::now();
 
@@ -50,7 +82,7 @@ impl Configuration {
 
         Configuration {
             fetched_at: now,
-            flags: config,
+            flags,
             bandits,
             bandit_hashing_algorithm,
         }
@@ -73,6 +105,16 @@ impl Configuration {
         self.bandits.as_ref()?.bandits.get(bandit_key)
     }
 
+    /// Returns when configuration was fetched by the SDK.
+    pub fn fetched_at(&self) -> DateTime<Utc> {
+        self.fetched_at
+    }
+
+    /// Returns when configuration was created on the server.
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.flags.compiled.created_at
+    }
+
     /// Returns an iterator over all flag keys. Note that this may return both disabled flags and
     /// flags with bad configuration. Mostly useful for debugging.
     pub fn flag_keys(&self) -> impl Iterator<Item = &Str> {
@@ -0,0 +1,1123 @@
+//! A typed builder for [`Configuration`].
+//!
+//! Building a configuration by hand normally requires writing the UFC (Universal Flag
+//! Configuration) JSON served by Eppo. [`ConfigurationBuilder`] provides a typed alternative that
+//! is mostly useful in tests: it emits the same wire JSON the server would send and compiles it
+//! into a [`Configuration`].
+//!
+//! # Examples
+//!
+//! ```
+//! # use eppo_core::configuration_builder::*;
+//! # use eppo_core::ufc::VariationType;
+//! let configuration = ConfigurationBuilder::new()
+//!     .flag(
+//!         FlagBuilder::new("new-checkout", VariationType::Boolean)
+//!             .variation("on", true)
+//!             .variation("off", false)
+//!             .allocation(
+//!                 AllocationBuilder::new("internal-users")
+//!                     .rule(
+//!                         RuleBuilder::new()
+//!                             .condition(ConditionBuilder::matches("email", "@example\\.com$")),
+//!                     )
+//!                     .split(SplitBuilder::new("on")),
+//!             )
+//!             .allocation(
+//!                 AllocationBuilder::new("rollout").split(
+//!                     SplitBuilder::new("on").shard("new-checkout-salt", [0..5000]),
+//!                 ),
+//!             ),
+//!     )
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(configuration.flag_keys().count(), 1);
+//! ```
+use std::ops::Range;
+
+use chrono::SecondsFormat;
+
+use crate::{
+    bandits::{
+        BanditCategoricalAttributeCoefficient, BanditCoefficients, BanditConfiguration,
+        BanditModelData, BanditNumericAttributeCoefficient, BanditResponse,
+    },
+    hashmap::HashMap,
+    timestamp::{self, Timestamp},
+    ufc::{
+        AllocationWire, BanditVariationWire, ConditionOperator, ConditionValue, ConditionWire,
+        Environment, FlagWire, RuleWire, ShardRange, ShardWire, SplitWire, TryParse,
+        UniversalFlagConfig, UniversalFlagConfigWire, ValueWire, VariationType, VariationWire,
+    },
+    Configuration, Result, SdkMetadata, Str,
+};
+
+/// Builder for [`Configuration`]. See [module documentation](self) for an example.
+#[derive(Debug, Clone)]
+pub struct ConfigurationBuilder {
+    sdk_metadata: SdkMetadata,
+    created_at: Timestamp,
+    environment: Str,
+    flags: Vec<FlagBuilder>,
+    bandits: Vec<BanditBuilder>,
+}
+
+/// Builder for a single flag.
+#[derive(Debug, Clone)]
+pub struct FlagBuilder {
+    key: Str,
+    enabled: bool,
+    variation_type: VariationType,
+    total_shards: u32,
+    variations: Vec<(Str, serde_json::Value)>,
+    allocations: Vec<AllocationBuilder>,
+    /// Pairs of (variation key, bandit key).
+    bandit_variations: Vec<(Str, Str)>,
+}
+
+/// Builder for a flag allocation.
+#[derive(Debug, Clone)]
+pub struct AllocationBuilder {
+    key: Str,
+    rules: Vec<RuleBuilder>,
+    start_at: Option<Timestamp>,
+    end_at: Option<Timestamp>,
+    splits: Vec<SplitBuilder>,
+    do_log: bool,
+}
+
+/// Builder for an allocation rule. A rule matches if all of its conditions match.
+#[derive(Debug, Clone, Default)]
+pub struct RuleBuilder {
+    conditions: Vec<ConditionBuilder>,
+}
+
+/// A single rule condition.
+#[derive(Debug, Clone)]
+pub struct ConditionBuilder {
+    wire: ConditionWire,
+}
+
+/// Builder for an allocation split.
+#[derive(Debug, Clone)]
+pub struct SplitBuilder {
+    variation_key: Str,
+    shards: Vec<ShardWire>,
+    extra_logging: HashMap<String, String>,
+}
+
+/// Builder for a bandit model.
+#[derive(Debug, Clone)]
+pub struct BanditBuilder {
+    key: Str,
+    model_name: String,
+    model_version: Str,
+    updated_at: Timestamp,
+    gamma: f64,
+    default_action_score: f64,
+    action_probability_floor: f64,
+    actions: Vec<BanditActionBuilder>,
+}
+
+/// Builder for coefficients of a single bandit action.
+#[derive(Debug, Clone)]
+pub struct BanditActionBuilder {
+    coefficients: BanditCoefficients,
+}
+
+impl Default for ConfigurationBuilder {
+    fn default() -> Self {
+        ConfigurationBuilder::new()
+    }
+}
+
+impl ConfigurationBuilder {
+    /// Create an empty configuration for the "Test" environment.
+    pub fn new() -> ConfigurationBuilder {
+        ConfigurationBuilder {
+            sdk_metadata: SdkMetadata {
+                name: "eppo_core",
+                version: env!("CARGO_PKG_VERSION"),
+            },
+            created_at: timestamp::now(),
+            environment: "Test".into(),
+            flags: Vec::new(),
+            bandits: Vec::new(),
+        }
+    }
+
+    /// Set SDK metadata attached to assignment events produced by the configuration.
+    pub fn sdk_metadata(mut self, sdk_metadata: SdkMetadata) -> Self {
+        self.sdk_metadata = sdk_metadata;
+        self
+    }
+
+    /// Set the time the configuration was published. Defaults to the time the builder was
+    /// created.
+    pub fn created_at(mut self, created_at: Timestamp) -> Self {
+        self.created_at = created_at;
+        self
+    }
+
+    /// Set environment name.
+    pub fn environment(mut self, name: impl Into<Str>) -> Self {
+        self.environment = name.into();
+        self
+    }
+
+    /// Add a flag. Adding a flag with the same key twice replaces the previous flag.
+    pub fn flag(mut self, flag: FlagBuilder) -> Self {
+        self.flags.retain(|it| it.key != flag.key);
+        self.flags.push(flag);
+        self
+    }
+
+    /// Add a bandit model. Bandits are associated with flag variations with
+    /// [`FlagBuilder::bandit_variation()`].
+    pub fn bandit(mut self, bandit: BanditBuilder) -> Self {
+        self.bandits.retain(|it| it.key != bandit.key);
+        self.bandits.push(bandit);
+        self
+    }
+
+    /// Serialize flags configuration to the wire JSON format (as served by the UFC endpoint).
+    pub fn to_flags_json(&self) -> Vec<u8> {
+        let mut bandits: HashMap<Str, Vec<BanditVariationWire>> = HashMap::default();
+        for flag in &self.flags {
+            for (variation_key, bandit_key) in &flag.bandit_variations {
+                let variation_value = flag
+                    .variations
+                    .iter()
+                    .find(|(key, _)| key == variation_key)
+                    .and_then(|(_, value)| value.as_str())
+                    .map_or_else(|| variation_key.clone(), Str::from);
+                bandits
+                    .entry(bandit_key.clone())
+                    .or_default()
+                    .push(BanditVariationWire {
+                        key: bandit_key.clone(),
+                        flag_key: flag.key.clone(),
+                        variation_key: variation_key.clone(),
+                        variation_value,
+                    });
+            }
+        }
+
+        let wire = UniversalFlagConfigWire {
+            created_at: self.created_at,
+            format: None,
+            environment: Environment {
+                name: self.environment.clone(),
+            },
+            flags: self
+                .flags
+                .iter()
+                .map(|flag| (flag.key.clone(), TryParse::Parsed(flag.to_wire())))
+                .collect(),
+            bandits,
+        };
+
+        serde_json::to_vec(&wire).expect("flags configuration should always serialize")
+    }
+
+    /// Serialize bandit models to the wire JSON format (as served by the bandits endpoint).
+    ///
+    /// Returns `None` if no bandit models were added.
+    pub fn to_bandits_json(&self) -> Option<Vec<u8>> {
+        let response = self.to_bandit_response()?;
+        Some(serde_json::to_vec(&response).expect("bandits configuration should always serialize"))
+    }
+
+    /// Compile configuration.
+    pub fn build(&self) -> Result<Configuration> {
+        let flags = UniversalFlagConfig::from_json(self.sdk_metadata, self.to_flags_json())?;
+        Ok(Configuration::from_server_response(
+            flags,
+            self.to_bandit_response(),
+        ))
+    }
+
+    fn to_bandit_response(&self) -> Option<BanditResponse> {
+        if self.bandits.is_empty() {
+            return None;
+        }
+        Some(BanditResponse {
+            bandits: self
+                .bandits
+                .iter()
+                .map(|bandit| (bandit.key.clone(), bandit.to_configuration()))
+                .collect(),
+            updated_at: self.created_at,
+        })
+    }
+}
+
+impl FlagBuilder {
+    /// Create an enabled flag with no variations and allocations.
+    pub fn new(key: impl Into<Str>, variation_type: VariationType) -> FlagBuilder {
+        FlagBuilder {
+            key: key.into(),
+            enabled: true,
+            variation_type,
+            total_shards: 10000,
+            variations: Vec::new(),
+            allocations: Vec::new(),
+            bandit_variations: Vec::new(),
+        }
+    }
+
+    /// Enable or disable the flag.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Set total number of shards. Defaults to 10000.
+    pub fn total_shards(mut self, total_shards: u32) -> Self {
+        self.total_shards = total_shards;
+        self
+    }
+
+    /// Add a variation.
+    ///
+    /// For `JSON` flags, `value` may be an arbitrary JSON value. For other flag types, `value`
+    /// should be a string, number, or boolean matching the flag's variation type; mismatching
+    /// values make the flag fail compilation the same way invalid server configuration would.
+    pub fn variation(mut self, key: impl Into<Str>, value: impl Into<serde_json::Value>) -> Self {
+        let key = key.into();
+        self.variations.retain(|(it, _)| *it != key);
+        self.variations.push((key, value.into()));
+        self
+    }
+
+    /// Add an allocation. Allocations are evaluated in the order they are added.
+    pub fn allocation(mut self, allocation: AllocationBuilder) -> Self {
+        self.allocations.push(allocation);
+        self
+    }
+
+    /// Associate a variation of this flag with a bandit.
+    pub fn bandit_variation(
+        mut self,
+        variation_key: impl Into<Str>,
+        bandit_key: impl Into<Str>,
+    ) -> Self {
+        self.bandit_variations
+            .push((variation_key.into(), bandit_key.into()));
+        self
+    }
+
+    fn to_wire(&self) -> FlagWire {
+        FlagWire {
+            key: self.key.clone(),
+            enabled: self.enabled,
+            variation_type: self.variation_type,
+            variations: self
+                .variations
+                .iter()
+                .map(|(key, value)| {
+                    (
+                        key.to_string(),
+                        VariationWire {
+                            key: key.clone(),
+                            value: variation_value_wire(self.variation_type, value),
+                        },
+                    )
+                })
+                .collect(),
+            allocations: self.allocations.iter().map(|it| it.to_wire()).collect(),
+            total_shards: self.total_shards,
+        }
+    }
+}
+
+impl AllocationBuilder {
+    /// Create an allocation with no rules and no splits.
+    pub fn new(key: impl Into<Str>) -> AllocationBuilder {
+        AllocationBuilder {
+            key: key.into(),
+            rules: Vec::new(),
+            start_at: None,
+            end_at: None,
+            splits: Vec::new(),
+            do_log: true,
+        }
+    }
+
+    /// Add a rule. The allocation matches if any of its rules match (or if it has no rules).
+    pub fn rule(mut self, rule: RuleBuilder) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Only match subjects at or after `start_at`.
+    pub fn start_at(mut self, start_at: Timestamp) -> Self {
+        self.start_at = Some(start_at);
+        self
+    }
+
+    /// Only match subjects before `end_at`.
+    pub fn end_at(mut self, end_at: Timestamp) -> Self {
+        self.end_at = Some(end_at);
+        self
+    }
+
+    /// Add a split.
+    pub fn split(mut self, split: SplitBuilder) -> Self {
+        self.splits.push(split);
+        self
+    }
+
+    /// Set whether assignments from this allocation should be logged. Defaults to `true`.
+    pub fn do_log(mut self, do_log: bool) -> Self {
+        self.do_log = do_log;
+        self
+    }
+
+    fn to_wire(&self) -> AllocationWire {
+        AllocationWire {
+            key: self.key.clone(),
+            rules: self.rules.iter().map(|it| it.to_wire()).collect(),
+            start_at: self.start_at,
+            end_at: self.end_at,
+            splits: self.splits.iter().map(|it| it.to_wire()).collect(),
+            do_log: self.do_log,
+        }
+    }
+}
+
+impl RuleBuilder {
+    /// Create a rule with no conditions.
+    pub fn new() -> RuleBuilder {
+        RuleBuilder::default()
+    }
+
+    /// Add a condition.
+    pub fn condition(mut self, condition: ConditionBuilder) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    fn to_wire(&self) -> RuleWire {
+        RuleWire {
+            conditions: self
+                .conditions
+                .iter()
+                .map(|it| {
+                    // Serialize conditions as the server would, so that invalid conditions (e.g.,
+                    // bad regex) fail parsing the same way.
+                    let value =
+                        serde_json::to_value(&it.wire).expect("condition should always serialize");
+                    serde_json::from_value(value.clone()).unwrap_or(TryParse::ParseFailed(value))
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ConditionBuilder {
+    fn new(
+        attribute: impl Into<String>,
+        operator: ConditionOperator,
+        value: ConditionValue,
+    ) -> ConditionBuilder {
+        ConditionBuilder {
+            wire: ConditionWire {
+                attribute: attribute.into().into_boxed_str(),
+                operator,
+                value,
+            },
+        }
+    }
+
+    /// Attribute matches regex.
+    pub fn matches(attribute: impl Into<String>, regex: impl Into<Str>) -> ConditionBuilder {
+        Self::new(
+            attribute,
+            ConditionOperator::Matches,
+            ValueWire::String(regex.into()).into(),
+        )
+    }
+
+    /// Attribute does not match regex.
+    pub fn not_matches(attribute: impl Into<String>, regex: impl Into<Str>) -> ConditionBuilder {
+        Self::new(
+            attribute,
+            ConditionOperator::NotMatches,
+            ValueWire::String(regex.into()).into(),
+        )
+    }
+
+    /// Attribute is greater than or equal to `value`. `value` should be a number or a semver
+    /// string.
+    pub fn gte(attribute: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        Self::new(
+            attribute,
+            ConditionOperator::Gte,
+            scalar_value(value.into()),
+        )
+    }
+
+    /// Attribute is greater than `value`. `value` should be a number or a semver string.
+    pub fn gt(attribute: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        Self::new(attribute, ConditionOperator::Gt, scalar_value(value.into()))
+    }
+
+    /// Attribute is less than or equal to `value`. `value` should be a number or a semver string.
+    pub fn lte(attribute: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        Self::new(
+            attribute,
+            ConditionOperator::Lte,
+            scalar_value(value.into()),
+        )
+    }
+
+    /// Attribute is less than `value`. `value` should be a number or a semver string.
+    pub fn lt(attribute: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        Self::new(attribute, ConditionOperator::Lt, scalar_value(value.into()))
+    }
+
+    /// Attribute is a version matching semver `requirement`, e.g. `"^4.2"` or `">=1.2, <2 || ^4"`.
+    pub fn semver_matches(
+        attribute: impl Into<String>,
+        requirement: impl Into<String>,
+    ) -> ConditionBuilder {
+        Self::new(
+            attribute,
+            ConditionOperator::SemverMatches,
+            ValueWire::String(requirement.into().into()).into(),
+        )
+    }
+
+    /// Attribute is one of `values`.
+    pub fn one_of(
+        attribute: impl Into<String>,
+        values: impl IntoIterator<Item = impl Into<String>>,
+    ) -> ConditionBuilder {
+        Self::new(attribute, ConditionOperator::OneOf, string_list(values))
+    }
+
+    /// Attribute is not one of `values`.
+    pub fn not_one_of(
+        attribute: impl Into<String>,
+        values: impl IntoIterator<Item = impl Into<String>>,
+    ) -> ConditionBuilder {
+        Self::new(attribute, ConditionOperator::NotOneOf, string_list(values))
+    }
+
+    /// List attribute contains any of `values`.
+    pub fn contains_any(
+        attribute: impl Into<String>,
+        values: impl IntoIterator<Item = impl Into<String>>,
+    ) -> ConditionBuilder {
+        Self::new(
+            attribute,
+            ConditionOperator::ContainsAny,
+            string_list(values),
+        )
+    }
+
+    /// List attribute contains all of `values`.
+    pub fn contains_all(
+        attribute: impl Into<String>,
+        values: impl IntoIterator<Item = impl Into<String>>,
+    ) -> ConditionBuilder {
+        Self::new(
+            attribute,
+            ConditionOperator::ContainsAll,
+            string_list(values),
+        )
+    }
+
+    /// List attribute contains none of `values`.
+    pub fn contains_none(
+        attribute: impl Into<String>,
+        values: impl IntoIterator<Item = impl Into<String>>,
+    ) -> ConditionBuilder {
+        Self::new(
+            attribute,
+            ConditionOperator::ContainsNone,
+            string_list(values),
+        )
+    }
+
+    /// Attribute is null (if `expected_null` is `true`) or not null (if `false`).
+    pub fn is_null(attribute: impl Into<String>, expected_null: bool) -> ConditionBuilder {
+        Self::new(
+            attribute,
+            ConditionOperator::IsNull,
+            ValueWire::Boolean(expected_null).into(),
+        )
+    }
+
+    /// Attribute contains `value`. Match is case-sensitive.
+    pub fn contains(attribute: impl Into<String>, value: impl Into<Str>) -> ConditionBuilder {
+        Self::new(
+            attribute,
+            ConditionOperator::Contains,
+            ValueWire::String(value.into()).into(),
+        )
+    }
+
+    /// Attribute does not contain `value`. Match is case-sensitive.
+    pub fn not_contains(attribute: impl Into<String>, value: impl Into<Str>) -> ConditionBuilder {
+        Self::new(
+            attribute,
+            ConditionOperator::NotContains,
+            ValueWire::String(value.into()).into(),
+        )
+    }
+
+    /// Attribute starts with `prefix`. Match is case-sensitive.
+    pub fn starts_with(attribute: impl Into<String>, prefix: impl Into<Str>) -> ConditionBuilder {
+        Self::new(
+            attribute,
+            ConditionOperator::StartsWith,
+            ValueWire::String(prefix.into()).into(),
+        )
+    }
+
+    /// Attribute ends with `suffix`. Match is case-sensitive.
+    pub fn ends_with(attribute: impl Into<String>, suffix: impl Into<Str>) -> ConditionBuilder {
+        Self::new(
+            attribute,
+            ConditionOperator::EndsWith,
+            ValueWire::String(suffix.into()).into(),
+        )
+    }
+
+    /// Case-insensitive version of [`ConditionBuilder::contains()`].
+    pub fn contains_ignore_case(
+        attribute: impl Into<String>,
+        value: impl Into<Str>,
+    ) -> ConditionBuilder {
+        Self::new(
+            attribute,
+            ConditionOperator::ContainsIgnoreCase,
+            ValueWire::String(value.into()).into(),
+        )
+    }
+
+    /// Case-insensitive version of [`ConditionBuilder::not_contains()`].
+    pub fn not_contains_ignore_case(
+        attribute: impl Into<String>,
+        value: impl Into<Str>,
+    ) -> ConditionBuilder {
+        Self::new(
+            attribute,
+            ConditionOperator::NotContainsIgnoreCase,
+            ValueWire::String(value.into()).into(),
+        )
+    }
+
+    /// Case-insensitive version of [`ConditionBuilder::starts_with()`].
+    pub fn starts_with_ignore_case(
+        attribute: impl Into<String>,
+        prefix: impl Into<Str>,
+    ) -> ConditionBuilder {
+        Self::new(
+            attribute,
+            ConditionOperator::StartsWithIgnoreCase,
+            ValueWire::String(prefix.into()).into(),
+        )
+    }
+
+    /// Attribute is an RFC 3339 datetime before `datetime`.
+    pub fn before(attribute: impl Into<String>, datetime: Timestamp) -> ConditionBuilder {
+        Self::new(
+            attribute,
+            ConditionOperator::Before,
+            ValueWire::String(datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true).into()).into(),
+        )
+    }
+
+    /// Attribute is an RFC 3339 datetime after `datetime`.
+    pub fn after(attribute: impl Into<String>, datetime: Timestamp) -> ConditionBuilder {
+        Self::new(
+            attribute,
+            ConditionOperator::After,
+            ValueWire::String(datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true).into()).into(),
+        )
+    }
+
+    /// Attribute is an RFC 3339 datetime within the last `days` days before evaluation time.
+    pub fn within_last_days(attribute: impl Into<String>, days: f64) -> ConditionBuilder {
+        Self::new(
+            attribute,
+            ConditionOperator::WithinLastDays,
+            ValueWire::Number(days).into(),
+        )
+    }
+
+    /// Case-insensitive version of [`ConditionBuilder::ends_with()`].
+    pub fn ends_with_ignore_case(
+        attribute: impl Into<String>,
+        suffix: impl Into<Str>,
+    ) -> ConditionBuilder {
+        Self::new(
+            attribute,
+            ConditionOperator::EndsWithIgnoreCase,
+            ValueWire::String(suffix.into()).into(),
+        )
+    }
+}
+
+impl SplitBuilder {
+    /// Create a split assigning `variation_key`. A split without shards matches all subjects.
+    pub fn new(variation_key: impl Into<Str>) -> SplitBuilder {
+        SplitBuilder {
+            variation_key: variation_key.into(),
+            shards: Vec::new(),
+            extra_logging: HashMap::default(),
+        }
+    }
+
+    /// Add a shard. Subject must fall into one of the `ranges` (with hashing salted by `salt`)
+    /// for every shard of the split.
+    pub fn shard(
+        mut self,
+        salt: impl Into<String>,
+        ranges: impl IntoIterator<Item = Range<u32>>,
+    ) -> Self {
+        self.shards.push(ShardWire {
+            salt: salt.into(),
+            ranges: ranges
+                .into_iter()
+                .map(|range| ShardRange {
+                    start: range.start,
+                    end: range.end,
+                })
+                .collect(),
+        });
+        self
+    }
+
+    /// Add extra logging information to assignment events produced by this split.
+    pub fn extra_logging(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra_logging.insert(key.into(), value.into());
+        self
+    }
+
+    fn to_wire(&self) -> SplitWire {
+        SplitWire {
+            shards: self.shards.clone(),
+            variation_key: self.variation_key.clone(),
+            extra_logging: self.extra_logging.clone(),
+        }
+    }
+}
+
+impl BanditBuilder {
+    /// Create a bandit model with no actions.
+    pub fn new(key: impl Into<Str>) -> BanditBuilder {
+        BanditBuilder {
+            key: key.into(),
+            model_name: "falcon".to_owned(),
+            model_version: "v1".into(),
+            updated_at: timestamp::now(),
+            gamma: 1.0,
+            default_action_score: 0.0,
+            action_probability_floor: 0.0,
+            actions: Vec::new(),
+        }
+    }
+
+    /// Set model name. Defaults to `"falcon"`.
+    pub fn model_name(mut self, model_name: impl Into<String>) -> Self {
+        self.model_name = model_name.into();
+        self
+    }
+
+    /// Set model version. Defaults to `"v1"`.
+    pub fn model_version(mut self, model_version: impl Into<Str>) -> Self {
+        self.model_version = model_version.into();
+        self
+    }
+
+    /// Set the time the model was updated. Defaults to the time the builder was created.
+    pub fn updated_at(mut self, updated_at: Timestamp) -> Self {
+        self.updated_at = updated_at;
+        self
+    }
+
+    /// Set gamma (exploration parameter). Defaults to `1.0`.
+    pub fn gamma(mut self, gamma: f64) -> Self {
+        self.gamma = gamma;
+        self
+    }
+
+    /// Set score for actions without coefficients. Defaults to `0.0`.
+    pub fn default_action_score(mut self, default_action_score: f64) -> Self {
+        self.default_action_score = default_action_score;
+        self
+    }
+
+    /// Set minimum probability of selecting each action. Defaults to `0.0`.
+    pub fn action_probability_floor(mut self, action_probability_floor: f64) -> Self {
+        self.action_probability_floor = action_probability_floor;
+        self
+    }
+
+    /// Add coefficients for an action.
+    pub fn action(mut self, action: BanditActionBuilder) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    fn to_configuration(&self) -> BanditConfiguration {
+        BanditConfiguration {
+            bandit_key: self.key.to_string(),
+            model_name: self.model_name.clone(),
+            model_version: self.model_version.clone(),
+            model_data: BanditModelData {
+                gamma: self.gamma,
+                default_action_score: self.default_action_score,
+                action_probability_floor: self.action_probability_floor,
+                coefficients: self
+                    .actions
+                    .iter()
+                    .map(|it| (it.coefficients.action_key.clone(), it.coefficients.clone()))
+                    .collect(),
+            },
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+impl BanditActionBuilder {
+    /// Create action coefficients with the given intercept.
+    pub fn new(action_key: impl Into<String>, intercept: f64) -> BanditActionBuilder {
+        BanditActionBuilder {
+            coefficients: BanditCoefficients {
+                action_key: action_key.into(),
+                intercept,
+                subject_numeric_coefficients: Vec::new(),
+                subject_categorical_coefficients: Vec::new(),
+                action_numeric_coefficients: Vec::new(),
+                action_categorical_coefficients: Vec::new(),
+            },
+        }
+    }
+
+    /// Add a coefficient for a numeric subject attribute.
+    pub fn subject_numeric(
+        mut self,
+        attribute_key: impl Into<String>,
+        coefficient: f64,
+        missing_value_coefficient: f64,
+    ) -> Self {
+        self.coefficients
+            .subject_numeric_coefficients
+            .push(numeric_coefficient(
+                attribute_key,
+                coefficient,
+                missing_value_coefficient,
+            ));
+        self
+    }
+
+    /// Add coefficients for a categorical subject attribute.
+    pub fn subject_categorical(
+        mut self,
+        attribute_key: impl Into<String>,
+        value_coefficients: impl IntoIterator<Item = (impl Into<String>, f64)>,
+        missing_value_coefficient: f64,
+    ) -> Self {
+        self.coefficients
+            .subject_categorical_coefficients
+            .push(categorical_coefficient(
+                attribute_key,
+                value_coefficients,
+                missing_value_coefficient,
+            ));
+        self
+    }
+
+    /// Add a coefficient for a numeric action attribute.
+    pub fn action_numeric(
+        mut self,
+        attribute_key: impl Into<String>,
+        coefficient: f64,
+        missing_value_coefficient: f64,
+    ) -> Self {
+        self.coefficients
+            .action_numeric_coefficients
+            .push(numeric_coefficient(
+                attribute_key,
+                coefficient,
+                missing_value_coefficient,
+            ));
+        self
+    }
+
+    /// Add coefficients for a categorical action attribute.
+    pub fn action_categorical(
+        mut self,
+        attribute_key: impl Into<String>,
+        value_coefficients: impl IntoIterator<Item = (impl Into<String>, f64)>,
+        missing_value_coefficient: f64,
+    ) -> Self {
+        self.coefficients
+            .action_categorical_coefficients
+            .push(categorical_coefficient(
+                attribute_key,
+                value_coefficients,
+                missing_value_coefficient,
+            ));
+        self
+    }
+}
+
+fn numeric_coefficient(
+    attribute_key: impl Into<String>,
+    coefficient: f64,
+    missing_value_coefficient: f64,
+) -> BanditNumericAttributeCoefficient {
+    BanditNumericAttributeCoefficient {
+        attribute_key: attribute_key.into(),
+        coefficient,
+        missing_value_coefficient,
+    }
+}
+
+fn categorical_coefficient(
+    attribute_key: impl Into<String>,
+    value_coefficients: impl IntoIterator<Item = (impl Into<String>, f64)>,
+    missing_value_coefficient: f64,
+) -> BanditCategoricalAttributeCoefficient {
+    BanditCategoricalAttributeCoefficient {
+        attribute_key: attribute_key.into(),
+        value_coefficients: value_coefficients
+            .into_iter()
+            .map(|(value, coefficient)| (value.into(), coefficient))
+            .collect(),
+        missing_value_coefficient,
+    }
+}
+
+/// Convert variation value to wire format. JSON variations are encoded as strings on the wire.
+fn variation_value_wire(ty: VariationType, value: &serde_json::Value) -> ValueWire {
+    match ty {
+        VariationType::Json => ValueWire::String(value.to_string().into()),
+        _ => match scalar_value(value.clone()) {
+            ConditionValue::Single(value) => value,
+            ConditionValue::Multiple(_) => unreachable!("scalar_value never returns Multiple"),
+        },
+    }
+}
+
+fn scalar_value(value: serde_json::Value) -> ConditionValue {
+    ConditionValue::Single(match value {
+        serde_json::Value::Bool(b) => ValueWire::Boolean(b),
+        serde_json::Value::Number(n) => ValueWire::Number(n.as_f64().unwrap_or(f64::NAN)),
+        serde_json::Value::String(s) => ValueWire::String(s.into()),
+        other @ (serde_json::Value::Null
+        | serde_json::Value::Array(_)
+        | serde_json::Value::Object(_)) => ValueWire::String(other.to_string().into()),
+    })
+}
+
+fn string_list(values: impl IntoIterator<Item = impl Into<String>>) -> ConditionValue {
+    ConditionValue::Multiple(
+        values
+            .into_iter()
+            .map(|it| it.into().into_boxed_str())
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::{TimeZone, Utc};
+
+    use crate::{
+        eval::{get_assignment, get_bandit_action},
+        ufc::{AssignmentValue, VariationType},
+        Attributes, ContextAttributes, SdkMetadata, Str,
+    };
+
+    use super::*;
+
+    #[test]
+    fn builds_flags_with_rules_splits_and_time_windows() {
+        let configuration = ConfigurationBuilder::new()
+            .flag(
+                FlagBuilder::new("flag", VariationType::Integer)
+                    .variation("one", 1)
+                    .variation("two", 2)
+                    .variation("three", 3)
+                    .allocation(
+                        AllocationBuilder::new("adults")
+                            .rule(RuleBuilder::new().condition(ConditionBuilder::gte("age", 18)))
+                            .split(SplitBuilder::new("one")),
+                    )
+                    .allocation(
+                        AllocationBuilder::new("expired")
+                            .end_at(Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap())
+                            .split(SplitBuilder::new("two")),
+                    )
+                    .allocation(
+                        AllocationBuilder::new("nobody")
+                            .split(SplitBuilder::new("two").shard("salt", [])),
+                    )
+                    .allocation(
+                        AllocationBuilder::new("default").split(SplitBuilder::new("three")),
+                    ),
+            )
+            .build()
+            .unwrap();
+
+        let get = |attributes: Attributes| {
+            get_assignment(
+                Some(&configuration),
+                "flag",
+                &"subject".into(),
+                &Arc::new(attributes),
+                Some(VariationType::Integer),
+                Utc::now(),
+            )
+            .unwrap()
+            .map(|it| it.value)
+        };
+
+        assert_eq!(
+            get([("age".into(), 30.0.into())].into_iter().collect()),
+            Some(AssignmentValue::Integer(1))
+        );
+        assert_eq!(
+            get(Attributes::default()),
+            Some(AssignmentValue::Integer(3))
+        );
+    }
+
+    #[test]
+    fn builds_datetime_conditions() {
+        let configuration = ConfigurationBuilder::new()
+            .flag(
+                FlagBuilder::new("flag", VariationType::Boolean)
+                    .variation("on", true)
+                    .allocation(
+                        AllocationBuilder::new("new-users")
+                            .rule(
+                                RuleBuilder::new()
+                                    .condition(ConditionBuilder::after(
+                                        "signup",
+                                        Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+                                    ))
+                                    .condition(ConditionBuilder::within_last_days("signup", 30.0)),
+                            )
+                            .split(SplitBuilder::new("on")),
+                    ),
+            )
+            .build()
+            .unwrap();
+
+        let get = |signup: &str, now| {
+            get_assignment(
+                Some(&configuration),
+                "flag",
+                &"subject".into(),
+                &Arc::new([("signup".into(), signup.into())].into_iter().collect()),
+                Some(VariationType::Boolean),
+                now,
+            )
+            .unwrap()
+            .map(|it| it.value)
+        };
+
+        let now = Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            get("2026-02-15T00:00:00Z", now),
+            Some(AssignmentValue::Boolean(true))
+        );
+        assert_eq!(get("2026-01-15T00:00:00Z", now), None);
+        assert_eq!(get("2025-12-31T00:00:00Z", now), None);
+    }
+
+    #[test]
+    fn emits_wire_json_that_round_trips() {
+        let builder = ConfigurationBuilder::new().environment("Staging").flag(
+            FlagBuilder::new("json-flag", VariationType::Json)
+                .variation("obj", serde_json::json!({"hello": "world"}))
+                .allocation(AllocationBuilder::new("all").split(SplitBuilder::new("obj"))),
+        );
+
+        let json: serde_json::Value = serde_json::from_slice(&builder.to_flags_json()).unwrap();
+        assert_eq!(json["environment"]["name"], "Staging");
+        assert_eq!(
+            json["flags"]["json-flag"]["variations"]["obj"]["value"],
+            r#"{"hello":"world"}"#
+        );
+        assert!(builder.to_bandits_json().is_none());
+
+        let configuration = Configuration::from_server_response(
+            UniversalFlagConfig::from_json(
+                SdkMetadata {
+                    name: "test",
+                    version: "0.1.0",
+                },
+                builder.to_flags_json(),
+            )
+            .unwrap(),
+            None,
+        );
+        let assignment = get_assignment(
+            Some(&configuration),
+            "json-flag",
+            &"subject".into(),
+            &Arc::default(),
+            Some(VariationType::Json),
+            Utc::now(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            assignment.value,
+            AssignmentValue::from_json(serde_json::json!({"hello": "world"})).unwrap()
+        );
+    }
+
+    #[test]
+    fn builds_bandits() {
+        let configuration = ConfigurationBuilder::new()
+            .flag(
+                FlagBuilder::new("bandit-flag", VariationType::String)
+                    .variation("bandit", "bandit")
+                    .allocation(AllocationBuilder::new("all").split(SplitBuilder::new("bandit")))
+                    .bandit_variation("bandit", "my-bandit"),
+            )
+            .bandit(
+                BanditBuilder::new("my-bandit")
+                    .gamma(1.0)
+                    .action(BanditActionBuilder::new("nike", 10.0))
+                    .action(BanditActionBuilder::new("adidas", 0.0)),
+            )
+            .build()
+            .unwrap();
+
+        let actions = [
+            (Str::from("nike"), ContextAttributes::default()),
+            (Str::from("adidas"), ContextAttributes::default()),
+        ]
+        .into_iter()
+        .collect();
+        let result = get_bandit_action(
+            Some(&configuration),
+            "bandit-flag",
+            &"subject".into(),
+            &ContextAttributes::default(),
+            &actions,
+            &"default".into(),
+            Utc::now(),
+            &SdkMetadata {
+                name: "test",
+                version: "0.1.0",
+            },
+        );
+        assert_eq!(result.variation, Str::from("bandit"));
+        assert!(result.action.is_some());
+        assert!(result.bandit_event.is_some());
+    }
+}
@@ -0,0 +1,236 @@
+//! A persistent on-disk cache of configuration for warm starts.
+//!
+//! The cache allows an SDK to start serving the last known configuration right away, even if the
+//! Eppo server is unreachable at the time the process starts.
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+
+use crate::{
+    bandits::BanditResponse, ufc::UniversalFlagConfig, Configuration, Error, Result, SdkMetadata,
+};
+
+const CACHE_FILE_NAME: &str = "eppo-configuration.json";
+
+/// Persistent configuration cache stored in a directory on disk.
+///
+/// Configuration is stored as a single file, which is replaced atomically on every write (by
+/// writing to a temporary file and renaming it). The file contains the raw flags and bandits
+/// configuration (as returned by [`Configuration::get_flags_configuration()`] and
+/// [`Configuration::get_bandits_configuration()`]) along with the time configuration was fetched.
+///
+/// The directory should not be shared between SDK instances that use different API keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigurationCache {
+    directory: PathBuf,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedConfigurationRef<'a> {
+    fetched_at: DateTime<Utc>,
+    flags: &'a RawValue,
+    bandits: Option<&'a RawValue>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedConfiguration {
+    fetched_at: DateTime<Utc>,
+    flags: Box<RawValue>,
+    bandits: Option<Box<RawValue>>,
+}
+
+impl ConfigurationCache {
+    /// Create a new cache that stores configuration in `directory`.
+    ///
+    /// The directory is created on first write if it does not exist.
+    pub fn new(directory: impl Into<PathBuf>) -> ConfigurationCache {
+        ConfigurationCache {
+            directory: directory.into(),
+        }
+    }
+
+    /// Directory where configuration is stored.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Load configuration from the cache.
+    ///
+    /// Returns `Ok(None)` if there is no cached configuration. Loaded configuration keeps the
+    /// original time it was fetched at.
+    pub fn load(&self, sdk_metadata: SdkMetadata) -> Result<Option<Configuration>> {
+        let path = self.directory.join(CACHE_FILE_NAME);
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                log::warn!(target: "eppo", "failed to read cached configuration from {}: {err}", path.display());
+                return Err(err.into());
+            }
+        };
+
+        let cached: CachedConfiguration = serde_json::from_slice(&bytes).map_err(|err| {
+            log::warn!(target: "eppo", "failed to parse cached configuration: {err:?}");
+            Error::ConfigurationCache(format!("failed to parse cached configuration: {err}"))
+        })?;
+
+        let flags =
+            UniversalFlagConfig::from_json(sdk_metadata, cached.flags.get().as_bytes().to_vec())?;
+        let bandits = cached
+            .bandits
+            .map(|bandits| serde_json::from_str::<BanditResponse>(bandits.get()))
+            .transpose()
+            .map_err(|err| {
+                log::warn!(target: "eppo", "failed to parse cached bandits configuration: {err:?}");
+                Error::ConfigurationCache(format!(
+                    "failed to parse cached bandits configuration: {err}"
+                ))
+            })?;
+
+        let mut configuration = Configuration::from_server_response(flags, bandits);
+        configuration.fetched_at = cached.fetched_at;
+
+        log::debug!(target: "eppo", fetched_at:% = cached.fetched_at; "loaded configuration from cache");
+
+        Ok(Some(configuration))
+    }
+
+    /// Atomically replace cached configuration with `configuration`.
+    pub fn store(&self, configuration: &Configuration) -> Result<()> {
+        self.write(configuration).inspect_err(|err| {
+            log::warn!(target: "eppo", "failed to write configuration to cache in {}: {err}", self.directory.display());
+        })
+    }
+
+    fn write(&self, configuration: &Configuration) -> Result<()> {
+        let Some(flags) = configuration.get_flags_configuration() else {
+            // Nothing to store.
+            return Ok(());
+        };
+        let bandits = configuration.get_bandits_configuration();
+
+        let contents = serde_json::to_vec(&CachedConfigurationRef {
+            fetched_at: configuration.fetched_at,
+            flags: parse_raw(&flags)?,
+            bandits: bandits.as_deref().map(parse_raw).transpose()?,
+        })
+        .map_err(|err| {
+            Error::ConfigurationCache(format!("failed to serialize configuration: {err}"))
+        })?;
+
+        fs::create_dir_all(&self.directory)?;
+
+        // Write to a temporary file in the same directory first, so that rename is atomic and
+        // readers never observe partially written configuration. Process id is included, so
+        // that multiple processes sharing the directory do not clobber each other's temporary
+        // files.
+        let tmp_path = self
+            .directory
+            .join(format!(".{CACHE_FILE_NAME}.{}.tmp", std::process::id()));
+        let result = (|| -> io::Result<()> {
+            let mut file = fs::File::create(&tmp_path)?;
+            file.write_all(&contents)?;
+            file.sync_all()?;
+            fs::rename(&tmp_path, self.directory.join(CACHE_FILE_NAME))
+        })();
+        if result.is_err() {
+            let _ = fs::remove_file(&tmp_path);
+        }
+        result?;
+
+        log::debug!(target: "eppo", "stored configuration in cache");
+
+        Ok(())
+    }
+}
+
+fn parse_raw(bytes: &[u8]) -> Result<&RawValue> {
+    serde_json::from_slice(bytes)
+        .map_err(|err| Error::ConfigurationCache(format!("configuration is not valid JSON: {err}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use chrono::{TimeZone, Utc};
+
+    use super::{ConfigurationCache, CACHE_FILE_NAME};
+    use crate::{
+        bandits::BanditResponse, ufc::UniversalFlagConfig, Configuration, Error, SdkMetadata,
+    };
+
+    const SDK_METADATA: SdkMetadata = SdkMetadata {
+        name: "test",
+        version: "0.1.0",
+    };
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "eppo-configuration-cache-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn load_returns_none_if_cache_is_empty() {
+        let cache = ConfigurationCache::new(temp_dir("empty"));
+
+        assert!(cache.load(SDK_METADATA).unwrap().is_none());
+    }
+
+    #[test]
+    fn store_and_load_roundtrip() {
+        let dir = temp_dir("roundtrip");
+        let cache = ConfigurationCache::new(&dir);
+
+        let flags = UniversalFlagConfig::from_json(
+            SDK_METADATA,
+            br#"{"createdAt":"2024-07-18T00:00:00Z","environment":{"name":"Test"},"flags":{}}"#
+                .to_vec(),
+        )
+        .unwrap();
+        let bandits: BanditResponse =
+            serde_json::from_str(r#"{"bandits":{},"updatedAt":"2024-07-18T00:00:00Z"}"#).unwrap();
+        let mut configuration = Configuration::from_server_response(flags, Some(bandits));
+        let fetched_at = Utc.with_ymd_and_hms(2024, 7, 18, 12, 0, 0).unwrap();
+        configuration.fetched_at = fetched_at;
+
+        cache.store(&configuration).unwrap();
+
+        let loaded = cache.load(SDK_METADATA).unwrap().unwrap();
+        assert_eq!(loaded.fetched_at, fetched_at);
+        assert_eq!(
+            loaded.get_flags_configuration().unwrap().as_ref(),
+            br#"{"createdAt":"2024-07-18T00:00:00Z","environment":{"name":"Test"},"flags":{}}"#
+        );
+        assert!(loaded.bandits.is_some());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn load_reports_corrupt_cache() {
+        let dir = temp_dir("corrupt");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(CACHE_FILE_NAME), "not json").unwrap();
+        let cache = ConfigurationCache::new(&dir);
+
+        assert!(matches!(
+            cache.load(SDK_METADATA),
+            Err(Error::ConfigurationCache(_))
+        ));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
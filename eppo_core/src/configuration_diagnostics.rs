@@ -0,0 +1,301 @@
+//! Problems found in flags configuration. See [`Configuration::diagnostics()`].
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    hashmap::HashMap,
+    ufc::{AllocationWire, Condition, ConditionWire, FlagWire, TryParse},
+    Configuration, Str,
+};
+
+/// A part of flags configuration that failed to parse or compile, as returned by
+/// [`Configuration::diagnostics()`].
+///
+/// Flags that fail to parse are served as errors (and return default values), while conditions
+/// that fail to parse never match.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct ConfigurationDiagnostic {
+    /// Key of the affected flag.
+    pub flag_key: Str,
+    /// Key of the affected allocation, if the problem is within an allocation.
+    pub allocation_key: Option<Str>,
+    /// Key of the affected variation, if the problem is with a variation value.
+    pub variation_key: Option<Str>,
+    /// Index of the rule within the allocation, if the problem is with a condition.
+    pub rule_index: Option<usize>,
+    /// Index of the condition within the rule, if the problem is with a condition.
+    pub condition_index: Option<usize>,
+    /// Human-readable description of the problem.
+    pub reason: String,
+    /// JSON fragment of the affected flag, allocation, variation, or condition as served by the
+    /// server.
+    pub raw: serde_json::Value,
+}
+
+impl ConfigurationDiagnostic {
+    fn flag(flag_key: &Str, reason: String, raw: serde_json::Value) -> ConfigurationDiagnostic {
+        ConfigurationDiagnostic {
+            flag_key: flag_key.clone(),
+            allocation_key: None,
+            variation_key: None,
+            rule_index: None,
+            condition_index: None,
+            reason,
+            raw,
+        }
+    }
+}
+
+/// Subset of `UniversalFlagConfigWire` that keeps flags unparsed.
+#[derive(Deserialize)]
+struct RawFlagsConfig {
+    flags: HashMap<Str, serde_json::Value>,
+}
+
+impl Configuration {
+    /// Returns problems found in flags configuration: flags, allocations, variations, and
+    /// conditions that failed to parse or compile.
+    ///
+    /// Diagnostics are sorted by flag key. Disabled flags are checked as well. An empty list means
+    /// that all flags are valid.
+    ///
+    /// Diagnostics are computed on every call, so avoid calling this on a hot path.
+    pub fn diagnostics(&self) -> Vec<ConfigurationDiagnostic> {
+        let raw = match serde_json::from_slice::<RawFlagsConfig>(self.flags.to_json()) {
+            Ok(raw) => raw,
+            Err(err) => {
+                // This should never happen as the same JSON was parsed to create configuration.
+                log::warn!(target: "eppo", "failed to parse flags configuration for diagnostics: {err}");
+                return Vec::new();
+            }
+        };
+
+        let mut flags = raw.flags.into_iter().collect::<Vec<_>>();
+        flags.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut diagnostics = Vec::new();
+        for (flag_key, raw_flag) in flags {
+            diagnose_flag(&mut diagnostics, &flag_key, raw_flag);
+        }
+        diagnostics
+    }
+}
+
+fn diagnose_flag(
+    diagnostics: &mut Vec<ConfigurationDiagnostic>,
+    flag_key: &Str,
+    raw_flag: serde_json::Value,
+) {
+    let flag = match serde_json::from_value::<FlagWire>(raw_flag.clone()) {
+        Ok(flag) => flag,
+        Err(err) => {
+            let diagnostic = find_invalid_allocation(flag_key, &raw_flag).unwrap_or_else(|| {
+                ConfigurationDiagnostic::flag(flag_key, err.to_string(), raw_flag)
+            });
+            diagnostics.push(diagnostic);
+            return;
+        }
+    };
+
+    let mut variations = flag.variations.iter().collect::<Vec<_>>();
+    variations.sort_by_key(|(key, _)| *key);
+    for (key, variation) in variations {
+        if variation
+            .value
+            .clone()
+            .into_assignment_value(flag.variation_type)
+            .is_none()
+        {
+            diagnostics.push(ConfigurationDiagnostic {
+                variation_key: Some(variation.key.clone()),
+                ..ConfigurationDiagnostic::flag(
+                    flag_key,
+                    format!(
+                        "variation value does not match variation type {:?}",
+                        flag.variation_type
+                    ),
+                    raw_flag["variations"][key].clone(),
+                )
+            });
+        }
+    }
+
+    for (allocation_index, allocation) in flag.allocations.iter().enumerate() {
+        let raw_allocation = &raw_flag["allocations"][allocation_index];
+
+        for split in &allocation.splits {
+            if !flag
+                .variations
+                .values()
+                .any(|variation| variation.key == split.variation_key)
+            {
+                diagnostics.push(ConfigurationDiagnostic {
+                    allocation_key: Some(allocation.key.clone()),
+                    ..ConfigurationDiagnostic::flag(
+                        flag_key,
+                        format!(
+                            "split references unknown variation {:?}",
+                            split.variation_key
+                        ),
+                        raw_allocation.clone(),
+                    )
+                });
+            }
+        }
+
+        for (rule_index, rule) in allocation.rules.iter().enumerate() {
+            for (condition_index, condition) in rule.conditions.iter().enumerate() {
+                let TryParse::ParseFailed(raw_condition) = condition else {
+                    continue;
+                };
+                diagnostics.push(ConfigurationDiagnostic {
+                    allocation_key: Some(allocation.key.clone()),
+                    rule_index: Some(rule_index),
+                    condition_index: Some(condition_index),
+                    ..ConfigurationDiagnostic::flag(
+                        flag_key,
+                        condition_failure_reason(raw_condition),
+                        raw_condition.clone(),
+                    )
+                });
+            }
+        }
+    }
+}
+
+/// Find the allocation that makes the flag fail parsing, so the diagnostic can point at it.
+fn find_invalid_allocation(
+    flag_key: &Str,
+    raw_flag: &serde_json::Value,
+) -> Option<ConfigurationDiagnostic> {
+    let allocations = raw_flag.get("allocations")?.as_array()?;
+    allocations.iter().find_map(|raw_allocation| {
+        let err = serde_json::from_value::<AllocationWire>(raw_allocation.clone()).err()?;
+        Some(ConfigurationDiagnostic {
+            allocation_key: raw_allocation
+                .get("key")
+                .and_then(|key| key.as_str())
+                .map(Str::from),
+            ..ConfigurationDiagnostic::flag(flag_key, err.to_string(), raw_allocation.clone())
+        })
+    })
+}
+
+/// Re-parse a condition that failed parsing to find out why.
+fn condition_failure_reason(raw_condition: &serde_json::Value) -> String {
+    match serde_json::from_value::<ConditionWire>(raw_condition.clone()) {
+        Err(err) => err.to_string(),
+        Ok(condition) => match Condition::parse(condition) {
+            Err(reason) => reason,
+            Ok(_) => "condition failed to parse".to_owned(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{Configuration, SdkMetadata};
+
+    fn configuration(flags: serde_json::Value) -> Configuration {
+        Configuration::from_json(
+            SdkMetadata {
+                name: "test",
+                version: "0.1.0",
+            },
+            serde_json::to_vec(&json!({
+                "createdAt": "2024-07-18T00:00:00Z",
+                "environment": {"name": "Test"},
+                "flags": flags,
+            }))
+            .unwrap(),
+            None,
+        )
+        .unwrap()
+    }
+
+    fn flag(variation_type: &str, allocations: serde_json::Value) -> serde_json::Value {
+        json!({
+            "key": "flag",
+            "enabled": true,
+            "variationType": variation_type,
+            "variations": {"on": {"key": "on", "value": true}},
+            "allocations": allocations,
+            "totalShards": 10000,
+        })
+    }
+
+    fn allocation(conditions: serde_json::Value) -> serde_json::Value {
+        json!({
+            "key": "allocation",
+            "rules": [{"conditions": conditions}],
+            "splits": [{"shards": [], "variationKey": "on"}],
+        })
+    }
+
+    #[test]
+    fn valid_configuration_has_no_diagnostics() {
+        let configuration = configuration(json!({
+            "flag": flag("BOOLEAN", json!([allocation(json!([
+                {"attribute": "country", "operator": "ONE_OF", "value": ["US"]},
+            ]))])),
+        }));
+
+        assert_eq!(configuration.diagnostics(), vec![]);
+    }
+
+    #[test]
+    fn reports_invalid_flags() {
+        let configuration = configuration(json!({
+            "unknown-type": flag("DATE", json!([])),
+            "bad-allocation": flag("BOOLEAN", json!([{"key": "allocation"}])),
+            "bad-variation": flag("INTEGER", json!([])),
+        }));
+
+        let diagnostics = configuration.diagnostics();
+        assert_eq!(diagnostics.len(), 3);
+
+        assert_eq!(diagnostics[0].flag_key.as_str(), "bad-allocation");
+        assert_eq!(diagnostics[0].allocation_key.as_deref(), Some("allocation"));
+        assert_eq!(diagnostics[0].raw, json!({"key": "allocation"}));
+        assert!(diagnostics[0].reason.contains("splits"));
+
+        assert_eq!(diagnostics[1].flag_key.as_str(), "bad-variation");
+        assert_eq!(diagnostics[1].variation_key.as_deref(), Some("on"));
+        assert_eq!(diagnostics[1].raw, json!({"key": "on", "value": true}));
+
+        assert_eq!(diagnostics[2].flag_key.as_str(), "unknown-type");
+        assert_eq!(diagnostics[2].allocation_key, None);
+        assert!(diagnostics[2].reason.contains("DATE"));
+        assert_eq!(diagnostics[2].raw["key"], "flag");
+    }
+
+    #[test]
+    fn reports_invalid_conditions() {
+        let configuration = configuration(json!({
+            "flag": flag("BOOLEAN", json!([allocation(json!([
+                {"attribute": "email", "operator": "MATCHES", "value": "(unclosed"},
+                {"attribute": "version", "operator": "GTE", "value": "one.two"},
+                {"attribute": "country", "operator": "ONE_OF", "value": "US"},
+                {"attribute": "country", "operator": "UNKNOWN", "value": "US"},
+            ]))])),
+        }));
+
+        let diagnostics = configuration.diagnostics();
+        assert_eq!(diagnostics.len(), 4);
+        for (index, diagnostic) in diagnostics.iter().enumerate() {
+            assert_eq!(diagnostic.allocation_key.as_deref(), Some("allocation"));
+            assert_eq!(diagnostic.rule_index, Some(0));
+            assert_eq!(diagnostic.condition_index, Some(index));
+        }
+        assert!(diagnostics[0].reason.contains("regex"));
+        assert!(diagnostics[1].reason.contains("neither semver, nor number"));
+        assert!(diagnostics[2].reason.contains("non-array"));
+        assert!(diagnostics[3].reason.contains("UNKNOWN"));
+        assert_eq!(
+            diagnostics[2].raw,
+            json!({"attribute": "country", "operator": "ONE_OF", "value": "US"})
+        );
+    }
+}
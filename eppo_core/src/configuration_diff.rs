@@ -0,0 +1,782 @@
+//! Comparison of two configurations. See [`Configuration::diff()`].
+use std::fmt::{self, Display, Formatter};
+
+use crate::{
+    error::EvaluationFailure,
+    hashmap::HashMap,
+    timestamp::Timestamp,
+    ufc::{Allocation, AssignmentValue, Flag, ShardRange, Split, VariationType},
+    Configuration, Str,
+};
+
+/// Difference between two configurations, as returned by [`Configuration::diff()`].
+///
+/// All lists are sorted by key, so that the diff is deterministic. `Display` implementation
+/// produces a human-readable summary of changes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigurationDiff {
+    /// Flags that are present in the new configuration only.
+    pub added_flags: Vec<Str>,
+    /// Flags that are present in the old configuration only.
+    pub removed_flags: Vec<Str>,
+    /// Flags that are present in both configurations but have changed.
+    pub modified_flags: Vec<FlagDiff>,
+    /// Bandits that were added, removed, or whose model version has changed.
+    pub bandits: Vec<BanditDiff>,
+}
+
+/// Changes of a single flag.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlagDiff {
+    pub flag_key: Str,
+    pub changes: Vec<FlagChange>,
+}
+
+/// Status of a flag in configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagStatus {
+    Enabled,
+    Disabled,
+    /// Flag configuration failed to parse or is otherwise invalid.
+    Invalid,
+}
+
+/// A single change to a flag.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum FlagChange {
+    /// Flag was enabled, disabled, or became (in)valid.
+    Status {
+        previous: FlagStatus,
+        current: FlagStatus,
+    },
+    VariationType {
+        previous: VariationType,
+        current: VariationType,
+    },
+    /// Variation was added, removed, or its value has changed. `None` means that variation is not
+    /// served by the respective configuration.
+    Variation {
+        variation_key: Str,
+        previous: Option<AssignmentValue>,
+        current: Option<AssignmentValue>,
+    },
+    AllocationAdded {
+        allocation_key: Str,
+    },
+    AllocationRemoved {
+        allocation_key: Str,
+    },
+    /// Allocations are evaluated in order, so reordering them may change assignments.
+    AllocationsReordered {
+        previous: Vec<Str>,
+        current: Vec<Str>,
+    },
+    /// Allocation start or end time has changed.
+    AllocationSchedule {
+        allocation_key: Str,
+        previous: (Option<Timestamp>, Option<Timestamp>),
+        current: (Option<Timestamp>, Option<Timestamp>),
+    },
+    /// Allocation targeting rules have changed.
+    AllocationRules {
+        allocation_key: Str,
+    },
+    /// Allocation splits were added, removed, or now serve different variations.
+    AllocationSplits {
+        allocation_key: Str,
+        previous: Vec<Str>,
+        current: Vec<Str>,
+    },
+    /// Shard ranges of a split have changed (e.g., traffic exposure or split weights were
+    /// updated).
+    ShardRanges {
+        allocation_key: Str,
+        variation_key: Str,
+        previous: Vec<Vec<ShardRange>>,
+        current: Vec<Vec<ShardRange>>,
+    },
+    /// Shard salts or the total number of shards of a split have changed. This reassigns subjects
+    /// even if shard ranges are the same.
+    Sharding {
+        allocation_key: Str,
+        variation_key: Str,
+    },
+    /// Allocation assignments are now logged or no longer logged (`doLog`).
+    AllocationLogging {
+        allocation_key: Str,
+        previous: bool,
+        current: bool,
+    },
+    /// Extra logging attached to assignment events of a split has changed.
+    SplitExtraLogging {
+        allocation_key: Str,
+        variation_key: Str,
+    },
+}
+
+/// Change of a single bandit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BanditDiff {
+    pub bandit_key: Str,
+    /// Model version in the old configuration. `None` if bandit was added.
+    pub previous_model_version: Option<Str>,
+    /// Model version in the new configuration. `None` if bandit was removed.
+    pub current_model_version: Option<Str>,
+}
+
+impl ConfigurationDiff {
+    /// Returns `true` if configurations are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.added_flags.is_empty()
+            && self.removed_flags.is_empty()
+            && self.modified_flags.is_empty()
+            && self.bandits.is_empty()
+    }
+}
+
+impl Configuration {
+    /// Compute changes going from `self` (old configuration) to `other` (new configuration).
+    ///
+    /// The comparison is performed on compiled flags and bandits configuration, so it reports
+    /// changes that affect evaluation or assignment logging and ignores formatting differences.
+    pub fn diff(&self, other: &Configuration) -> ConfigurationDiff {
+        let old_flags = &self.flags.compiled.flags;
+        let new_flags = &other.flags.compiled.flags;
+
+        let mut diff = ConfigurationDiff::default();
+
+        for (key, new_flag) in new_flags {
+            match old_flags.get(key) {
+                None => diff.added_flags.push(key.clone()),
+                Some(old_flag) => {
+                    let changes = diff_flag(old_flag, new_flag);
+                    if !changes.is_empty() {
+                        diff.modified_flags.push(FlagDiff {
+                            flag_key: key.clone(),
+                            changes,
+                        });
+                    }
+                }
+            }
+        }
+        diff.removed_flags = old_flags
+            .keys()
+            .filter(|key| !new_flags.contains_key(*key))
+            .cloned()
+            .collect();
+
+        let old_bandits = bandit_versions(self);
+        let new_bandits = bandit_versions(other);
+        for (key, new_version) in &new_bandits {
+            let old_version = old_bandits.get(key);
+            if old_version != Some(new_version) {
+                diff.bandits.push(BanditDiff {
+                    bandit_key: key.clone(),
+                    previous_model_version: old_version.cloned(),
+                    current_model_version: Some(new_version.clone()),
+                });
+            }
+        }
+        for (key, old_version) in &old_bandits {
+            if !new_bandits.contains_key(key) {
+                diff.bandits.push(BanditDiff {
+                    bandit_key: key.clone(),
+                    previous_model_version: Some(old_version.clone()),
+                    current_model_version: None,
+                });
+            }
+        }
+
+        diff.added_flags.sort();
+        diff.removed_flags.sort();
+        diff.modified_flags
+            .sort_by(|a, b| a.flag_key.cmp(&b.flag_key));
+        diff.bandits.sort_by(|a, b| a.bandit_key.cmp(&b.bandit_key));
+
+        diff
+    }
+}
+
+fn bandit_versions(configuration: &Configuration) -> HashMap<Str, Str> {
+    configuration
+        .bandits
+        .iter()
+        .flat_map(|bandits| bandits.bandits.iter())
+        .map(|(key, bandit)| (key.clone(), bandit.model_version.clone()))
+        .collect()
+}
+
+fn flag_status(flag: &Result<Flag, EvaluationFailure>) -> FlagStatus {
+    match flag {
+        Ok(_) => FlagStatus::Enabled,
+        Err(EvaluationFailure::FlagDisabled) => FlagStatus::Disabled,
+        Err(_) => FlagStatus::Invalid,
+    }
+}
+
+fn diff_flag(
+    old: &Result<Flag, EvaluationFailure>,
+    new: &Result<Flag, EvaluationFailure>,
+) -> Vec<FlagChange> {
+    let (Ok(old), Ok(new)) = (old, new) else {
+        let (previous, current) = (flag_status(old), flag_status(new));
+        return if previous != current {
+            vec![FlagChange::Status { previous, current }]
+        } else {
+            Vec::new()
+        };
+    };
+
+    let mut changes = Vec::new();
+
+    if old.variation_type != new.variation_type {
+        changes.push(FlagChange::VariationType {
+            previous: old.variation_type,
+            current: new.variation_type,
+        });
+    }
+
+    let old_variations = variations(old);
+    let new_variations = variations(new);
+    let mut variation_keys: Vec<&Str> =
+        old_variations.keys().chain(new_variations.keys()).collect();
+    variation_keys.sort();
+    variation_keys.dedup();
+    for variation_key in variation_keys {
+        let previous = old_variations.get(variation_key).copied();
+        let current = new_variations.get(variation_key).copied();
+        if previous != current {
+            changes.push(FlagChange::Variation {
+                variation_key: variation_key.clone(),
+                previous: previous.cloned(),
+                current: current.cloned(),
+            });
+        }
+    }
+
+    let old_keys: Vec<Str> = old.allocations.iter().map(|it| it.key.clone()).collect();
+    let new_keys: Vec<Str> = new.allocations.iter().map(|it| it.key.clone()).collect();
+    for key in &new_keys {
+        if !old_keys.contains(key) {
+            changes.push(FlagChange::AllocationAdded {
+                allocation_key: key.clone(),
+            });
+        }
+    }
+    for key in &old_keys {
+        if !new_keys.contains(key) {
+            changes.push(FlagChange::AllocationRemoved {
+                allocation_key: key.clone(),
+            });
+        }
+    }
+    let old_common: Vec<&Str> = old_keys.iter().filter(|k| new_keys.contains(k)).collect();
+    let new_common: Vec<&Str> = new_keys.iter().filter(|k| old_keys.contains(k)).collect();
+    if old_common != new_common {
+        changes.push(FlagChange::AllocationsReordered {
+            previous: old_keys.clone(),
+            current: new_keys.clone(),
+        });
+    }
+
+    for new_allocation in new.allocations.iter() {
+        if let Some(old_allocation) = old
+            .allocations
+            .iter()
+            .find(|it| it.key == new_allocation.key)
+        {
+            diff_allocation(old_allocation, new_allocation, &mut changes);
+        }
+    }
+
+    changes
+}
+
+/// Collect variations served by the flag.
+fn variations(flag: &Flag) -> HashMap<Str, &AssignmentValue> {
+    flag.allocations
+        .iter()
+        .flat_map(|allocation| allocation.splits.iter())
+        .filter_map(|split| {
+            let (value, _) = split.result.as_ref().ok()?;
+            Some((split.variation_key.clone(), value))
+        })
+        .collect()
+}
+
+fn diff_allocation(old: &Allocation, new: &Allocation, changes: &mut Vec<FlagChange>) {
+    let allocation_key = &new.key;
+
+    if (old.start_at, old.end_at) != (new.start_at, new.end_at) {
+        changes.push(FlagChange::AllocationSchedule {
+            allocation_key: allocation_key.clone(),
+            previous: (old.start_at, old.end_at),
+            current: (new.start_at, new.end_at),
+        });
+    }
+
+    if old.do_log != new.do_log {
+        changes.push(FlagChange::AllocationLogging {
+            allocation_key: allocation_key.clone(),
+            previous: old.do_log,
+            current: new.do_log,
+        });
+    }
+
+    // Rules are compared in their wire format, so that conditions that failed to parse are
+    // compared too.
+    if serde_json::to_value(&old.rules).ok() != serde_json::to_value(&new.rules).ok() {
+        changes.push(FlagChange::AllocationRules {
+            allocation_key: allocation_key.clone(),
+        });
+    }
+
+    let split_variations = |splits: &[Split]| -> Vec<Str> {
+        splits.iter().map(|it| it.variation_key.clone()).collect()
+    };
+    let (old_splits, new_splits) = (split_variations(&old.splits), split_variations(&new.splits));
+    if old_splits != new_splits {
+        changes.push(FlagChange::AllocationSplits {
+            allocation_key: allocation_key.clone(),
+            previous: old_splits,
+            current: new_splits,
+        });
+        // Splits cannot be matched one-to-one, so there is no point comparing shards.
+        return;
+    }
+
+    for (old_split, new_split) in old.splits.iter().zip(new.splits.iter()) {
+        let (previous, current) = (shard_ranges(old_split), shard_ranges(new_split));
+        if previous != current {
+            changes.push(FlagChange::ShardRanges {
+                allocation_key: allocation_key.clone(),
+                variation_key: new_split.variation_key.clone(),
+                previous,
+                current,
+            });
+        }
+        if shard_salts(old_split) != shard_salts(new_split) {
+            changes.push(FlagChange::Sharding {
+                allocation_key: allocation_key.clone(),
+                variation_key: new_split.variation_key.clone(),
+            });
+        }
+        // Extra logging only matters if assignments are logged.
+        if old.do_log && new.do_log && extra_logging(old_split) != extra_logging(new_split) {
+            changes.push(FlagChange::SplitExtraLogging {
+                allocation_key: allocation_key.clone(),
+                variation_key: new_split.variation_key.clone(),
+            });
+        }
+    }
+}
+
+fn shard_ranges(split: &Split) -> Vec<Vec<ShardRange>> {
+    split
+        .shards
+        .iter()
+        .map(|shard| shard.ranges.to_vec())
+        .collect()
+}
+
+fn shard_salts(split: &Split) -> Vec<(&Str, u32)> {
+    split
+        .shards
+        .iter()
+        .map(|shard| (&shard.salt, shard.total_shards))
+        .collect()
+}
+
+/// Extra logging of the split. `None` if the split does not serve a valid variation.
+fn extra_logging(split: &Split) -> Option<&HashMap<String, String>> {
+    let (_, event) = split.result.as_ref().ok()?;
+    event.as_ref().map(|event| &event.extra_logging)
+}
+
+impl Display for ConfigurationDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "no changes");
+        }
+
+        let mut first = true;
+        let mut line = |f: &mut Formatter<'_>| -> fmt::Result {
+            if !std::mem::take(&mut first) {
+                writeln!(f)?;
+            }
+            Ok(())
+        };
+
+        if !self.added_flags.is_empty() {
+            line(f)?;
+            write!(f, "added flags: {}", self.added_flags.join(", "))?;
+        }
+        if !self.removed_flags.is_empty() {
+            line(f)?;
+            write!(f, "removed flags: {}", self.removed_flags.join(", "))?;
+        }
+        for flag in &self.modified_flags {
+            line(f)?;
+            write!(f, "modified flag {:?}:", flag.flag_key)?;
+            for change in &flag.changes {
+                write!(f, "\n  - {change}")?;
+            }
+        }
+        for bandit in &self.bandits {
+            line(f)?;
+            match (
+                &bandit.previous_model_version,
+                &bandit.current_model_version,
+            ) {
+                (None, Some(current)) => write!(
+                    f,
+                    "added bandit {:?} (model version {current})",
+                    bandit.bandit_key
+                )?,
+                (Some(previous), None) => write!(
+                    f,
+                    "removed bandit {:?} (model version {previous})",
+                    bandit.bandit_key
+                )?,
+                (previous, current) => write!(
+                    f,
+                    "bandit {:?} model version changed: {} -> {}",
+                    bandit.bandit_key,
+                    OptionDisplay(previous.as_ref()),
+                    OptionDisplay(current.as_ref())
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for FlagChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FlagChange::Status { previous, current } => {
+                write!(f, "status changed: {previous:?} -> {current:?}")
+            }
+            FlagChange::VariationType { previous, current } => {
+                write!(f, "variation type changed: {previous:?} -> {current:?}")
+            }
+            FlagChange::Variation {
+                variation_key,
+                previous: None,
+                current: Some(current),
+            } => write!(
+                f,
+                "variation {variation_key:?} added: {}",
+                ValueDisplay(current)
+            ),
+            FlagChange::Variation {
+                variation_key,
+                previous: Some(previous),
+                current: None,
+            } => write!(
+                f,
+                "variation {variation_key:?} removed: {}",
+                ValueDisplay(previous)
+            ),
+            FlagChange::Variation {
+                variation_key,
+                previous,
+                current,
+            } => write!(
+                f,
+                "variation {variation_key:?} changed: {} -> {}",
+                OptionDisplay(previous.as_ref().map(ValueDisplay)),
+                OptionDisplay(current.as_ref().map(ValueDisplay))
+            ),
+            FlagChange::AllocationAdded { allocation_key } => {
+                write!(f, "allocation {allocation_key:?} added")
+            }
+            FlagChange::AllocationRemoved { allocation_key } => {
+                write!(f, "allocation {allocation_key:?} removed")
+            }
+            FlagChange::AllocationsReordered { previous, current } => write!(
+                f,
+                "allocations reordered: [{}] -> [{}]",
+                previous.join(", "),
+                current.join(", ")
+            ),
+            FlagChange::AllocationSchedule {
+                allocation_key,
+                previous,
+                current,
+            } => write!(
+                f,
+                "allocation {allocation_key:?} schedule changed: {}..{} -> {}..{}",
+                OptionDisplay(previous.0.as_ref()),
+                OptionDisplay(previous.1.as_ref()),
+                OptionDisplay(current.0.as_ref()),
+                OptionDisplay(current.1.as_ref())
+            ),
+            FlagChange::AllocationRules { allocation_key } => {
+                write!(f, "allocation {allocation_key:?} rules changed")
+            }
+            FlagChange::AllocationSplits {
+                allocation_key,
+                previous,
+                current,
+            } => write!(
+                f,
+                "allocation {allocation_key:?} splits changed: [{}] -> [{}]",
+                previous.join(", "),
+                current.join(", ")
+            ),
+            FlagChange::ShardRanges {
+                allocation_key,
+                variation_key,
+                previous,
+                current,
+            } => write!(
+                f,
+                "allocation {allocation_key:?} split {variation_key:?} shard ranges changed: {} -> {}",
+                ShardsDisplay(previous),
+                ShardsDisplay(current)
+            ),
+            FlagChange::Sharding {
+                allocation_key,
+                variation_key,
+            } => write!(
+                f,
+                "allocation {allocation_key:?} split {variation_key:?} shard salt or total shards changed"
+            ),
+            FlagChange::AllocationLogging {
+                allocation_key,
+                previous,
+                current,
+            } => write!(
+                f,
+                "allocation {allocation_key:?} logging changed: {previous} -> {current}"
+            ),
+            FlagChange::SplitExtraLogging {
+                allocation_key,
+                variation_key,
+            } => write!(
+                f,
+                "allocation {allocation_key:?} split {variation_key:?} extra logging changed"
+            ),
+        }
+    }
+}
+
+struct OptionDisplay<T>(Option<T>);
+impl<T: Display> Display for OptionDisplay<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(value) => value.fmt(f),
+            None => f.write_str("none"),
+        }
+    }
+}
+
+struct ValueDisplay<'a>(&'a AssignmentValue);
+impl Display for ValueDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            AssignmentValue::String(s) => write!(f, "{s:?}"),
+            AssignmentValue::Integer(i) => write!(f, "{i}"),
+            AssignmentValue::Numeric(n) => write!(f, "{n}"),
+            AssignmentValue::Boolean(b) => write!(f, "{b}"),
+            AssignmentValue::Json { raw, .. } => f.write_str(raw),
+        }
+    }
+}
+
+struct ShardsDisplay<'a>(&'a [Vec<ShardRange>]);
+impl Display for ShardsDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (i, ranges) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str("{")?;
+            for (j, range) in ranges.iter().enumerate() {
+                if j > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}..{}", range.start, range.end)?;
+            }
+            f.write_str("}")?;
+        }
+        f.write_str("]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ufc::UniversalFlagConfig, Configuration, SdkMetadata, Str};
+
+    use super::{FlagChange, FlagStatus};
+
+    fn make_configuration(flags_json: &str) -> Configuration {
+        Configuration::from_server_response(
+            UniversalFlagConfig::from_json(
+                SdkMetadata {
+                    name: "test",
+                    version: "0.1.0",
+                },
+                format!(
+                    r#"{{"createdAt":"2024-07-18T00:00:00Z","environment":{{"name":"Test"}},"flags":{flags_json}}}"#
+                )
+                .into_bytes(),
+            )
+            .unwrap(),
+            None,
+        )
+    }
+
+    fn flag(enabled: bool, on_value: bool, shard_end: u32) -> String {
+        format!(
+            r#"{{
+              "key": "flag",
+              "enabled": {enabled},
+              "variationType": "BOOLEAN",
+              "variations": {{
+                "on": {{"key": "on", "value": {on_value}}},
+                "off": {{"key": "off", "value": false}}
+              }},
+              "allocations": [
+                {{
+                  "key": "rollout",
+                  "rules": [],
+                  "splits": [
+                    {{"variationKey": "on", "shards": [{{"salt": "a", "ranges": [{{"start": 0, "end": {shard_end}}}]}}]}},
+                    {{"variationKey": "off", "shards": []}}
+                  ],
+                  "doLog": true
+                }}
+              ],
+              "totalShards": 10000
+            }}"#
+        )
+    }
+
+    #[test]
+    fn identical_configurations_have_empty_diff() {
+        let old = make_configuration(&format!(r#"{{"flag": {}}}"#, flag(true, true, 5000)));
+        let new = make_configuration(&format!(r#"{{"flag": {}}}"#, flag(true, true, 5000)));
+
+        let diff = old.diff(&new);
+
+        assert!(diff.is_empty(), "{diff}");
+    }
+
+    #[test]
+    fn reports_added_removed_and_modified_flags() {
+        let old = make_configuration(&format!(
+            r#"{{"flag": {}, "removed": {}, "disabled": {}}}"#,
+            flag(true, true, 5000),
+            flag(true, true, 5000),
+            flag(true, true, 5000)
+        ));
+        let new = make_configuration(&format!(
+            r#"{{"flag": {}, "added": {}, "disabled": {}}}"#,
+            flag(true, false, 2000),
+            flag(true, true, 5000),
+            flag(false, true, 5000)
+        ));
+
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.added_flags, vec![Str::from("added")]);
+        assert_eq!(diff.removed_flags, vec![Str::from("removed")]);
+        assert_eq!(diff.modified_flags.len(), 2);
+
+        assert_eq!(diff.modified_flags[0].flag_key, Str::from("disabled"));
+        assert_eq!(
+            diff.modified_flags[0].changes,
+            vec![FlagChange::Status {
+                previous: FlagStatus::Enabled,
+                current: FlagStatus::Disabled
+            }]
+        );
+
+        assert_eq!(diff.modified_flags[1].flag_key, Str::from("flag"));
+        let changes = &diff.modified_flags[1].changes;
+        assert!(
+            changes
+                .iter()
+                .any(|it| matches!(it, FlagChange::Variation { variation_key, .. } if variation_key.as_str() == "on")),
+            "{changes:?}"
+        );
+        assert!(
+            changes
+                .iter()
+                .any(|it| matches!(it, FlagChange::ShardRanges { variation_key, .. } if variation_key.as_str() == "on")),
+            "{changes:?}"
+        );
+
+        let summary = diff.to_string();
+        assert!(summary.contains("added flags: added"), "{summary}");
+        assert!(
+            summary.contains(r#"variation "on" changed: true -> false"#),
+            "{summary}"
+        );
+    }
+
+    #[test]
+    fn reports_salt_only_changes() {
+        let old = make_configuration(&format!(r#"{{"flag": {}}}"#, flag(true, true, 5000)));
+        let new = make_configuration(&format!(
+            r#"{{"flag": {}}}"#,
+            flag(true, true, 5000).replace(r#""salt": "a""#, r#""salt": "b""#)
+        ));
+
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.modified_flags.len(), 1, "{diff}");
+        assert_eq!(
+            diff.modified_flags[0].changes,
+            vec![FlagChange::Sharding {
+                allocation_key: "rollout".into(),
+                variation_key: "on".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_total_shards_and_logging_changes() {
+        let old = make_configuration(&format!(r#"{{"flag": {}}}"#, flag(true, true, 5000)));
+        let new = make_configuration(&format!(
+            r#"{{"flag": {}}}"#,
+            flag(true, true, 5000).replace(r#""totalShards": 10000"#, r#""totalShards": 20000"#)
+        ));
+        assert!(old.diff(&new).modified_flags[0]
+            .changes
+            .iter()
+            .any(|it| matches!(it, FlagChange::Sharding { .. })));
+
+        let new = make_configuration(&format!(
+            r#"{{"flag": {}}}"#,
+            flag(true, true, 5000).replace(r#""doLog": true"#, r#""doLog": false"#)
+        ));
+        assert_eq!(
+            old.diff(&new).modified_flags[0].changes,
+            vec![FlagChange::AllocationLogging {
+                allocation_key: "rollout".into(),
+                previous: true,
+                current: false,
+            }]
+        );
+
+        let new = make_configuration(&format!(
+            r#"{{"flag": {}}}"#,
+            flag(true, true, 5000).replace(
+                r#"{"variationKey": "off", "shards": []}"#,
+                r#"{"variationKey": "off", "shards": [], "extraLogging": {"holdout": "h1"}}"#
+            )
+        ));
+        assert_eq!(
+            old.diff(&new).modified_flags[0].changes,
+            vec![FlagChange::SplitExtraLogging {
+                allocation_key: "rollout".into(),
+                variation_key: "off".into(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,343 @@
+//! Offline mode: loading configuration from local files.
+//!
+//! Configuration files use the same format as Eppo servers: flags configuration in UFC format
+//! (`/flag-config/v1/config`) and optional bandits configuration (`/flag-config/v1/bandits`).
+//! Files can be loaded once with [`ConfigurationFiles::load()`] or watched for changes with
+//! [`start_configuration_file_watcher()`], which reloads them into a [`ConfigurationStore`].
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use tokio_util::sync::CancellationToken;
+
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::sleep;
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::tokio::sleep;
+
+use crate::{
+    background::{AsyncRuntime, BackgroundRuntime},
+    configuration_poller::log_configuration_changes,
+    configuration_source::{ConfigurationSource, FileConfigurationSource},
+    configuration_store::ConfigurationStore,
+    Configuration, Result, SdkMetadata,
+};
+
+/// Paths to flags and bandits configuration files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigurationFiles {
+    flags: PathBuf,
+    bandits: Option<PathBuf>,
+}
+
+impl ConfigurationFiles {
+    /// Create configuration files with flags configuration at `flags` and no bandits.
+    pub fn new(flags: impl Into<PathBuf>) -> ConfigurationFiles {
+        ConfigurationFiles {
+            flags: flags.into(),
+            bandits: None,
+        }
+    }
+
+    /// Load bandits configuration from `bandits`.
+    pub fn with_bandits(mut self, bandits: impl Into<PathBuf>) -> ConfigurationFiles {
+        self.bandits = Some(bandits.into());
+        self
+    }
+
+    /// Path to flags configuration file.
+    pub fn flags(&self) -> &Path {
+        &self.flags
+    }
+
+    /// Path to bandits configuration file.
+    pub fn bandits(&self) -> Option<&Path> {
+        self.bandits.as_deref()
+    }
+
+    /// Read and parse configuration from files.
+    pub fn load(&self, sdk_metadata: SdkMetadata) -> Result<Configuration> {
+        let read = |path: &Path| {
+            fs::read(path).inspect_err(|err| {
+                log::warn!(target: "eppo", "failed to read configuration from {}: {err}", path.display());
+            })
+        };
+
+        let flags = read(&self.flags)?;
+        let bandits = self.bandits.as_deref().map(read).transpose()?;
+
+        let configuration = Configuration::from_json(sdk_metadata, flags, bandits.as_deref())?;
+
+        log::debug!(target: "eppo", "loaded configuration from {}", self.flags.display());
+
+        Ok(configuration)
+    }
+
+    /// Modification time and size of each file, used to detect changes.
+    pub(crate) fn stamp(&self) -> Vec<Option<(SystemTime, u64)>> {
+        std::iter::once(&self.flags)
+            .chain(&self.bandits)
+            .map(|path| {
+                let metadata = fs::metadata(path).ok()?;
+                Some((metadata.modified().ok()?, metadata.len()))
+            })
+            .collect()
+    }
+}
+
+/// Configuration for [`start_configuration_file_watcher()`].
+#[derive(Debug, Clone)]
+pub struct ConfigurationFileWatcherConfig {
+    /// Interval between checks for file changes.
+    ///
+    /// Defaults to [`ConfigurationFileWatcherConfig::DEFAULT_INTERVAL`].
+    pub interval: Duration,
+}
+
+impl ConfigurationFileWatcherConfig {
+    /// Default value for [`ConfigurationFileWatcherConfig::interval`].
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// Create a new `ConfigurationFileWatcherConfig` using default configuration.
+    pub fn new() -> ConfigurationFileWatcherConfig {
+        ConfigurationFileWatcherConfig::default()
+    }
+
+    /// Update check interval with `interval`.
+    pub fn with_interval(mut self, interval: Duration) -> ConfigurationFileWatcherConfig {
+        self.interval = interval;
+        self
+    }
+}
+
+impl Default for ConfigurationFileWatcherConfig {
+    fn default() -> ConfigurationFileWatcherConfig {
+        ConfigurationFileWatcherConfig {
+            interval: ConfigurationFileWatcherConfig::DEFAULT_INTERVAL,
+        }
+    }
+}
+
+/// Handle to a running configuration file watcher.
+#[derive(Debug)]
+pub struct ConfigurationFileWatcher {
+    cancellation_token: CancellationToken,
+}
+
+impl ConfigurationFileWatcher {
+    /// Stop watching files.
+    pub fn stop(&self) {
+        self.cancellation_token.cancel();
+    }
+}
+
+/// Watch configuration `files` for changes and reload them into `store`.
+///
+/// Files are checked for changes (modification time and size) every
+/// [`ConfigurationFileWatcherConfig::interval`]. Checks that find the files unchanged confirm that
+/// the stored configuration is up to date. The watcher does not load the files initially,
+/// so [`ConfigurationFiles::load()`] should be used to initialize the store. If reloading fails
+/// (e.g., a file is only partially written or contains invalid configuration), the store keeps
+/// the previous configuration.
+pub fn start_configuration_file_watcher<AR: AsyncRuntime>(
+    runtime: &BackgroundRuntime<AR>,
+    files: ConfigurationFiles,
+    store: Arc<ConfigurationStore>,
+    sdk_metadata: SdkMetadata,
+    config: ConfigurationFileWatcherConfig,
+) -> ConfigurationFileWatcher {
+    #[cfg(not(target_arch = "wasm32"))]
+    let spawn = |f| runtime.spawn_untracked(f);
+
+    #[cfg(target_arch = "wasm32")]
+    let spawn = wasm_bindgen_futures::spawn_local;
+
+    let cancellation_token = runtime.cancellation_token();
+    log::info!(target: "eppo", "watching configuration file {}", files.flags.display());
+    spawn({
+        let cancellation_token = cancellation_token.clone();
+        async move {
+            cancellation_token
+                .run_until_cancelled(watch_configuration_files(
+                    files,
+                    store,
+                    sdk_metadata,
+                    config,
+                ))
+                .await;
+        }
+    });
+
+    ConfigurationFileWatcher { cancellation_token }
+}
+
+async fn watch_configuration_files(
+    files: ConfigurationFiles,
+    store: Arc<ConfigurationStore>,
+    sdk_metadata: SdkMetadata,
+    config: ConfigurationFileWatcherConfig,
+) {
+    let mut source = FileConfigurationSource::loaded(files, sdk_metadata);
+    let mut failing = false;
+
+    loop {
+        sleep(config.interval).await;
+
+        match source.fetch().await {
+            Ok(Some(configuration)) => {
+                log_configuration_changes(&store, &configuration);
+                store.set_configuration(Arc::new(configuration));
+                failing = false;
+            }
+            Ok(None) => {
+                if !failing {
+                    store.confirm_configuration();
+                }
+            }
+            Err(err) => {
+                // The source returns the same error until files change again, so only log when
+                // reloading starts failing. Keep serving the previous configuration meanwhile.
+                if !failing {
+                    log::warn!(target: "eppo", "failed to reload configuration files, keeping previous configuration: {err}");
+                }
+                failing = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use crate::{
+        background::BackgroundThread,
+        configuration_builder::{ConfigurationBuilder, FlagBuilder},
+        configuration_store::ConfigurationStore,
+        ufc::VariationType,
+        SdkMetadata,
+    };
+
+    use super::{
+        start_configuration_file_watcher, ConfigurationFileWatcherConfig, ConfigurationFiles,
+    };
+
+    const SDK_METADATA: SdkMetadata = SdkMetadata {
+        name: "test",
+        version: "0.1.0",
+    };
+
+    fn flags_json(flag_keys: &[&str]) -> Vec<u8> {
+        flag_keys
+            .iter()
+            .fold(ConfigurationBuilder::new(), |builder, key| {
+                builder.flag(FlagBuilder::new(*key, VariationType::Boolean))
+            })
+            .to_flags_json()
+    }
+
+    fn flag_count(store: &ConfigurationStore) -> Option<usize> {
+        store
+            .get_configuration()
+            .map(|configuration| configuration.flag_keys().count())
+    }
+
+    #[test]
+    fn loads_configuration_from_files() {
+        let dir = std::env::temp_dir().join(format!("eppo-files-load-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("flags.json"), flags_json(&["a", "b"])).unwrap();
+
+        let configuration = ConfigurationFiles::new(dir.join("flags.json"))
+            .load(SDK_METADATA)
+            .unwrap();
+        assert_eq!(configuration.flag_keys().count(), 2);
+
+        let missing = ConfigurationFiles::new(dir.join("flags.json"))
+            .with_bandits(dir.join("missing.json"))
+            .load(SDK_METADATA);
+        assert!(missing.is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reloads_changed_files() {
+        let dir = std::env::temp_dir().join(format!("eppo-files-watch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("flags.json");
+        std::fs::write(&path, flags_json(&["a"])).unwrap();
+
+        let files = ConfigurationFiles::new(&path);
+        let store = Arc::new(ConfigurationStore::new());
+        store.set_configuration(Arc::new(files.load(SDK_METADATA).unwrap()));
+
+        let thread = BackgroundThread::start().unwrap();
+        let watcher = start_configuration_file_watcher(
+            thread.runtime(),
+            files,
+            store.clone(),
+            SDK_METADATA,
+            ConfigurationFileWatcherConfig::new().with_interval(Duration::from_millis(10)),
+        );
+
+        let wait_for_flags = |count| {
+            let deadline = std::time::Instant::now() + Duration::from_secs(5);
+            while flag_count(&store) != Some(count) && std::time::Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            flag_count(&store)
+        };
+
+        // Invalid configuration is ignored.
+        std::fs::write(&path, b"{ not json").unwrap();
+        std::thread::sleep(Duration::from_millis(100));
+        assert_eq!(flag_count(&store), Some(1));
+
+        std::fs::write(&path, flags_json(&["a", "b", "c"])).unwrap();
+        assert_eq!(wait_for_flags(3), Some(3));
+
+        watcher.stop();
+        thread.shutdown();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn confirms_unchanged_files() {
+        let dir = std::env::temp_dir().join(format!("eppo-files-confirm-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("flags.json");
+        std::fs::write(&path, flags_json(&["a"])).unwrap();
+
+        let files = ConfigurationFiles::new(&path);
+        let store = Arc::new(ConfigurationStore::new());
+        store.set_configuration(Arc::new(files.load(SDK_METADATA).unwrap()));
+        let loaded_at = store.configuration_confirmed_at().unwrap();
+
+        let thread = BackgroundThread::start().unwrap();
+        let watcher = start_configuration_file_watcher(
+            thread.runtime(),
+            files,
+            store.clone(),
+            SDK_METADATA,
+            ConfigurationFileWatcherConfig::new().with_interval(Duration::from_millis(10)),
+        );
+
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(store.configuration_confirmed_at().unwrap() > loaded_at);
+
+        // Configuration is not confirmed while files fail to load.
+        std::fs::write(&path, b"{ not json").unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+        let failed_at = store.configuration_confirmed_at().unwrap();
+        std::thread::sleep(Duration::from_millis(100));
+        assert_eq!(store.configuration_confirmed_at().unwrap(), failed_at);
+
+        watcher.stop();
+        thread.shutdown();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,5 +1,9 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
+use exponential_backoff::Backoff;
 use rand::{thread_rng, Rng};
 use tokio::sync::watch;
 use tokio_util::sync::CancellationToken;
@@ -11,9 +15,13 @@ use wasmtimer::tokio::sleep;
 
 use crate::{
     background::{AsyncRuntime, BackgroundRuntime},
+    configuration_cache::ConfigurationCache,
     configuration_fetcher::ConfigurationFetcher,
+    configuration_source::{ConfigurationSource, MaybeSend},
     configuration_store::ConfigurationStore,
-    Error,
+    configuration_validator::ConfigurationValidator,
+    timestamp::{self, Timestamp},
+    Configuration, Error,
 };
 
 /// Configuration for [`configuration_poller`].
@@ -29,6 +37,39 @@ pub struct ConfigurationPollerConfig {
     ///
     /// Defaults to [`ConfigurationPollerConfig::DEFAULT_POLL_JITTER`].
     pub jitter: Duration,
+    /// Delay before the first retry of a failed fetch. Subsequent retries back off exponentially
+    /// (with jitter) up to [`ConfigurationPollerConfig::interval`].
+    ///
+    /// Only network errors, timeouts, and server errors (5xx and 429 Too Many Requests) are
+    /// retried. Other errors wait for the next poll.
+    ///
+    /// Defaults to [`ConfigurationPollerConfig::DEFAULT_RETRY_DELAY`].
+    pub retry_delay: Duration,
+    /// If set, every newly fetched configuration is persisted to the cache, so it can be loaded
+    /// on the next start with [`ConfigurationCache::load()`].
+    ///
+    /// Defaults to `None`.
+    pub cache: Option<ConfigurationCache>,
+    /// If set, the poller holds a server-sent events connection to this URL and applies pushed
+    /// configuration updates immediately. Polling is used as a fallback while the stream is
+    /// disconnected.
+    ///
+    /// The stream understands the following events:
+    /// - `flags` — `data` contains flags configuration in UFC format. It is applied immediately.
+    /// - `refresh` — configuration has changed and should be re-fetched from the server.
+    ///
+    /// Streaming is only supported when polling [`ConfigurationFetcher`]. It is not supported on
+    /// wasm32, where this field is ignored.
+    ///
+    /// Defaults to `None`.
+    pub streaming_url: Option<String>,
+    /// If set, newly fetched configuration is applied only if it passes validation. Rejected
+    /// configuration is discarded, the previous configuration stays active, and the rejection is
+    /// reported in [`ConfigurationPoller::status()`] as [`Error::ConfigurationRejected`] until a
+    /// valid configuration is fetched.
+    ///
+    /// Defaults to `None`.
+    pub validator: Option<ConfigurationValidator>,
 }
 
 impl ConfigurationPollerConfig {
@@ -36,6 +77,8 @@ impl ConfigurationPollerConfig {
     pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(30);
     /// Default value for [`ConfigurationPollerConfig::jitter`].
     pub const DEFAULT_POLL_JITTER: Duration = Duration::from_secs(3);
+    /// Default value for [`ConfigurationPollerConfig::retry_delay`].
+    pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(1);
 
     /// Create a new `ConfigurationPollerConfig` using default configuration.
     pub fn new() -> ConfigurationPollerConfig {
@@ -53,6 +96,33 @@ impl ConfigurationPollerConfig {
         self.jitter = jitter;
         self
     }
+
+    /// Update delay before the first retry of a failed fetch with `retry_delay`.
+    pub fn with_retry_delay(mut self, retry_delay: Duration) -> ConfigurationPollerConfig {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Persist fetched configuration to `cache`.
+    pub fn with_cache(mut self, cache: ConfigurationCache) -> ConfigurationPollerConfig {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Receive configuration updates from the server-sent events stream at `url`.
+    pub fn with_streaming_url(mut self, url: impl Into<String>) -> ConfigurationPollerConfig {
+        self.streaming_url = Some(url.into());
+        self
+    }
+
+    /// Validate fetched configuration with `validator` before applying it.
+    pub fn with_validator(
+        mut self,
+        validator: ConfigurationValidator,
+    ) -> ConfigurationPollerConfig {
+        self.validator = Some(validator);
+        self
+    }
 }
 
 impl Default for ConfigurationPollerConfig {
@@ -60,16 +130,87 @@ impl Default for ConfigurationPollerConfig {
         ConfigurationPollerConfig {
             interval: ConfigurationPollerConfig::DEFAULT_POLL_INTERVAL,
             jitter: ConfigurationPollerConfig::DEFAULT_POLL_JITTER,
+            retry_delay: ConfigurationPollerConfig::DEFAULT_RETRY_DELAY,
+            cache: None,
+            streaming_url: None,
+            validator: None,
         }
     }
 }
 
-#[derive(Debug)]
 pub struct ConfigurationPoller {
     status: watch::Receiver<Option<Result<(), crate::Error>>>,
+    health: Arc<Mutex<PollerHealth>>,
+    store: Arc<ConfigurationStore>,
     cancellation_token: CancellationToken,
 }
 
+impl std::fmt::Debug for ConfigurationPoller {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfigurationPoller")
+            .field("status", &self.status)
+            .field("health", &self.health)
+            .field("cancellation_token", &self.cancellation_token)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Snapshot of configuration poller health returned by [`ConfigurationPoller::status()`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ConfigurationPollerStatus {
+    /// Whether the poller is still running. The poller stops when it's stopped explicitly, its
+    /// background runtime is stopped, or it encounters an unrecoverable error (e.g., invalid API
+    /// key).
+    pub running: bool,
+    /// When configuration was last fetched successfully (including fetches that found
+    /// configuration unchanged).
+    pub last_success_at: Option<Timestamp>,
+    /// The most recent error. It is kept after subsequent successful fetches, so compare
+    /// `last_error_at` with `last_success_at` or check `consecutive_failures` to tell if the
+    /// poller is currently failing.
+    pub last_error: Option<Error>,
+    /// When `last_error` occurred.
+    pub last_error_at: Option<Timestamp>,
+    /// Number of failed fetches since the last successful one.
+    pub consecutive_failures: u32,
+    /// When the active configuration was fetched by the SDK.
+    pub configuration_fetched_at: Option<Timestamp>,
+    /// When the active configuration was created on the server.
+    pub configuration_created_at: Option<Timestamp>,
+    /// When configuration was last known to be up to date: when it was last fetched or when the
+    /// source last confirmed it has not changed, whichever is later.
+    pub configuration_confirmed_at: Option<Timestamp>,
+}
+
+impl ConfigurationPollerStatus {
+    /// Time elapsed since configuration was last known to be up to date, or `None` if there is no
+    /// configuration yet.
+    ///
+    /// Fetches that find configuration unchanged reset the age, so the age of a healthy poller
+    /// stays within the poll interval even if configuration does not change.
+    pub fn configuration_age(&self) -> Option<Duration> {
+        let confirmed_at = self.configuration_confirmed_at?;
+        Some(
+            (timestamp::now() - confirmed_at)
+                .to_std()
+                .unwrap_or(Duration::ZERO),
+        )
+    }
+}
+
+/// Poller health shared between the poller task and [`ConfigurationPoller`].
+#[derive(Debug, Default)]
+struct PollerHealth {
+    last_success_at: Option<Timestamp>,
+    last_error: Option<(Timestamp, Error)>,
+    consecutive_failures: u32,
+    /// Set while the latest fetched configuration is rejected by the validator.
+    rejection: Option<Error>,
+    /// Set when the poller exits because of an unrecoverable error.
+    stopped: bool,
+}
+
 impl ConfigurationPoller {
     pub async fn wait_for_configuration(&self) -> Result<(), crate::Error> {
         let mut status_rx = self.status.clone();
@@ -83,14 +224,34 @@ impl ConfigurationPoller {
             .expect("option should always be Some because it's checked in .wait_for()")
     }
 
+    /// Return the current poller health.
+    pub fn status(&self) -> ConfigurationPollerStatus {
+        let configuration = self.store.get_configuration();
+        let health = self.health.lock().unwrap_or_else(|err| err.into_inner());
+        ConfigurationPollerStatus {
+            running: !health.stopped && !self.cancellation_token.is_cancelled(),
+            last_success_at: health.last_success_at,
+            last_error: health.last_error.as_ref().map(|(_, err)| err.clone()),
+            last_error_at: health.last_error.as_ref().map(|(at, _)| *at),
+            consecutive_failures: health.consecutive_failures,
+            configuration_fetched_at: configuration.as_ref().map(|it| it.fetched_at()),
+            configuration_created_at: configuration.as_ref().map(|it| it.created_at()),
+            configuration_confirmed_at: self.store.configuration_confirmed_at(),
+        }
+    }
+
     pub fn stop(&self) {
         self.cancellation_token.cancel();
     }
 }
 
-pub fn start_configuration_poller<AR: AsyncRuntime>(
+/// Start polling `source` for configuration and storing it in `store`.
+pub fn start_configuration_poller<
+    AR: AsyncRuntime,
+    S: ConfigurationSource + MaybeSend + 'static,
+>(
     runtime: &BackgroundRuntime<AR>,
-    fetcher: ConfigurationFetcher,
+    source: S,
     store: Arc<ConfigurationStore>,
     config: ConfigurationPollerConfig,
 ) -> ConfigurationPoller {
@@ -100,42 +261,282 @@ pub fn start_configuration_poller<AR: AsyncRuntime>(
     #[cfg(not(target_arch = "wasm32"))]
     let spawn = |f| runtime.spawn_untracked(f);
 
-    // On wasm32, reqwest is non-send, so we can't use normal spawn.
+    // On wasm32, reqwest is non-send (and so are configuration sources), so we can't use normal
+    // spawn.
     #[cfg(target_arch = "wasm32")]
     let spawn = wasm_bindgen_futures::spawn_local;
 
     let (status_tx, status_rx) = watch::channel(None);
+    let reporter = StatusReporter::new(status_tx);
+    let health = reporter.health.clone();
 
     let cancellation_token = runtime.cancellation_token();
     log::info!(target: "eppo", "starting configuration poller");
     spawn({
         let cancellation_token = cancellation_token.clone();
+        let store = store.clone();
         async move {
             cancellation_token
-                .run_until_cancelled(configuration_poller(fetcher, store, config, status_tx))
+                .run_until_cancelled(configuration_poller(source, store, config, reporter))
                 .await;
         }
     });
 
     ConfigurationPoller {
         status: status_rx,
+        health,
+        store,
         cancellation_token,
     }
 }
 
-/// Polls periodically for `Configuration` using `fetcher` and stores it in a `store`. Additionally,
-/// it reports its current status (successfully fetched configuration or error occurred) to
-/// `status`.
-async fn configuration_poller(
-    mut fetcher: ConfigurationFetcher,
+/// Polls periodically for `Configuration` from `source` and stores it in a `store`. If streaming
+/// is configured, the poller listens for pushed updates between polls. Additionally, it reports
+/// its current status (successfully fetched configuration or error occurred) to `status`.
+async fn configuration_poller<S: ConfigurationSource>(
+    mut source: S,
     store: Arc<ConfigurationStore>,
     config: ConfigurationPollerConfig,
+    status: StatusReporter,
+) {
+    #[cfg(target_arch = "wasm32")]
+    if config.streaming_url.is_some() {
+        log::warn!(target: "eppo", "configuration streaming is not supported on this platform, falling back to polling");
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    if config.streaming_url.is_some() && source.stream().is_none() {
+        log::warn!(target: "eppo", "configuration streaming is only supported for HTTP configuration source, falling back to polling");
+    }
+
+    // Backoff delays for the current streak of failed fetches.
+    let mut retries = None;
+
+    loop {
+        match fetch_configuration(&mut source, &store, &config, &status).await {
+            Ok(()) => {
+                retries = None;
+            }
+            Err(err @ (Error::Unauthorized | Error::InvalidBaseUrl(_))) => {
+                // These errors are not recoverable. Update result and exit the poller.
+                status.fatal(err);
+                return;
+            }
+            Err(err) if is_retriable(&err) => {
+                let delay = retries
+                    .get_or_insert_with(|| {
+                        Backoff::new(u32::MAX, config.retry_delay, config.interval).into_iter()
+                    })
+                    .next()
+                    .flatten()
+                    .unwrap_or(config.interval);
+                log::debug!(target: "eppo", "retrying configuration fetch in {delay:?}: {err}");
+                sleep(delay).await;
+                continue;
+            }
+            Err(_) => {
+                // Wait for the next poll.
+                retries = None;
+            }
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let (Some(url), None, Some(fetcher)) = (&config.streaming_url, &retries, source.stream())
+        {
+            match stream_configuration(fetcher, url, &store, &config, &status).await {
+                Ok(()) => {
+                    log::warn!(target: "eppo", "configuration stream closed, falling back to polling");
+                }
+                Err(err @ (Error::Unauthorized | Error::InvalidBaseUrl(_))) => {
+                    status.fatal(err);
+                    return;
+                }
+                Err(err) => {
+                    log::warn!(target: "eppo", "configuration stream failed, falling back to polling: {err}");
+                }
+            }
+        }
+
+        let timeout = jitter(config.interval, config.jitter);
+
+        sleep(timeout).await;
+    }
+}
+
+/// Fetch configuration from `source` and apply it to `store`.
+async fn fetch_configuration(
+    source: &mut impl ConfigurationSource,
+    store: &ConfigurationStore,
+    config: &ConfigurationPollerConfig,
+    status: &StatusReporter,
+) -> Result<(), Error> {
+    let fetched = source
+        .fetch()
+        .await
+        .inspect_err(|err| status.failure(err))?;
+    if let Some(configuration) = fetched {
+        apply_configuration(store, config, configuration)
+            .await
+            .inspect_err(|err| status.rejected(err))?;
+    } else if let Some(err) = status.pending_rejection() {
+        // Configuration has not changed since it was rejected, so it is still invalid.
+        status.failure(&err);
+        return Err(err);
+    } else {
+        // Configuration has not changed since the last fetch, so there is nothing to update in
+        // the store besides confirming it's still up to date.
+        store.confirm_configuration();
+    }
+    status.success();
+    Ok(())
+}
+
+/// Returns `true` if the failed fetch is likely to succeed if retried soon.
+fn is_retriable(err: &Error) -> bool {
+    match err {
+        Error::Network(err) => err.status().map_or(true, |status| {
+            status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        }),
+        Error::Timeout | Error::Io(_) => true,
+        _ => false,
+    }
+}
+
+/// Listen to server-sent events at `url` and apply pushed configuration until the stream is
+/// closed.
+#[cfg(not(target_arch = "wasm32"))]
+async fn stream_configuration(
+    fetcher: &mut ConfigurationFetcher,
+    url: &str,
+    store: &ConfigurationStore,
+    config: &ConfigurationPollerConfig,
+    status: &StatusReporter,
+) -> Result<(), Error> {
+    let mut response = fetcher.connect_stream(url).await?;
+    log::info!(target: "eppo", "connected to configuration stream");
+    confirm_streamed_configuration(store, status);
+
+    let mut parser = EventStreamParser::default();
+    while let Some(chunk) = response.chunk().await? {
+        // While the stream is connected, any data (including keep-alives) means that the server
+        // would have pushed newer configuration if there was one.
+        confirm_streamed_configuration(store, status);
+        for event in parser.feed(&chunk)? {
+            match event.event.as_str() {
+                "flags" => match fetcher.configuration_from_pushed_flags(event.data.into_bytes()) {
+                    Ok(Some(configuration)) => {
+                        log::debug!(target: "eppo", "received configuration from stream");
+                        match apply_configuration(store, config, configuration).await {
+                            Ok(()) => status.success(),
+                            Err(err) => status.rejected(&err),
+                        }
+                    }
+                    Ok(None) => {
+                        // Pushed flags reference bandits, so we need to fetch the rest of
+                        // configuration.
+                        refetch_configuration(fetcher, store, config, status).await?;
+                    }
+                    Err(err) => {
+                        log::warn!(target: "eppo", "failed to parse configuration received from stream: {err}");
+                    }
+                },
+                "refresh" => {
+                    refetch_configuration(fetcher, store, config, status).await?;
+                }
+                other => {
+                    log::debug!(target: "eppo", "ignoring unknown configuration stream event: {other:?}");
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Record that the active configuration is up to date because the stream is healthy. Rejected
+/// configuration is not confirmed, the same way as when polling.
+#[cfg(not(target_arch = "wasm32"))]
+fn confirm_streamed_configuration(store: &ConfigurationStore, status: &StatusReporter) {
+    if status.pending_rejection().is_none() {
+        store.confirm_configuration();
+    }
+}
+
+/// Fetch configuration in response to a stream event. Only unrecoverable errors are returned, so
+/// the stream is kept open otherwise.
+#[cfg(not(target_arch = "wasm32"))]
+async fn refetch_configuration(
+    fetcher: &mut ConfigurationFetcher,
+    store: &ConfigurationStore,
+    config: &ConfigurationPollerConfig,
+    status: &StatusReporter,
+) -> Result<(), Error> {
+    match fetch_configuration(fetcher, store, config, status).await {
+        Err(err @ (Error::Unauthorized | Error::InvalidBaseUrl(_))) => Err(err),
+        Err(err) => {
+            log::warn!(target: "eppo", "failed to fetch configuration requested by stream: {err}");
+            Ok(())
+        }
+        Ok(()) => Ok(()),
+    }
+}
+
+/// Reports poller progress to [`ConfigurationPoller`].
+struct StatusReporter {
     // TODO: This option-result is somewhat convoluted. Maybe remodel this with an explicit
-    // ConfigurationPollerStatus enum.
+    // status enum.
     status: watch::Sender<Option<Result<(), crate::Error>>>,
-) {
-    let update_status = move |next: Result<(), crate::Error>| {
-        status.send_if_modified(|value| {
+    health: Arc<Mutex<PollerHealth>>,
+}
+
+impl StatusReporter {
+    fn new(status: watch::Sender<Option<Result<(), crate::Error>>>) -> StatusReporter {
+        StatusReporter {
+            status,
+            health: Arc::default(),
+        }
+    }
+
+    /// Record a successful fetch or pushed configuration update.
+    fn success(&self) {
+        {
+            let mut health = self.health();
+            health.last_success_at = Some(timestamp::now());
+            health.consecutive_failures = 0;
+            health.rejection = None;
+        }
+        self.update(Ok(()));
+    }
+
+    /// Record fetched configuration that was rejected by the validator.
+    fn rejected(&self, err: &Error) {
+        self.failure(err);
+        self.health().rejection = Some(err.clone());
+    }
+
+    /// Returns the rejection error if the latest fetched configuration was rejected.
+    fn pending_rejection(&self) -> Option<Error> {
+        self.health().rejection.clone()
+    }
+
+    /// Record a failed fetch.
+    fn failure(&self, err: &Error) {
+        let mut health = self.health();
+        health.last_error = Some((timestamp::now(), err.clone()));
+        health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+    }
+
+    /// Record an unrecoverable error that stops the poller.
+    fn fatal(&self, err: Error) {
+        {
+            let mut health = self.health();
+            health.last_error = Some((timestamp::now(), err.clone()));
+            health.stopped = true;
+        }
+        self.update(Err(err));
+    }
+
+    fn update(&self, next: Result<(), crate::Error>) {
+        self.status.send_if_modified(|value| {
             let update = value.as_ref().is_none()
                 || value
                     .as_ref()
@@ -145,27 +546,180 @@ async fn configuration_poller(
             }
             update
         });
-    };
+    }
 
-    loop {
-        match fetcher.fetch_configuration().await {
-            Ok(configuration) => {
-                store.set_configuration(Arc::new(configuration));
-                update_status(Ok(()));
-            }
-            Err(err @ (Error::Unauthorized | Error::InvalidBaseUrl(_))) => {
-                // These errors are not recoverable. Update result and exit the poller.
-                update_status(Err(Error::from(err)));
-                return;
+    fn health(&self) -> std::sync::MutexGuard<'_, PollerHealth> {
+        self.health.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+/// Validate `configuration` and make it active. Rejected configuration is not cached or stored.
+async fn apply_configuration(
+    store: &ConfigurationStore,
+    config: &ConfigurationPollerConfig,
+    configuration: Configuration,
+) -> Result<(), Error> {
+    if let Some(validator) = &config.validator {
+        let previous = store.get_configuration();
+        validator
+            .validate(&configuration, previous.as_deref())
+            .inspect_err(|err| {
+                log::warn!(target: "eppo", "rejected new configuration, keeping the previous one: {err}");
+            })?;
+    }
+    let configuration = Arc::new(configuration);
+    if let Some(cache) = &config.cache {
+        store_in_cache(cache, configuration.clone()).await;
+    }
+    log_configuration_changes(store, &configuration);
+    store.set_configuration(configuration);
+    Ok(())
+}
+
+/// Write `configuration` to `cache` without blocking the async runtime on file system calls.
+async fn store_in_cache(cache: &ConfigurationCache, configuration: Arc<Configuration>) {
+    // Errors are logged by the cache and are not fatal for the poller.
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let cache = cache.clone();
+        let _ = tokio::task::spawn_blocking(move || cache.store(&configuration)).await;
+    }
+    #[cfg(target_arch = "wasm32")]
+    let _ = cache.store(&configuration);
+}
+
+/// A single event received from a server-sent events stream.
+#[derive(Debug, PartialEq, Eq)]
+struct StreamEvent {
+    event: String,
+    data: String,
+}
+
+/// Incremental parser for `text/event-stream` responses.
+///
+/// See <https://html.spec.whatwg.org/multipage/server-sent-events.html#event-stream-interpretation>.
+/// `id` and `retry` fields are not used by the poller and are ignored.
+#[derive(Debug)]
+struct EventStreamParser {
+    /// Bytes of the current incomplete line.
+    line: Vec<u8>,
+    /// Whether the last seen byte was `\r`, so a following `\n` should be skipped.
+    after_cr: bool,
+    event: String,
+    data: String,
+    /// Maximum size of a single line or event data in bytes.
+    max_size: usize,
+}
+
+impl Default for EventStreamParser {
+    fn default() -> EventStreamParser {
+        EventStreamParser::with_max_size(EventStreamParser::DEFAULT_MAX_SIZE)
+    }
+}
+
+impl EventStreamParser {
+    /// Default limit for line and event size. Pushed flags configuration is well below it, so a
+    /// larger event means the stream is misbehaving.
+    const DEFAULT_MAX_SIZE: usize = 8 * 1024 * 1024;
+
+    fn with_max_size(max_size: usize) -> EventStreamParser {
+        EventStreamParser {
+            line: Vec::new(),
+            after_cr: false,
+            event: String::new(),
+            data: String::new(),
+            max_size,
+        }
+    }
+
+    /// Feed the next chunk of the stream and return events that were completed by it.
+    ///
+    /// Returns an error if a line or an event exceeds the size limit, in which case the stream
+    /// should be dropped.
+    fn feed(&mut self, chunk: &[u8]) -> Result<Vec<StreamEvent>, Error> {
+        let mut events = Vec::new();
+        for &byte in chunk {
+            let after_cr = std::mem::replace(&mut self.after_cr, byte == b'\r');
+            match byte {
+                b'\n' if after_cr => {}
+                b'\r' | b'\n' => {
+                    let line = std::mem::take(&mut self.line);
+                    if let Some(event) = self.process_line(&String::from_utf8_lossy(&line))? {
+                        events.push(event);
+                    }
+                }
+                _ if self.line.len() >= self.max_size => return Err(self.too_large("line")),
+                _ => self.line.push(byte),
             }
-            _ => {
-                // Other errors are retriable.
+        }
+        Ok(events)
+    }
+
+    fn process_line(&mut self, line: &str) -> Result<Option<StreamEvent>, Error> {
+        if line.is_empty() {
+            return Ok(self.dispatch());
+        }
+        if line.starts_with(':') {
+            // Comment, usually used as a keep-alive.
+            return Ok(None);
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event = value.to_owned(),
+            "data" => {
+                if self.data.len() + value.len() >= self.max_size {
+                    return Err(self.too_large("event"));
+                }
+                self.data.push_str(value);
+                self.data.push('\n');
             }
+            _ => {}
         }
+        Ok(None)
+    }
 
-        let timeout = jitter(config.interval, config.jitter);
+    fn too_large(&self, what: &str) -> Error {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!(
+                "configuration stream {what} exceeds {} bytes",
+                self.max_size
+            ),
+        )
+        .into()
+    }
 
-        sleep(timeout).await;
+    fn dispatch(&mut self) -> Option<StreamEvent> {
+        let mut event = std::mem::take(&mut self.event);
+        let mut data = std::mem::take(&mut self.data);
+        if data.is_empty() {
+            return None;
+        }
+        data.pop();
+        if event.is_empty() {
+            event.push_str("message");
+        }
+        Some(StreamEvent { event, data })
+    }
+}
+
+/// Log a human-readable summary of changes between currently active configuration and
+/// `configuration`.
+pub(crate) fn log_configuration_changes(store: &ConfigurationStore, configuration: &Configuration) {
+    if !log::log_enabled!(target: "eppo", log::Level::Info) {
+        // Skip computing the diff if it's not going to be logged anyway.
+        return;
+    }
+    let Some(previous) = store.get_configuration() else {
+        return;
+    };
+    let diff = previous.diff(configuration);
+    if !diff.is_empty() {
+        log::info!(target: "eppo", "configuration updated:\n{diff}");
     }
 }
 
@@ -208,3 +762,520 @@ mod jitter_tests {
         assert_eq!(result, Duration::from_secs(30));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use tokio::sync::watch;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use crate::{
+        background::BackgroundThread,
+        configuration_builder::{
+            AllocationBuilder, ConfigurationBuilder, FlagBuilder, SplitBuilder,
+        },
+        configuration_cache::ConfigurationCache,
+        configuration_fetcher::{
+            ConfigurationFetcher, ConfigurationFetcherConfig, HttpClientConfig,
+        },
+        configuration_source::InMemoryConfigurationSource,
+        configuration_store::ConfigurationStore,
+        configuration_validator::ConfigurationValidator,
+        eval::{Evaluator, EvaluatorConfig, StaleConfigurationPolicy},
+        ufc::{AssignmentValue, VariationType},
+        Configuration, SdkMetadata,
+    };
+
+    use super::{
+        configuration_poller, start_configuration_poller, ConfigurationPollerConfig,
+        EventStreamParser, StatusReporter, StreamEvent,
+    };
+
+    fn test_fetcher(mock_server: &MockServer) -> ConfigurationFetcher {
+        ConfigurationFetcher::new(ConfigurationFetcherConfig {
+            base_url: mock_server.uri(),
+            api_key: "test-api-key".to_owned(),
+            sdk_metadata: SdkMetadata {
+                name: "test-sdk",
+                version: "1.0.0",
+            },
+            http_client: HttpClientConfig::default(),
+        })
+        .unwrap()
+    }
+
+    fn configuration(flags: &[&str]) -> Configuration {
+        flags
+            .iter()
+            .fold(ConfigurationBuilder::new(), |builder, key| {
+                builder.flag(FlagBuilder::new(*key, VariationType::Boolean))
+            })
+            .build()
+            .unwrap()
+    }
+
+    /// Wait until `store` holds configuration with `count` flags.
+    async fn wait_for_flag_count(store: &ConfigurationStore, count: usize) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while store
+                .get_configuration()
+                .map(|configuration| configuration.flag_keys().count())
+                != Some(count)
+            {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("configuration should be applied");
+    }
+
+    #[tokio::test]
+    async fn polls_configuration_source() {
+        let source = InMemoryConfigurationSource::new(configuration(&["a"]));
+        let store = Arc::new(ConfigurationStore::new());
+        let config = ConfigurationPollerConfig::new()
+            .with_interval(Duration::from_millis(10))
+            .with_jitter(Duration::ZERO)
+            .with_retry_delay(Duration::from_millis(10));
+        let (status_tx, mut status_rx) = watch::channel(None);
+
+        let poller = tokio::spawn(configuration_poller(
+            source.clone(),
+            store.clone(),
+            config,
+            StatusReporter::new(status_tx),
+        ));
+
+        wait_for_flag_count(&store, 1).await;
+        assert!(matches!(*status_rx.borrow_and_update(), Some(Ok(()))));
+
+        source.set_error(crate::Error::Timeout);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        source.set_configuration(configuration(&["a", "b"]));
+        wait_for_flag_count(&store, 2).await;
+        poller.abort();
+    }
+
+    #[tokio::test]
+    async fn stores_configuration_in_cache() {
+        let dir = std::env::temp_dir().join(format!(
+            "eppo-configuration-poller-cache-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        let cache = ConfigurationCache::new(&dir);
+        let store = Arc::new(ConfigurationStore::new());
+        let config = ConfigurationPollerConfig::new()
+            .with_interval(Duration::from_millis(10))
+            .with_jitter(Duration::ZERO)
+            .with_cache(cache.clone());
+        let (status_tx, _status_rx) = watch::channel(None);
+
+        let poller = tokio::spawn(configuration_poller(
+            InMemoryConfigurationSource::new(configuration(&["a", "b"])),
+            store.clone(),
+            config,
+            StatusReporter::new(status_tx),
+        ));
+
+        wait_for_flag_count(&store, 2).await;
+        poller.abort();
+
+        let cached = cache.load(SdkMetadata {
+            name: "test-sdk",
+            version: "1.0.0",
+        });
+        assert_eq!(cached.unwrap().unwrap().flag_keys().count(), 2);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reports_poller_status() {
+        let thread = BackgroundThread::start().unwrap();
+        let source = InMemoryConfigurationSource::new(configuration(&["a"]));
+        let poller = start_configuration_poller(
+            thread.runtime(),
+            source.clone(),
+            Arc::new(ConfigurationStore::new()),
+            ConfigurationPollerConfig::new()
+                .with_interval(Duration::from_millis(10))
+                .with_jitter(Duration::ZERO)
+                .with_retry_delay(Duration::from_millis(10)),
+        );
+        thread
+            .runtime()
+            .async_runtime
+            .block_on(poller.wait_for_configuration())
+            .unwrap();
+
+        let status = poller.status();
+        assert!(status.running);
+        assert!(status.last_success_at.is_some());
+        assert!(status.configuration_fetched_at.is_some());
+        assert!(status.configuration_created_at.is_some());
+        assert!(status.configuration_age().unwrap() < Duration::from_secs(60));
+
+        source.set_error(crate::Error::Timeout);
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while poller.status().consecutive_failures < 2 {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "failures are not reported"
+            );
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        let status = poller.status();
+        assert!(matches!(status.last_error, Some(crate::Error::Timeout)));
+        assert!(status.last_error_at.is_some());
+        // Configuration is kept while the source is failing.
+        assert!(status.configuration_fetched_at.is_some());
+
+        poller.stop();
+        assert!(!poller.status().running);
+        thread.shutdown();
+    }
+
+    #[test]
+    fn unchanged_configuration_resets_age() {
+        let thread = BackgroundThread::start().unwrap();
+        let mut old = configuration(&["a"]);
+        old.fetched_at = chrono::Utc::now() - chrono::Duration::hours(2);
+        let source = InMemoryConfigurationSource::new(old);
+        let poller = start_configuration_poller(
+            thread.runtime(),
+            source,
+            Arc::new(ConfigurationStore::new()),
+            ConfigurationPollerConfig::new()
+                .with_interval(Duration::from_millis(10))
+                .with_jitter(Duration::ZERO),
+        );
+        thread
+            .runtime()
+            .async_runtime
+            .block_on(poller.wait_for_configuration())
+            .unwrap();
+
+        // The source keeps reporting configuration as unchanged, which confirms it's up to date.
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while poller.status().configuration_age().unwrap() > Duration::from_secs(60) {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "unchanged configuration should reset its age"
+            );
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(
+            poller.status().configuration_fetched_at.unwrap()
+                < chrono::Utc::now() - chrono::Duration::hours(1)
+        );
+
+        poller.stop();
+        thread.shutdown();
+    }
+
+    #[test]
+    fn keeps_previous_configuration_when_rejected() {
+        let thread = BackgroundThread::start().unwrap();
+        let source = InMemoryConfigurationSource::new(configuration(&["a"]));
+        let store = Arc::new(ConfigurationStore::new());
+        let poller = start_configuration_poller(
+            thread.runtime(),
+            source.clone(),
+            store.clone(),
+            ConfigurationPollerConfig::new()
+                .with_interval(Duration::from_millis(10))
+                .with_jitter(Duration::ZERO)
+                .with_validator(ConfigurationValidator::new().with_required_flag("a")),
+        );
+        thread
+            .runtime()
+            .async_runtime
+            .block_on(poller.wait_for_configuration())
+            .unwrap();
+
+        source.set_configuration(configuration(&["b"]));
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        // Rejection is reported again on polls that find configuration unchanged.
+        while poller.status().consecutive_failures < 2 {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "rejection is not reported"
+            );
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        let status = poller.status();
+        assert!(matches!(
+            status.last_error,
+            Some(crate::Error::ConfigurationRejected(_))
+        ));
+        assert!(status.running);
+        let active = store.get_configuration().unwrap();
+        assert!(active.flag_keys().map(|key| key.as_str()).eq(["a"]));
+
+        source.set_configuration(configuration(&["a", "b"]));
+        thread
+            .runtime()
+            .async_runtime
+            .block_on(wait_for_flag_count(&store, 2));
+        while poller.status().consecutive_failures != 0 {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "recovery is not reported"
+            );
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        poller.stop();
+        thread.shutdown();
+    }
+
+    #[test]
+    fn parses_event_stream() {
+        let mut parser = EventStreamParser::default();
+
+        assert_eq!(parser.feed(b": keep-alive\n\nevent: fl").unwrap(), vec![]);
+        assert_eq!(
+            parser
+                .feed(b"ags\ndata: {\"a\":\r\ndata:1}\r\n\r\ndata: hello\n\n")
+                .unwrap(),
+            vec![
+                StreamEvent {
+                    event: "flags".to_owned(),
+                    data: "{\"a\":\n1}".to_owned(),
+                },
+                StreamEvent {
+                    event: "message".to_owned(),
+                    data: "hello".to_owned(),
+                },
+            ]
+        );
+        // Events without data are not dispatched.
+        assert_eq!(parser.feed(b"event: refresh\n\n").unwrap(), vec![]);
+        assert_eq!(
+            parser.feed(b"event: refresh\ndata\n\n").unwrap(),
+            vec![StreamEvent {
+                event: "refresh".to_owned(),
+                data: "".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn rejects_oversized_stream_events() {
+        let mut parser = EventStreamParser::with_max_size(16);
+        assert_eq!(parser.feed(b"data: 0123456789\n\n").unwrap().len(), 1);
+        assert!(parser.feed(b"data: 0123456789abcdef").is_err());
+
+        let mut parser = EventStreamParser::with_max_size(16);
+        assert_eq!(parser.feed(b"data: 01234\ndata: 56789\n").unwrap(), vec![]);
+        assert!(parser.feed(b"data: abcdef\n").is_err());
+    }
+
+    #[tokio::test]
+    async fn applies_streamed_configuration() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/flag-config/v1/config"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"createdAt":"2024-07-18T00:00:00Z","environment":{"name":"Test"},"flags":{}}"#,
+            ))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/stream"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Content-Type", "text/event-stream")
+                    .set_body_string(concat!(
+                        ": keep-alive\n\n",
+                        "event: flags\n",
+                        r#"data: {"createdAt":"2024-07-18T00:00:01Z","environment":{"name":"Test"},"flags":{"#,
+                        r#""kill-switch":{"key":"kill-switch","enabled":true,"variationType":"BOOLEAN","#,
+                        r#""variations":{},"allocations":[],"totalShards":10000}}}"#,
+                        "\n\n",
+                    )),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = test_fetcher(&mock_server);
+        let store = Arc::new(ConfigurationStore::new());
+        let config = ConfigurationPollerConfig::new()
+            .with_interval(Duration::from_secs(3600))
+            .with_streaming_url(format!("{}/stream", mock_server.uri()));
+        let (status_tx, _status_rx) = watch::channel(None);
+
+        let poller = tokio::spawn(configuration_poller(
+            fetcher,
+            store.clone(),
+            config,
+            StatusReporter::new(status_tx),
+        ));
+
+        let streamed = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let flags = store
+                    .get_configuration()
+                    .map(|configuration| configuration.flag_keys().count());
+                if flags == Some(1) {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await;
+        poller.abort();
+
+        assert!(streamed.is_ok(), "streamed configuration was not applied");
+    }
+
+    #[tokio::test]
+    async fn idle_stream_keeps_configuration_fresh() {
+        let configuration = ConfigurationBuilder::new()
+            .flag(
+                FlagBuilder::new("flag", VariationType::String)
+                    .variation("a", "a")
+                    .allocation(AllocationBuilder::new("all").split(SplitBuilder::new("a"))),
+            )
+            .build()
+            .unwrap();
+        let flags = configuration.get_flags_configuration().unwrap();
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/flag-config/v1/config"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(flags.to_vec()))
+            .mount(&mock_server)
+            .await;
+
+        // Stream that only sends keep-alives until the client disconnects.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let stream_url = format!("http://{}/stream", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            use std::io::{Read, Write};
+            let (mut socket, _) = listener.accept().unwrap();
+            let _ = socket.read(&mut [0; 4096]);
+            let _ = socket.write_all(
+                b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n",
+            );
+            while socket.write_all(b": keep-alive\n\n").is_ok() {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+        });
+
+        let store = Arc::new(ConfigurationStore::new());
+        let config = ConfigurationPollerConfig::new()
+            .with_interval(Duration::from_secs(3600))
+            .with_streaming_url(stream_url);
+        let (status_tx, _status_rx) = watch::channel(None);
+        let poller = tokio::spawn(configuration_poller(
+            test_fetcher(&mock_server),
+            store.clone(),
+            config,
+            StatusReporter::new(status_tx),
+        ));
+        let evaluator = Evaluator::new(EvaluatorConfig {
+            configuration_store: store.clone(),
+            sdk_metadata: SdkMetadata {
+                name: "test-sdk",
+                version: "1.0.0",
+            },
+            assignment_cache: None,
+            max_configuration_age: Some(Duration::from_millis(300)),
+            stale_configuration_policy: StaleConfigurationPolicy::ReturnDefault,
+        });
+
+        wait_for_flag_count(&store, 1).await;
+        // Poll interval is long, so only the stream can keep configuration fresh.
+        tokio::time::sleep(Duration::from_millis(800)).await;
+
+        let assignment = evaluator
+            .get_assignment("flag", &"subject".into(), &Arc::default(), None)
+            .unwrap();
+        poller.abort();
+
+        assert_eq!(
+            assignment.map(|assignment| assignment.value),
+            Some(AssignmentValue::String("a".into()))
+        );
+    }
+
+    #[tokio::test]
+    async fn retries_failed_fetch() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/flag-config/v1/config"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/flag-config/v1/config"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"createdAt":"2024-07-18T00:00:00Z","environment":{"name":"Test"},"flags":{}}"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let store = Arc::new(ConfigurationStore::new());
+        let config = ConfigurationPollerConfig::new()
+            .with_interval(Duration::from_secs(3600))
+            .with_retry_delay(Duration::from_millis(10));
+        let (status_tx, mut status_rx) = watch::channel(None);
+
+        let poller = tokio::spawn(configuration_poller(
+            test_fetcher(&mock_server),
+            store.clone(),
+            config,
+            StatusReporter::new(status_tx),
+        ));
+
+        let status = tokio::time::timeout(
+            Duration::from_secs(5),
+            status_rx.wait_for(|status| status.is_some()),
+        )
+        .await
+        .expect("configuration should be fetched after retries")
+        .unwrap()
+        .clone();
+        poller.abort();
+
+        assert!(matches!(status, Some(Ok(()))));
+        assert!(store.get_configuration().is_some());
+    }
+
+    #[tokio::test]
+    async fn stops_on_unauthorized() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let (status_tx, mut status_rx) = watch::channel(None);
+        let poller = tokio::spawn(configuration_poller(
+            test_fetcher(&mock_server),
+            Arc::new(ConfigurationStore::new()),
+            ConfigurationPollerConfig::new().with_retry_delay(Duration::from_millis(10)),
+            StatusReporter::new(status_tx),
+        ));
+
+        let status = status_rx
+            .wait_for(|status| status.is_some())
+            .await
+            .unwrap()
+            .clone();
+
+        assert!(matches!(status, Some(Err(crate::Error::Unauthorized))));
+        tokio::time::timeout(Duration::from_secs(5), poller)
+            .await
+            .expect("poller should exit")
+            .unwrap();
+    }
+}
@@ -0,0 +1,353 @@
+//! Sources of configuration that drive the configuration poller.
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use crate::{
+    configuration_fetcher::ConfigurationFetcher, configuration_file::ConfigurationFiles,
+    Configuration, Error, Result, SdkMetadata,
+};
+
+#[cfg(doc)]
+use crate::configuration_poller::start_configuration_poller;
+
+/// A source of configuration that can be polled with [`start_configuration_poller()`].
+///
+/// The poller handles retries and reporting, so implementations should simply return an error
+/// if configuration cannot be fetched. [`Error::Unauthorized`](crate::Error::Unauthorized) and
+/// [`Error::InvalidBaseUrl`](crate::Error::InvalidBaseUrl) stop the poller, network errors,
+/// timeouts, and I/O errors are retried with backoff, and other errors wait for the next poll.
+pub trait ConfigurationSource {
+    /// Fetch the latest configuration.
+    ///
+    /// Returns `Ok(None)` if configuration has not changed since the last successful fetch.
+    fn fetch(&mut self) -> impl Future<Output = Result<Option<Configuration>>> + MaybeSend;
+
+    /// Returns the HTTP fetcher to stream pushed configuration updates with, if the source
+    /// supports streaming.
+    ///
+    /// Defaults to `None`, in which case the poller only polls the source.
+    fn stream(&mut self) -> Option<&mut ConfigurationFetcher> {
+        None
+    }
+}
+
+/// `Send` on all platforms except wasm32, where futures are not required to be `Send`.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSend: Send {}
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Send + ?Sized> MaybeSend for T {}
+
+/// `Send` on all platforms except wasm32, where futures are not required to be `Send`.
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSend {}
+#[cfg(target_arch = "wasm32")]
+impl<T: ?Sized> MaybeSend for T {}
+
+/// Fetches configuration from Eppo servers over HTTP.
+impl ConfigurationSource for ConfigurationFetcher {
+    async fn fetch(&mut self) -> Result<Option<Configuration>> {
+        self.fetch_configuration().await
+    }
+
+    fn stream(&mut self) -> Option<&mut ConfigurationFetcher> {
+        Some(self)
+    }
+}
+
+/// Loads configuration from local files, e.g., mirrored from an S3 bucket.
+///
+/// Files are only re-read when their modification time or size changes. If loading fails, the
+/// same error is returned until the files change again.
+#[derive(Debug)]
+pub struct FileConfigurationSource {
+    files: ConfigurationFiles,
+    sdk_metadata: SdkMetadata,
+    /// Stamp of the files at the last load.
+    last_stamp: Option<Vec<Option<(SystemTime, u64)>>>,
+    /// Error of the last load, if it failed.
+    last_error: Option<Error>,
+}
+
+impl FileConfigurationSource {
+    pub fn new(files: ConfigurationFiles, sdk_metadata: SdkMetadata) -> FileConfigurationSource {
+        FileConfigurationSource {
+            files,
+            sdk_metadata,
+            last_stamp: None,
+            last_error: None,
+        }
+    }
+
+    /// Create a source for files that have already been loaded, so they are only re-read after
+    /// they change.
+    pub(crate) fn loaded(
+        files: ConfigurationFiles,
+        sdk_metadata: SdkMetadata,
+    ) -> FileConfigurationSource {
+        let last_stamp = Some(files.stamp());
+        FileConfigurationSource {
+            last_stamp,
+            ..FileConfigurationSource::new(files, sdk_metadata)
+        }
+    }
+}
+
+impl ConfigurationSource for FileConfigurationSource {
+    async fn fetch(&mut self) -> Result<Option<Configuration>> {
+        let stamp = self.files.stamp();
+        if self.last_stamp.as_ref() == Some(&stamp) {
+            return match &self.last_error {
+                Some(err) => Err(err.clone()),
+                None => Ok(None),
+            };
+        }
+        self.last_stamp = Some(stamp);
+        let result = self.files.load(self.sdk_metadata);
+        self.last_error = result.as_ref().err().cloned();
+        result.map(Some)
+    }
+}
+
+/// Serves configuration that is set programmatically. Mostly useful for tests.
+///
+/// Clones share the same state, so a clone can be used to update configuration after the source
+/// has been passed to the poller.
+#[derive(Debug, Clone)]
+pub struct InMemoryConfigurationSource {
+    state: Arc<Mutex<InMemoryState>>,
+}
+
+#[derive(Debug)]
+struct InMemoryState {
+    /// Configuration that has not been fetched yet.
+    pending: Option<Configuration>,
+    /// Error to return from all fetches until configuration is set.
+    error: Option<crate::Error>,
+}
+
+impl InMemoryConfigurationSource {
+    /// Create a source that serves `configuration`.
+    pub fn new(configuration: Configuration) -> InMemoryConfigurationSource {
+        InMemoryConfigurationSource {
+            state: Arc::new(Mutex::new(InMemoryState {
+                pending: Some(configuration),
+                error: None,
+            })),
+        }
+    }
+
+    /// Replace configuration, so it's returned by the next fetch.
+    pub fn set_configuration(&self, configuration: Configuration) {
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        state.pending = Some(configuration);
+        state.error = None;
+    }
+
+    /// Make all fetches fail with `error` until configuration is set again.
+    pub fn set_error(&self, error: crate::Error) {
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        state.error = Some(error);
+    }
+
+    /// Stop failing fetches set up with [`InMemoryConfigurationSource::set_error()`].
+    pub fn clear_error(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        state.error = None;
+    }
+}
+
+impl ConfigurationSource for InMemoryConfigurationSource {
+    async fn fetch(&mut self) -> Result<Option<Configuration>> {
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(error) = &state.error {
+            return Err(error.clone());
+        }
+        Ok(state.pending.take())
+    }
+}
+
+/// Fetches configuration from `primary` source, falling back to `fallback` if it fails.
+///
+/// Chains can be nested to build longer fallback lists.
+#[derive(Debug)]
+pub struct ChainConfigurationSource<P, F> {
+    primary: P,
+    fallback: F,
+    /// Last configuration returned by each source. When a source that did not serve the active
+    /// configuration reports no changes, its last configuration is served again.
+    last_primary: Option<Configuration>,
+    last_fallback: Option<Configuration>,
+    active: Option<ChainSource>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChainSource {
+    Primary,
+    Fallback,
+}
+
+impl<P: ConfigurationSource, F: ConfigurationSource> ChainConfigurationSource<P, F> {
+    pub fn new(primary: P, fallback: F) -> ChainConfigurationSource<P, F> {
+        ChainConfigurationSource {
+            primary,
+            fallback,
+            last_primary: None,
+            last_fallback: None,
+            active: None,
+        }
+    }
+}
+
+impl<P, F> ConfigurationSource for ChainConfigurationSource<P, F>
+where
+    P: ConfigurationSource + MaybeSend,
+    F: ConfigurationSource + MaybeSend,
+{
+    async fn fetch(&mut self) -> Result<Option<Configuration>> {
+        match self.primary.fetch().await {
+            Ok(fetched) => {
+                let was_active = self.active.replace(ChainSource::Primary);
+                return Ok(select(
+                    &mut self.last_primary,
+                    fetched,
+                    was_active == Some(ChainSource::Primary),
+                ));
+            }
+            Err(err) => {
+                log::warn!(target: "eppo", "failed to fetch configuration from primary source, trying fallback: {err}");
+            }
+        }
+
+        let fetched = self.fallback.fetch().await?;
+        let was_active = self.active.replace(ChainSource::Fallback);
+        Ok(select(
+            &mut self.last_fallback,
+            fetched,
+            was_active == Some(ChainSource::Fallback),
+        ))
+    }
+}
+
+/// Pick configuration to return from a chain after one of its sources returned `fetched`.
+fn select(
+    last: &mut Option<Configuration>,
+    fetched: Option<Configuration>,
+    was_active: bool,
+) -> Option<Configuration> {
+    match fetched {
+        Some(configuration) => {
+            *last = Some(configuration.clone());
+            Some(configuration)
+        }
+        None if was_active => None,
+        // The source has not changed, but the active configuration came from another source.
+        None => last.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        configuration_builder::{ConfigurationBuilder, FlagBuilder},
+        configuration_file::ConfigurationFiles,
+        ufc::VariationType,
+        Configuration, Error, SdkMetadata,
+    };
+
+    use super::{
+        ChainConfigurationSource, ConfigurationSource, FileConfigurationSource,
+        InMemoryConfigurationSource,
+    };
+
+    fn configuration(flags: &[&str]) -> Configuration {
+        flags
+            .iter()
+            .fold(ConfigurationBuilder::new(), |builder, key| {
+                builder.flag(FlagBuilder::new(*key, VariationType::Boolean))
+            })
+            .build()
+            .unwrap()
+    }
+
+    fn flag_count(fetched: Option<Configuration>) -> Option<usize> {
+        fetched.map(|configuration| configuration.flag_keys().count())
+    }
+
+    #[tokio::test]
+    async fn in_memory_source_returns_updates_once() {
+        let mut source = InMemoryConfigurationSource::new(configuration(&["a"]));
+
+        assert_eq!(flag_count(source.fetch().await.unwrap()), Some(1));
+        assert_eq!(flag_count(source.fetch().await.unwrap()), None);
+
+        source.set_error(Error::Timeout);
+        assert!(matches!(source.fetch().await, Err(Error::Timeout)));
+        assert!(matches!(source.fetch().await, Err(Error::Timeout)));
+
+        source.clone().set_configuration(configuration(&["a", "b"]));
+        assert_eq!(flag_count(source.fetch().await.unwrap()), Some(2));
+    }
+
+    #[tokio::test]
+    async fn chain_source_falls_back() {
+        let primary = InMemoryConfigurationSource::new(configuration(&["a"]));
+        let fallback = InMemoryConfigurationSource::new(configuration(&["a", "b"]));
+        let mut chain = ChainConfigurationSource::new(primary.clone(), fallback.clone());
+
+        assert_eq!(flag_count(chain.fetch().await.unwrap()), Some(1));
+        assert_eq!(flag_count(chain.fetch().await.unwrap()), None);
+
+        primary.set_error(Error::Timeout);
+        assert_eq!(flag_count(chain.fetch().await.unwrap()), Some(2));
+        assert_eq!(flag_count(chain.fetch().await.unwrap()), None);
+
+        // Primary recovered without changes, so its last configuration is served again.
+        primary.clear_error();
+        assert_eq!(flag_count(chain.fetch().await.unwrap()), Some(1));
+        assert_eq!(flag_count(chain.fetch().await.unwrap()), None);
+
+        primary.set_error(Error::Unauthorized);
+        fallback.set_error(Error::Timeout);
+        assert!(matches!(chain.fetch().await, Err(Error::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn file_source_reloads_changed_files() {
+        let dir = std::env::temp_dir().join(format!("eppo-file-source-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("flags.json");
+        let flags_json = |flags: &[&str]| {
+            flags
+                .iter()
+                .fold(ConfigurationBuilder::new(), |builder, key| {
+                    builder.flag(FlagBuilder::new(*key, VariationType::Boolean))
+                })
+                .to_flags_json()
+        };
+        std::fs::write(&path, flags_json(&["a"])).unwrap();
+
+        let mut source = FileConfigurationSource::new(
+            ConfigurationFiles::new(&path),
+            SdkMetadata {
+                name: "test",
+                version: "0.1.0",
+            },
+        );
+
+        assert_eq!(flag_count(source.fetch().await.unwrap()), Some(1));
+        assert_eq!(flag_count(source.fetch().await.unwrap()), None);
+
+        // Invalid files keep failing until they change.
+        std::fs::write(&path, b"{ not json").unwrap();
+        assert!(source.fetch().await.is_err());
+        assert!(source.fetch().await.is_err());
+
+        std::fs::write(&path, flags_json(&["a", "b"])).unwrap();
+        assert_eq!(flag_count(source.fetch().await.unwrap()), Some(2));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
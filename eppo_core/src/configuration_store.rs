@@ -1,60 +1,403 @@
 //! A thread-safe in-memory storage for currently active configuration. [`ConfigurationStore`]
 //! provides concurrent access for readers (e.g., flag evaluation) and writers (e.g., periodic
 //! configuration fetcher).
-use std::sync::{Arc, RwLock};
+//!
+//! Reads are lock-free: the active configuration is kept behind an atomically swapped pointer, so
+//! readers on different threads never contend with each other or block on a writer. Each read
+//! returns a consistent snapshot that stays valid even if configuration is replaced concurrently.
+//!
+//! The store also notifies subscribers whenever a new configuration is stored. See
+//! [`ConfigurationStore::subscribe()`] and [`ConfigurationStore::add_listener()`].
+//!
+//! The store keeps a history of recently stored configurations, so that an operator can roll back
+//! to a previous configuration (e.g., during an incident caused by a bad flag change). See
+//! [`ConfigurationStore::rollback()`].
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicI64, AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+};
 
-use crate::Configuration;
+use arc_swap::{ArcSwapOption, Guard};
+use tokio::sync::watch;
+
+use crate::{
+    timestamp::{self, Timestamp},
+    Configuration, Str,
+};
 
 /// `ConfigurationStore` provides a thread-safe (`Sync`) storage for Eppo configuration that allows
 /// concurrent access for readers and writers.
 ///
 /// `Configuration` itself is always immutable and can only be replaced completely.
-#[derive(Default)]
 pub struct ConfigurationStore {
-    configuration: RwLock<Option<Arc<Configuration>>>,
+    configuration: ArcSwapOption<Configuration>,
+    /// Notifies async subscribers about configuration updates.
+    sender: watch::Sender<Option<Arc<Configuration>>>,
+    /// Synchronous callbacks to call on configuration updates.
+    listeners: Mutex<Vec<(ListenerId, Arc<Listener>)>>,
+    next_listener_id: AtomicU64,
+    history: Mutex<History>,
+    /// Milliseconds since Unix epoch when configuration was last confirmed to be up to date, or
+    /// `i64::MIN` if there is no configuration yet.
+    confirmed_at: AtomicI64,
+}
+
+/// Recently stored configurations and pinning state.
+struct History {
+    /// Oldest entries first.
+    entries: VecDeque<(u64, Arc<Configuration>)>,
+    size: usize,
+    next_version: u64,
+    /// Version of the active configuration.
+    active_version: Option<u64>,
+    /// When set, newly stored configurations are recorded but not activated.
+    pinned: bool,
+}
+
+impl History {
+    fn push(&mut self, configuration: Arc<Configuration>) -> u64 {
+        let version = self.next_version;
+        self.next_version += 1;
+        self.entries.push_back((version, configuration));
+        while self.entries.len() > self.size {
+            // The active configuration is never evicted, so it can be seen in the history while
+            // pinned.
+            let Some(index) = self
+                .entries
+                .iter()
+                .position(|(version, _)| Some(*version) != self.active_version)
+            else {
+                break;
+            };
+            self.entries.remove(index);
+        }
+        version
+    }
+
+    fn get(&self, version: u64) -> Option<&Arc<Configuration>> {
+        self.entries
+            .iter()
+            .find(|(it, _)| *it == version)
+            .map(|(_, configuration)| configuration)
+    }
+}
+
+/// A configuration recorded in [`ConfigurationStore`] history, as returned by
+/// [`ConfigurationStore::history()`].
+///
+/// Use [`Configuration::fetched_at()`] and [`Configuration::created_at()`] to tell configurations
+/// apart.
+#[derive(Debug, Clone)]
+pub struct ConfigurationHistoryEntry {
+    /// Sequential number assigned to configuration when it was stored. Pass it to
+    /// [`ConfigurationStore::rollback()`] to make this configuration active again.
+    pub version: u64,
+    /// Whether this configuration is currently active.
+    pub active: bool,
+    pub configuration: Arc<Configuration>,
+}
+
+type Listener = dyn Fn(&ConfigurationChange) + Send + Sync;
+
+/// Identifier of a listener registered with [`ConfigurationStore::add_listener()`]. Can be used to
+/// remove the listener with [`ConfigurationStore::remove_listener()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ListenerId(u64);
+
+/// Notification about a new configuration being stored in [`ConfigurationStore`].
+#[derive(Debug, Clone)]
+pub struct ConfigurationChange {
+    /// Configuration that was active before the update (if any).
+    pub previous: Option<Arc<Configuration>>,
+    /// Newly stored configuration.
+    pub current: Arc<Configuration>,
+}
+
+/// Summary of flags that differ between two configurations.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FlagChanges {
+    /// Flags that are present in the new configuration only.
+    pub added: Vec<Str>,
+    /// Flags that are present in the previous configuration only.
+    pub removed: Vec<Str>,
+    /// Flags that are present in both configurations but whose definition has changed.
+    pub changed: Vec<Str>,
+}
+
+impl FlagChanges {
+    /// Returns `true` if no flags were added, removed, or changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl ConfigurationChange {
+    /// Compute which flag keys were added, removed or changed by this update. Keys are sorted.
+    ///
+    /// This is computed on demand with [`Configuration::diff()`], so only changes that affect
+    /// evaluation or assignment logging are reported.
+    pub fn flag_changes(&self) -> FlagChanges {
+        let Some(previous) = &self.previous else {
+            let mut added: Vec<Str> = self.current.flag_keys().cloned().collect();
+            added.sort();
+            return FlagChanges {
+                added,
+                ..Default::default()
+            };
+        };
+        if Arc::ptr_eq(&previous.flags, &self.current.flags) {
+            // Flags configuration is shared, so nothing could have changed.
+            return FlagChanges::default();
+        }
+
+        let diff = previous.diff(&self.current);
+        FlagChanges {
+            added: diff.added_flags,
+            removed: diff.removed_flags,
+            changed: diff
+                .modified_flags
+                .into_iter()
+                .map(|flag| flag.flag_key)
+                .collect(),
+        }
+    }
+}
+
+impl Default for ConfigurationStore {
+    fn default() -> Self {
+        ConfigurationStore::with_history_size(ConfigurationStore::DEFAULT_HISTORY_SIZE)
+    }
 }
 
 impl ConfigurationStore {
+    /// Default number of configurations kept in history.
+    pub const DEFAULT_HISTORY_SIZE: usize = 5;
+
     /// Create a new empty configuration store.
     pub fn new() -> Self {
         ConfigurationStore::default()
     }
 
+    /// Create a new empty configuration store that keeps the last `size` configurations in
+    /// history. `size` is at least 1, as the latest configuration is always kept.
+    pub fn with_history_size(size: usize) -> Self {
+        ConfigurationStore {
+            configuration: ArcSwapOption::empty(),
+            sender: watch::channel(None).0,
+            listeners: Mutex::new(Vec::new()),
+            next_listener_id: AtomicU64::new(0),
+            history: Mutex::new(History {
+                entries: VecDeque::new(),
+                size: size.max(1),
+                next_version: 1,
+                active_version: None,
+                pinned: false,
+            }),
+            confirmed_at: AtomicI64::new(i64::MIN),
+        }
+    }
+
     /// Get currently-active configuration. Returns None if configuration hasn't been fetched/stored
     /// yet.
     pub fn get_configuration(&self) -> Option<Arc<Configuration>> {
-        // self.configuration.read() should always return Ok(). Err() is possible only if the lock
-        // is poisoned (writer panicked while holding the lock), which should never happen.
-        let configuration = self
-            .configuration
-            .read()
-            .expect("thread holding configuration lock should not panic");
+        self.configuration.load_full()
+    }
 
-        configuration.clone()
+    /// Get a short-lived guard for currently-active configuration.
+    ///
+    /// This is cheaper than [`ConfigurationStore::get_configuration()`] as it usually avoids
+    /// touching the reference counter, so it's preferred on hot paths (e.g., flag evaluation).
+    /// Guards should not be held for long: the number of cheap guards per thread is limited and
+    /// the old configuration is not released while a guard is alive.
+    pub(crate) fn load_configuration(&self) -> Guard<Option<Arc<Configuration>>> {
+        self.configuration.load()
     }
 
     /// Set new configuration.
+    ///
+    /// Subscribers and listeners are notified after the configuration is stored. Listeners are
+    /// called synchronously on the current thread.
+    ///
+    /// If the store is pinned (see [`ConfigurationStore::pin()`]), configuration is recorded in
+    /// history but does not become active until the store is unpinned.
     pub fn set_configuration(&self, config: Arc<Configuration>) {
-        let mut configuration_slot = self
-            .configuration
-            .write()
-            .expect("thread holding configuration lock should not panic");
+        self.confirm_at(config.fetched_at());
+        let mut history = self.lock_history();
+        let version = history.push(config.clone());
+        if history.pinned {
+            log::info!(target: "eppo", "configuration is pinned, new configuration (version {version}) will be applied when unpinned");
+            return;
+        }
+        history.active_version = Some(version);
+        self.activate(history, config);
+    }
+
+    /// Record that the source has just confirmed that the latest stored configuration is still up
+    /// to date (e.g., the server replied with 304 Not Modified).
+    pub fn confirm_configuration(&self) {
+        self.confirm_at(timestamp::now());
+    }
+
+    /// Returns when the latest stored configuration was last known to be up to date: when it was
+    /// fetched or when the source last confirmed it has not changed, whichever is later.
+    ///
+    /// Returns `None` if no configuration has been stored yet.
+    pub fn configuration_confirmed_at(&self) -> Option<Timestamp> {
+        match self.confirmed_at.load(Ordering::Relaxed) {
+            i64::MIN => None,
+            millis => Timestamp::from_timestamp_millis(millis),
+        }
+    }
+
+    fn confirm_at(&self, at: Timestamp) {
+        self.confirmed_at
+            .fetch_max(at.timestamp_millis(), Ordering::Relaxed);
+    }
+
+    /// Returns recently stored configurations, newest first.
+    ///
+    /// While the store is pinned, this includes configurations that were stored but not
+    /// activated.
+    pub fn history(&self) -> Vec<ConfigurationHistoryEntry> {
+        let history = self.lock_history();
+        history
+            .entries
+            .iter()
+            .rev()
+            .map(|(version, configuration)| ConfigurationHistoryEntry {
+                version: *version,
+                active: history.active_version == Some(*version),
+                configuration: configuration.clone(),
+            })
+            .collect()
+    }
+
+    /// Pin the active configuration. While pinned, newly stored configurations are recorded in
+    /// history but not activated.
+    pub fn pin(&self) {
+        self.lock_history().pinned = true;
+    }
+
+    /// Make configuration `version` from [`ConfigurationStore::history()`] active and pin it.
+    ///
+    /// Returns `false` if the version is not in history.
+    pub fn rollback(&self, version: u64) -> bool {
+        let mut history = self.lock_history();
+        let Some(configuration) = history.get(version).cloned() else {
+            return false;
+        };
+        history.pinned = true;
+        if history.active_version != Some(version) {
+            history.active_version = Some(version);
+            log::warn!(target: "eppo", "rolled back to configuration version {version} created at {}", configuration.created_at());
+            self.activate(history, configuration);
+        }
+        true
+    }
+
+    /// Unpin the store and activate the most recently stored configuration.
+    pub fn unpin(&self) {
+        let mut history = self.lock_history();
+        history.pinned = false;
+        let Some((version, configuration)) = history.entries.back().cloned() else {
+            return;
+        };
+        if history.active_version != Some(version) {
+            history.active_version = Some(version);
+            self.activate(history, configuration);
+        }
+    }
+
+    /// Returns `true` if the store is pinned.
+    pub fn is_pinned(&self) -> bool {
+        self.lock_history().pinned
+    }
+
+    fn lock_history(&self) -> MutexGuard<'_, History> {
+        self.history
+            .lock()
+            .expect("thread holding history lock should not panic")
+    }
+
+    /// Make `config` the active configuration and notify subscribers and listeners.
+    ///
+    /// `history` lock is held until configuration is replaced, so that concurrent updates are
+    /// applied in the same order as they are recorded in history. It is released before calling
+    /// listeners, so they can access the store.
+    fn activate(&self, history: MutexGuard<'_, History>, config: Arc<Configuration>) {
+        let previous = self.configuration.swap(Some(config.clone()));
+
+        self.sender.send_replace(Some(config.clone()));
+        drop(history);
+
+        // Clone listeners, so that callbacks can add or remove listeners without deadlocking.
+        let listeners: Vec<Arc<Listener>> = self
+            .listeners
+            .lock()
+            .expect("thread holding listeners lock should not panic")
+            .iter()
+            .map(|(_, listener)| listener.clone())
+            .collect();
+        if !listeners.is_empty() {
+            let change = ConfigurationChange {
+                previous,
+                current: config,
+            };
+            for listener in listeners {
+                listener(&change);
+            }
+        }
+    }
+
+    /// Subscribe to configuration updates.
+    ///
+    /// The returned receiver always holds the currently active configuration and can be used to
+    /// wait for updates asynchronously (e.g., with [`watch::Receiver::changed()`]).
+    pub fn subscribe(&self) -> watch::Receiver<Option<Arc<Configuration>>> {
+        self.sender.subscribe()
+    }
 
-        *configuration_slot = Some(config);
+    /// Register a callback to be called synchronously every time a new configuration is stored.
+    ///
+    /// The callback is invoked on the thread that stores configuration (usually, the poller
+    /// thread), so it should return quickly and must not panic.
+    pub fn add_listener(
+        &self,
+        listener: impl Fn(&ConfigurationChange) + Send + Sync + 'static,
+    ) -> ListenerId {
+        let id = ListenerId(self.next_listener_id.fetch_add(1, Ordering::Relaxed));
+        self.listeners
+            .lock()
+            .expect("thread holding listeners lock should not panic")
+            .push((id, Arc::new(listener)));
+        id
+    }
+
+    /// Remove a listener previously registered with [`ConfigurationStore::add_listener()`].
+    ///
+    /// Returns `false` if listener was not found.
+    pub fn remove_listener(&self, id: ListenerId) -> bool {
+        let mut listeners = self
+            .listeners
+            .lock()
+            .expect("thread holding listeners lock should not panic");
+        let len = listeners.len();
+        listeners.retain(|(listener_id, _)| *listener_id != id);
+        listeners.len() != len
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
-
-    use crate::hashmap::*;
-    use chrono::Utc;
+    use std::sync::{Arc, Mutex};
 
-    use super::ConfigurationStore;
+    use super::{ConfigurationStore, FlagChanges};
     use crate::{
-        ufc::{CompiledFlagsConfig, Environment, UniversalFlagConfig},
-        Configuration,
+        configuration_builder::ConfigurationBuilder, ufc::UniversalFlagConfig, Configuration,
+        SdkMetadata,
     };
 
     #[test]
@@ -66,24 +409,197 @@ mod tests {
         {
             let store = store.clone();
             let _ = std::thread::spawn(move || {
-                store.set_configuration(Arc::new(Configuration::from_server_response(
-                    UniversalFlagConfig {
-                        wire_json: b"test-bytes".to_vec(),
-                        compiled: CompiledFlagsConfig {
-                            created_at: Utc::now(),
-                            environment: Environment {
-                                name: "test".into(),
-                            },
-                            flags: HashMap::new(),
-                            flag_to_bandit_associations: HashMap::new(),
-                        },
-                    },
-                    None,
-                )))
+                store.set_configuration(Arc::new(ConfigurationBuilder::new().build().unwrap()))
             })
             .join();
         }
 
         assert!(store.get_configuration().is_some());
     }
+
+    #[test]
+    fn readers_keep_snapshot_while_configuration_is_replaced() {
+        let store = Arc::new(ConfigurationStore::new());
+        let first = Arc::new(ConfigurationBuilder::new().build().unwrap());
+        store.set_configuration(first.clone());
+
+        let snapshot = store.load_configuration();
+
+        {
+            let store = store.clone();
+            std::thread::spawn(move || {
+                for _ in 0..100 {
+                    store.set_configuration(Arc::new(ConfigurationBuilder::new().build().unwrap()));
+                }
+            })
+            .join()
+            .unwrap();
+        }
+
+        assert!(Arc::ptr_eq(snapshot.as_ref().unwrap(), &first));
+        assert!(!Arc::ptr_eq(&store.get_configuration().unwrap(), &first));
+    }
+
+    fn make_configuration(flags_json: &str) -> Arc<Configuration> {
+        Arc::new(Configuration::from_server_response(
+            UniversalFlagConfig::from_json(
+                SdkMetadata {
+                    name: "test",
+                    version: "0.1.0",
+                },
+                format!(
+                    r#"{{"createdAt":"2024-07-18T00:00:00Z","environment":{{"name":"Test"}},"flags":{flags_json}}}"#
+                )
+                .into_bytes(),
+            )
+            .unwrap(),
+            None,
+        ))
+    }
+
+    #[test]
+    fn listeners_receive_flag_changes() {
+        let store = ConfigurationStore::new();
+        let flag = |key: &str, enabled: bool| {
+            format!(
+                r#""{key}":{{"key":"{key}","enabled":{enabled},"variationType":"BOOLEAN","variations":{{}},"allocations":[],"totalShards":10000}}"#
+            )
+        };
+        store.set_configuration(make_configuration(&format!(
+            "{{{},{},{}}}",
+            flag("removed", true),
+            flag("changed", true),
+            flag("same", true)
+        )));
+
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let listener_id = store.add_listener({
+            let changes = changes.clone();
+            move |change| changes.lock().unwrap().push(change.flag_changes())
+        });
+
+        store.set_configuration(make_configuration(&format!(
+            "{{{},{},{}}}",
+            flag("added", true),
+            flag("changed", false),
+            flag("same", true)
+        )));
+
+        assert_eq!(
+            *changes.lock().unwrap(),
+            vec![FlagChanges {
+                added: vec!["added".into()],
+                removed: vec!["removed".into()],
+                changed: vec!["changed".into()],
+            }]
+        );
+
+        assert!(store.remove_listener(listener_id));
+        store.set_configuration(make_configuration("{}"));
+        assert_eq!(changes.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn listeners_receive_salt_only_changes() {
+        let flag = |salt: &str| {
+            make_configuration(&format!(
+                r#"{{"flag":{{"key":"flag","enabled":true,"variationType":"BOOLEAN","variations":{{"on":{{"key":"on","value":true}}}},"allocations":[{{"key":"rollout","rules":[],"splits":[{{"variationKey":"on","shards":[{{"salt":"{salt}","ranges":[{{"start":0,"end":5000}}]}}]}}],"doLog":true}}],"totalShards":10000}}}}"#
+            ))
+        };
+        let store = ConfigurationStore::new();
+        store.set_configuration(flag("a"));
+
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        store.add_listener({
+            let changes = changes.clone();
+            move |change| changes.lock().unwrap().push(change.flag_changes())
+        });
+        store.set_configuration(flag("b"));
+
+        assert_eq!(
+            *changes.lock().unwrap(),
+            vec![FlagChanges {
+                changed: vec!["flag".into()],
+                ..Default::default()
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn subscribers_are_notified() {
+        let store = ConfigurationStore::new();
+        let mut receiver = store.subscribe();
+        assert!(receiver.borrow_and_update().is_none());
+
+        let configuration = make_configuration("{}");
+        store.set_configuration(configuration.clone());
+
+        receiver.changed().await.unwrap();
+        assert!(receiver
+            .borrow_and_update()
+            .as_ref()
+            .is_some_and(|it| Arc::ptr_eq(it, &configuration)));
+    }
+
+    #[test]
+    fn keeps_configuration_history() {
+        let store = ConfigurationStore::with_history_size(2);
+        for _ in 0..3 {
+            store.set_configuration(make_configuration("{}"));
+        }
+
+        let history = store.history();
+        assert_eq!(
+            history
+                .iter()
+                .map(|entry| (entry.version, entry.active))
+                .collect::<Vec<_>>(),
+            vec![(3, true), (2, false)]
+        );
+    }
+
+    #[test]
+    fn rolls_back_and_unpins() {
+        let store = ConfigurationStore::with_history_size(2);
+        let first = make_configuration(r#"{"a":{"enabled":true}}"#);
+        let second = make_configuration(r#"{"b":{"enabled":true}}"#);
+        store.set_configuration(first.clone());
+        store.set_configuration(second.clone());
+
+        let activated = Arc::new(Mutex::new(Vec::new()));
+        store.add_listener({
+            let activated = activated.clone();
+            move |change| activated.lock().unwrap().push(change.current.clone())
+        });
+
+        assert!(!store.rollback(42));
+        assert!(!store.is_pinned());
+
+        assert!(store.rollback(1));
+        assert!(store.is_pinned());
+        assert!(Arc::ptr_eq(&store.get_configuration().unwrap(), &first));
+
+        // New configurations are recorded, but not activated while pinned. Pinned configuration is
+        // never evicted from history.
+        let third = make_configuration(r#"{"c":{"enabled":true}}"#);
+        store.set_configuration(third.clone());
+        assert!(Arc::ptr_eq(&store.get_configuration().unwrap(), &first));
+        assert_eq!(
+            store
+                .history()
+                .iter()
+                .map(|entry| (entry.version, entry.active))
+                .collect::<Vec<_>>(),
+            vec![(3, false), (1, true)]
+        );
+
+        store.unpin();
+        assert!(!store.is_pinned());
+        assert!(Arc::ptr_eq(&store.get_configuration().unwrap(), &third));
+
+        let activated = activated.lock().unwrap();
+        assert_eq!(activated.len(), 2);
+        assert!(Arc::ptr_eq(&activated[0], &first));
+        assert!(Arc::ptr_eq(&activated[1], &third));
+    }
 }
@@ -0,0 +1,324 @@
+//! Validation of newly fetched configuration before it replaces the active one.
+//!
+//! [`ConfigurationValidator`] is run by the configuration poller (see
+//! [`ConfigurationPollerConfig::validator`]). Rejected configuration is never stored: the poller
+//! keeps serving the previous configuration and reports [`Error::ConfigurationRejected`] in its
+//! status until a configuration that passes validation is fetched.
+use std::sync::Arc;
+
+use crate::{error::EvaluationFailure, Configuration, Error, Result, Str};
+
+#[cfg(doc)]
+use crate::configuration_poller::ConfigurationPollerConfig;
+
+/// User-supplied check for [`ConfigurationValidator::with_predicate()`].
+///
+/// Receives the new configuration and the currently active one (if any), and returns the reason
+/// for rejecting the new configuration as an error.
+pub type ConfigurationPredicate =
+    dyn Fn(&Configuration, Option<&Configuration>) -> std::result::Result<(), String> + Send + Sync;
+
+/// A set of checks that configuration must pass before it is applied.
+///
+/// No checks are enabled by default.
+///
+/// # Examples
+/// ```
+/// # use eppo_core::configuration_validator::ConfigurationValidator;
+/// let validator = ConfigurationValidator::new()
+///     .with_max_parse_failure_ratio(0.5)
+///     .with_min_flag_count_ratio(0.5)
+///     .with_required_flag("kill-switch")
+///     .with_bandit_models_required();
+/// ```
+#[derive(Clone, Default)]
+pub struct ConfigurationValidator {
+    max_parse_failure_ratio: Option<f64>,
+    min_flag_count_ratio: Option<f64>,
+    required_flags: Vec<Str>,
+    require_bandit_models: bool,
+    predicates: Vec<(Str, Arc<ConfigurationPredicate>)>,
+}
+
+impl std::fmt::Debug for ConfigurationValidator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfigurationValidator")
+            .field("max_parse_failure_ratio", &self.max_parse_failure_ratio)
+            .field("min_flag_count_ratio", &self.min_flag_count_ratio)
+            .field("required_flags", &self.required_flags)
+            .field("require_bandit_models", &self.require_bandit_models)
+            .field(
+                "predicates",
+                &self
+                    .predicates
+                    .iter()
+                    .map(|(name, _)| name)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl ConfigurationValidator {
+    /// Create a validator with no checks enabled.
+    pub fn new() -> ConfigurationValidator {
+        ConfigurationValidator::default()
+    }
+
+    /// Reject configuration if more than `ratio` (0.0–1.0) of its flags failed to parse.
+    pub fn with_max_parse_failure_ratio(mut self, ratio: f64) -> ConfigurationValidator {
+        self.max_parse_failure_ratio = Some(ratio);
+        self
+    }
+
+    /// Reject configuration that has fewer than `ratio` times the number of flags in the active
+    /// configuration. E.g., `0.5` rejects configuration that lost more than half of the flags.
+    pub fn with_min_flag_count_ratio(mut self, ratio: f64) -> ConfigurationValidator {
+        self.min_flag_count_ratio = Some(ratio);
+        self
+    }
+
+    /// Reject configuration that does not have `flag_key` flag, or where it failed to parse.
+    pub fn with_required_flag(mut self, flag_key: impl Into<Str>) -> ConfigurationValidator {
+        self.required_flags.push(flag_key.into());
+        self
+    }
+
+    /// Reject configuration if any flag variation references a bandit whose model is missing.
+    pub fn with_bandit_models_required(mut self) -> ConfigurationValidator {
+        self.require_bandit_models = true;
+        self
+    }
+
+    /// Add a custom check. `name` is used to identify the check in rejection reports.
+    pub fn with_predicate(
+        mut self,
+        name: impl Into<Str>,
+        predicate: impl Fn(&Configuration, Option<&Configuration>) -> std::result::Result<(), String>
+            + Send
+            + Sync
+            + 'static,
+    ) -> ConfigurationValidator {
+        self.predicates.push((name.into(), Arc::new(predicate)));
+        self
+    }
+
+    /// Check if `configuration` may replace `previous` configuration.
+    ///
+    /// Returns [`Error::ConfigurationRejected`] describing the first failed check.
+    pub fn validate(
+        &self,
+        configuration: &Configuration,
+        previous: Option<&Configuration>,
+    ) -> Result<()> {
+        self.run_checks(configuration, previous)
+            .map_err(Error::ConfigurationRejected)
+    }
+
+    fn run_checks(
+        &self,
+        configuration: &Configuration,
+        previous: Option<&Configuration>,
+    ) -> std::result::Result<(), String> {
+        let flags = &configuration.flags.compiled.flags;
+
+        if let Some(max_ratio) = self.max_parse_failure_ratio {
+            let failed = flags
+                .values()
+                .filter(|flag| matches!(flag, Err(EvaluationFailure::Error(_))))
+                .count();
+            if !flags.is_empty() && failed as f64 / flags.len() as f64 > max_ratio {
+                return Err(format!("{failed} of {} flags failed to parse", flags.len()));
+            }
+        }
+
+        if let (Some(min_ratio), Some(previous)) = (self.min_flag_count_ratio, previous) {
+            let previous_count = previous.flags.compiled.flags.len();
+            if (flags.len() as f64) < previous_count as f64 * min_ratio {
+                return Err(format!(
+                    "flag count dropped from {previous_count} to {}",
+                    flags.len()
+                ));
+            }
+        }
+
+        for flag_key in &self.required_flags {
+            match flags.get(flag_key) {
+                None => return Err(format!("required flag {flag_key:?} is missing")),
+                Some(Err(EvaluationFailure::Error(_))) => {
+                    return Err(format!("required flag {flag_key:?} failed to parse"))
+                }
+                Some(_) => {}
+            }
+        }
+
+        if self.require_bandit_models {
+            let associations = &configuration.flags.compiled.flag_to_bandit_associations;
+            let mut bandit_keys = associations
+                .values()
+                .flat_map(|variations| variations.values())
+                .map(|variation| &variation.key);
+            if let Some(bandit_key) =
+                bandit_keys.find(|bandit_key| configuration.get_bandit(bandit_key).is_none())
+            {
+                return Err(format!("bandit model {bandit_key:?} is missing"));
+            }
+        }
+
+        for (name, predicate) in &self.predicates {
+            predicate(configuration, previous).map_err(|reason| format!("{name}: {reason}"))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        configuration_builder::{BanditBuilder, ConfigurationBuilder, FlagBuilder},
+        ufc::VariationType,
+        Configuration, Error, SdkMetadata,
+    };
+
+    use super::ConfigurationValidator;
+
+    fn configuration(flags: &[&str]) -> Configuration {
+        flags
+            .iter()
+            .fold(ConfigurationBuilder::new(), |builder, key| {
+                builder.flag(FlagBuilder::new(*key, VariationType::Boolean))
+            })
+            .build()
+            .unwrap()
+    }
+
+    fn is_rejected(
+        validator: &ConfigurationValidator,
+        configuration: &Configuration,
+        previous: Option<&Configuration>,
+    ) -> bool {
+        match validator.validate(configuration, previous) {
+            Ok(()) => false,
+            Err(Error::ConfigurationRejected(_)) => true,
+            Err(err) => panic!("unexpected error: {err}"),
+        }
+    }
+
+    #[test]
+    fn accepts_everything_by_default() {
+        let validator = ConfigurationValidator::new();
+        assert!(!is_rejected(
+            &validator,
+            &configuration(&[]),
+            Some(&configuration(&["a", "b"]))
+        ));
+    }
+
+    #[test]
+    fn rejects_parse_failures() {
+        let configuration = Configuration::from_json(
+            SdkMetadata {
+                name: "test",
+                version: "0.1.0",
+            },
+            br#"{
+                "createdAt": "2024-07-18T00:00:00Z",
+                "environment": {"name": "Test"},
+                "flags": {
+                    "ok": {"key": "ok", "enabled": true, "variationType": "BOOLEAN", "variations": {}, "allocations": [], "totalShards": 10000},
+                    "broken": {"key": "broken", "enabled": true}
+                }
+            }"#
+            .to_vec(),
+            None,
+        )
+        .unwrap();
+
+        let validator = ConfigurationValidator::new().with_max_parse_failure_ratio(0.5);
+        assert!(!is_rejected(&validator, &configuration, None));
+        let validator = ConfigurationValidator::new().with_max_parse_failure_ratio(0.25);
+        assert!(is_rejected(&validator, &configuration, None));
+        let validator = ConfigurationValidator::new().with_required_flag("broken");
+        assert!(is_rejected(&validator, &configuration, None));
+    }
+
+    #[test]
+    fn rejects_shrunk_flag_set() {
+        let validator = ConfigurationValidator::new().with_min_flag_count_ratio(0.5);
+        let previous = configuration(&["a", "b", "c", "d"]);
+
+        assert!(!is_rejected(&validator, &configuration(&["a"]), None));
+        assert!(!is_rejected(
+            &validator,
+            &configuration(&["a", "b"]),
+            Some(&previous)
+        ));
+        assert!(is_rejected(
+            &validator,
+            &configuration(&["a"]),
+            Some(&previous)
+        ));
+    }
+
+    #[test]
+    fn rejects_missing_required_flags() {
+        let validator = ConfigurationValidator::new().with_required_flag("kill-switch");
+        assert!(!is_rejected(
+            &validator,
+            &configuration(&["kill-switch"]),
+            None
+        ));
+        assert!(is_rejected(&validator, &configuration(&["a"]), None));
+    }
+
+    #[test]
+    fn rejects_missing_bandit_models() {
+        let flag = FlagBuilder::new("flag", VariationType::String)
+            .variation("bandit", "bandit")
+            .bandit_variation("bandit", "bandit");
+        let without_model = ConfigurationBuilder::new()
+            .flag(flag.clone())
+            .build()
+            .unwrap();
+        let with_model = ConfigurationBuilder::new()
+            .flag(flag)
+            .bandit(BanditBuilder::new("bandit"))
+            .build()
+            .unwrap();
+
+        let validator = ConfigurationValidator::new().with_bandit_models_required();
+        assert!(is_rejected(&validator, &without_model, None));
+        assert!(!is_rejected(&validator, &with_model, None));
+    }
+
+    #[test]
+    fn runs_predicates() {
+        let validator = ConfigurationValidator::new().with_predicate(
+            "no-removals",
+            |configuration, previous| match previous {
+                Some(previous)
+                    if previous
+                        .flag_keys()
+                        .any(|key| !configuration.flag_keys().any(|it| it == key)) =>
+                {
+                    Err("flags were removed".to_owned())
+                }
+                _ => Ok(()),
+            },
+        );
+
+        let previous = configuration(&["a", "b"]);
+        assert!(!is_rejected(
+            &validator,
+            &configuration(&["a", "b", "c"]),
+            Some(&previous)
+        ));
+        let Err(Error::ConfigurationRejected(reason)) =
+            validator.validate(&configuration(&["a"]), Some(&previous))
+        else {
+            panic!("configuration should be rejected");
+        };
+        assert_eq!(reason, "no-removals: flags were removed");
+    }
+}
@@ -44,6 +44,22 @@ pub enum Error {
 
     #[error("timed out waiting for operation")]
     Timeout,
+
+    /// Flag overrides could not be parsed.
+    #[error("invalid flag overrides: {0}")]
+    InvalidFlagOverrides(String),
+
+    /// HTTP client settings are invalid (e.g., malformed proxy URL or root certificate).
+    #[error("invalid HTTP client configuration: {0}")]
+    InvalidHttpClientConfig(String),
+
+    /// Fetched configuration did not pass validation and was not applied.
+    #[error("configuration rejected: {0}")]
+    ConfigurationRejected(String),
+
+    /// Cached configuration could not be read or written.
+    #[error("configuration cache error: {0}")]
+    ConfigurationCache(String),
 }
 
 impl From<std::io::Error> for Error {
@@ -96,6 +112,11 @@ pub(crate) enum EvaluationFailure {
     #[error("configuration has not been fetched yet")]
     ConfigurationMissing,
 
+    /// Configuration is older than the configured maximum age and the evaluator is set up to
+    /// return default values in that case.
+    #[error("configuration is stale")]
+    ConfigurationStale,
+
     /// The requested flag configuration was not found. It either does not exist or is disabled.
     #[error("flag is missing in configuration, it is either unrecognized or disabled")]
     FlagUnrecognizedOrDisabled,
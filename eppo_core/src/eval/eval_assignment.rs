@@ -260,7 +260,7 @@ impl Allocation {
         let is_allowed_by_rules = self.rules.is_empty()
             || self.rules.iter().any(|rule| {
                 let mut visitor = visitor.visit_rule(rule);
-                let result = rule.eval(&mut visitor, subject);
+                let result = rule.eval_at(&mut visitor, subject, now);
                 visitor.on_result(result);
                 result
             });
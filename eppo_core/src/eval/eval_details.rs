@@ -16,6 +16,8 @@ pub enum FlagEvaluationCode {
     Match,
     /// Configuration has not been fetched yet.
     ConfigurationMissing,
+    /// Configuration is older than the maximum allowed age, so the default value was returned.
+    ConfigurationStale,
     /// Flag does not exist or is not enabled for the environment in use.
     FlagUnrecognizedOrDisabled,
     /// Default allocation is matched and is also serving NULL, resulting in the default value being
@@ -26,6 +28,8 @@ pub enum FlagEvaluationCode {
     /// Configuration received from the server is invalid for the SDK. This should normally never
     /// happen and is likely a signal that you should update SDK.
     UnexpectedConfigurationError,
+    /// Value was set by a local flag override and configuration was not consulted.
+    LocalOverride,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -35,6 +39,8 @@ pub enum BanditEvaluationCode {
     Match,
     /// Configuration has not been fetched yet.
     ConfigurationMissing,
+    /// Configuration is older than the maximum allowed age, so the default variation was returned.
+    ConfigurationStale,
     /// Configuration received from the server is invalid for the SDK. This should normally never
     /// happen and is likely a signal that you should update SDK.
     UnexpectedConfigurationError,
@@ -171,6 +177,7 @@ impl From<EvaluationFailure> for FlagEvaluationCode {
     fn from(value: EvaluationFailure) -> Self {
         match value {
             EvaluationFailure::ConfigurationMissing => Self::ConfigurationMissing,
+            EvaluationFailure::ConfigurationStale => Self::ConfigurationStale,
             EvaluationFailure::FlagUnrecognizedOrDisabled => Self::FlagUnrecognizedOrDisabled,
             EvaluationFailure::FlagDisabled => Self::FlagUnrecognizedOrDisabled,
             EvaluationFailure::DefaultAllocationNull => Self::DefaultAllocationNull,
@@ -213,6 +220,7 @@ impl From<EvaluationFailure> for BanditEvaluationCode {
         match value {
             EvaluationFailure::Error(err) => err.into(),
             EvaluationFailure::ConfigurationMissing => Self::ConfigurationMissing,
+            EvaluationFailure::ConfigurationStale => Self::ConfigurationStale,
             EvaluationFailure::FlagUnrecognizedOrDisabled
             | EvaluationFailure::FlagDisabled
             | EvaluationFailure::DefaultAllocationNull => {
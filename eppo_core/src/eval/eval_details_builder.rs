@@ -29,6 +29,8 @@ pub(crate) struct EvalDetailsBuilder {
     environment_name: Option<Str>,
 
     flag_evaluation_failure: Option<Result<(), EvaluationFailure>>,
+    /// Set if the value comes from a local flag override.
+    overridden: bool,
     variation_key: Option<Str>,
     variation_value: Option<AssignmentValue>,
 
@@ -83,6 +85,7 @@ impl EvalDetailsBuilder {
             configuration_published_at: None,
             environment_name: None,
             flag_evaluation_failure: None,
+            overridden: false,
             variation_key: None,
             variation_value: None,
             bandit_evaluation_failure: None,
@@ -105,7 +108,11 @@ impl EvalDetailsBuilder {
             config_published_at: self.configuration_published_at,
             environment_name: self.environment_name,
             bandit_evaluation_code: self.bandit_evaluation_failure.map(|it| it.into()),
-            flag_evaluation_code: self.flag_evaluation_failure.map(|it| it.into()),
+            flag_evaluation_code: if self.overridden {
+                Some(FlagEvaluationCode::LocalOverride)
+            } else {
+                self.flag_evaluation_failure.map(|it| it.into())
+            },
             flag_evaluation_description,
             variation_key: self.variation_key,
             variation_value: self.variation_value,
@@ -129,7 +136,21 @@ impl EvalDetailsBuilder {
         }
     }
 
+    /// Record that the value was set by a local flag override.
+    pub fn on_override(&mut self, value: AssignmentValue) {
+        self.overridden = true;
+        self.flag_evaluation_failure = Some(Ok(()));
+        self.variation_value = Some(value);
+    }
+
     fn build_flag_evaluation_description(&self) -> String {
+        if self.overridden {
+            return format!(
+                "Flag {} is overridden locally for {}",
+                self.flag_key, self.subject_key
+            );
+        }
+
         if self.flag_evaluation_failure.is_none() {
             return "Flag evaluation was not attempted".to_owned();
         }
@@ -146,6 +167,13 @@ impl EvalDetailsBuilder {
                 EvaluationFailure::ConfigurationMissing => {
                     format!("Configuration has not been fetched yet")
                 }
+                EvaluationFailure::ConfigurationStale => match self.configuration_fetched_at {
+                    Some(fetched_at) => format!(
+                        "Configuration fetched at {} is stale. Returning default value",
+                        fetched_at.to_rfc3339()
+                    ),
+                    None => "Configuration is stale. Returning default value".to_owned(),
+                },
                 EvaluationFailure::FlagUnrecognizedOrDisabled => {
                     format!("Unrecognized or disabled flag: {}", self.flag_key)
                 }
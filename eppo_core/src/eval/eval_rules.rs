@@ -1,25 +1,46 @@
+use chrono::{DateTime, Utc};
 use semver::Version;
 
 use crate::{
-    ufc::{Comparand, ComparisonOperator, Condition, ConditionCheck, RuleWire, TryParse},
+    timestamp::Timestamp,
+    ufc::{
+        Comparand, ComparisonOperator, Condition, ConditionCheck, ListOperator, RuleWire,
+        SubstringOperator, TryParse,
+    },
     AttributeValue,
 };
 
 use super::{eval_visitor::EvalRuleVisitor, subject::Subject};
 
 impl RuleWire {
-    pub(super) fn eval<V: EvalRuleVisitor>(&self, visitor: &mut V, subject: &Subject) -> bool {
+    #[cfg(test)]
+    fn eval<V: EvalRuleVisitor>(&self, visitor: &mut V, subject: &Subject) -> bool {
+        self.eval_at(visitor, subject, Utc::now())
+    }
+
+    /// Evaluate rule at `now`, which is used for relative datetime checks.
+    pub(super) fn eval_at<V: EvalRuleVisitor>(
+        &self,
+        visitor: &mut V,
+        subject: &Subject,
+        now: Timestamp,
+    ) -> bool {
         self.conditions.iter().all(|condition| match condition {
-            TryParse::Parsed(condition) => condition.eval(visitor, subject),
+            TryParse::Parsed(condition) => condition.eval_at(visitor, subject, now),
             TryParse::ParseFailed(_) => false,
         })
     }
 }
 
 impl Condition {
-    fn eval<V: EvalRuleVisitor>(&self, visitor: &mut V, subject: &Subject) -> bool {
+    fn eval_at<V: EvalRuleVisitor>(
+        &self,
+        visitor: &mut V,
+        subject: &Subject,
+        now: Timestamp,
+    ) -> bool {
         let attribute = subject.get_attribute(self.attribute.as_ref());
-        let result = self.check.eval(attribute);
+        let result = self.check.eval_at(attribute, now);
         visitor.on_condition_eval(self, attribute, result);
         result
     }
@@ -27,12 +48,18 @@ impl Condition {
 
 impl ConditionCheck {
     /// Check if `attribute` matches.
+    #[cfg(test)]
     fn eval(&self, attribute: Option<&AttributeValue>) -> bool {
-        self.try_eval(attribute).unwrap_or(false)
+        self.eval_at(attribute, Utc::now())
+    }
+
+    /// Check if `attribute` matches at `now`, which is used for relative datetime checks.
+    fn eval_at(&self, attribute: Option<&AttributeValue>, now: Timestamp) -> bool {
+        self.try_eval(attribute, now).unwrap_or(false)
     }
 
     /// Try applying `Operator` to the values, returning `None` if the operator cannot be applied.
-    fn try_eval(&self, attribute: Option<&AttributeValue>) -> Option<bool> {
+    fn try_eval(&self, attribute: Option<&AttributeValue>, now: Timestamp) -> Option<bool> {
         let result = match self {
             ConditionCheck::Comparison {
                 operator,
@@ -41,19 +68,23 @@ impl ConditionCheck {
                 let attribute = attribute?.clone();
                 let ordering = match comparand {
                     Comparand::Version(comparand) => {
-                        let attribute = Version::parse(attribute.as_str()?).ok()?;
+                        let attribute = parse_version(&attribute)?;
                         attribute.cmp(comparand)
                     }
                     Comparand::Number(comparand) => {
                         let attribute = attribute.coerce_to_number()?;
                         attribute.partial_cmp(comparand)?
                     }
+                    Comparand::DateTime { datetime, .. } => {
+                        let attribute = parse_datetime(&attribute)?;
+                        attribute.cmp(datetime)
+                    }
                 };
                 match operator {
                     ComparisonOperator::Gte => ordering.is_gt() || ordering.is_eq(),
-                    ComparisonOperator::Gt => ordering.is_gt(),
+                    ComparisonOperator::Gt | ComparisonOperator::After => ordering.is_gt(),
                     ComparisonOperator::Lte => ordering.is_lt() || ordering.is_eq(),
-                    ComparisonOperator::Lt => ordering.is_lt(),
+                    ComparisonOperator::Lt | ComparisonOperator::Before => ordering.is_lt(),
                 }
             }
             ConditionCheck::Regex {
@@ -68,17 +99,84 @@ impl ConditionCheck {
                 let s = s.as_ref();
                 values.into_iter().any(|it| it.as_ref() == s) == *expected_membership
             }
+            ConditionCheck::ListMembership { operator, values } => {
+                let items = attribute?.coerce_to_string_list()?;
+                let contains = |value: &str| items.iter().any(|it| it == value);
+                let mut values = values.iter().map(|it| it.as_ref());
+                match operator {
+                    ListOperator::Any => values.any(contains),
+                    ListOperator::All => values.all(contains),
+                    ListOperator::None => !values.any(contains),
+                }
+            }
+            ConditionCheck::VersionMatches {
+                value: _,
+                requirements,
+            } => {
+                let attribute = parse_version(attribute?)?;
+                requirements.iter().any(|it| it.matches(&attribute))
+            }
             ConditionCheck::Null { expected_null } => {
                 let is_present = attribute.is_some_and(|it| !it.is_null());
                 let is_null = !is_present;
                 is_null == *expected_null
             }
+            ConditionCheck::WithinLast { days: _, period } => {
+                let attribute = parse_datetime(attribute?)?;
+                attribute <= now && now - attribute <= *period
+            }
+            ConditionCheck::Substring {
+                operator,
+                expected_match,
+                ignore_case,
+                value: _,
+                needle,
+            } => {
+                let s = attribute?.coerce_to_string()?;
+                let s = if *ignore_case {
+                    s.to_lowercase().into()
+                } else {
+                    s
+                };
+                let needle = needle.as_ref();
+                let is_match = match operator {
+                    SubstringOperator::Contains => s.contains(needle),
+                    SubstringOperator::StartsWith => s.starts_with(needle),
+                    SubstringOperator::EndsWith => s.ends_with(needle),
+                };
+                is_match == *expected_match
+            }
         };
 
         Some(result)
     }
 }
 
+fn parse_datetime(attribute: &AttributeValue) -> Option<Timestamp> {
+    DateTime::parse_from_rfc3339(attribute.as_str()?)
+        .ok()
+        .map(|it| it.with_timezone(&Utc))
+}
+
+/// Parse attribute as a semver version. Unlike `Version::parse()`, this accepts `v` prefix and
+/// missing minor/patch components (`"v4.2"` is parsed as `4.2.0`).
+fn parse_version(attribute: &AttributeValue) -> Option<Version> {
+    let s = attribute.as_str()?.trim();
+    let s = s.strip_prefix(['v', 'V']).unwrap_or(s);
+    if let Ok(version) = Version::parse(s) {
+        return Some(version);
+    }
+
+    // Pad missing components, keeping pre-release and build metadata in place.
+    let (core, rest) = s.split_at(s.find(['-', '+']).unwrap_or(s.len()));
+    let padded = match core.split('.').count() {
+        1 => format!("{core}.0.0{rest}"),
+        2 => format!("{core}.0{rest}"),
+        _ => return None,
+    };
+    Version::parse(&padded).ok()
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -87,7 +185,8 @@ mod tests {
 
     use crate::{
         eval::{eval_visitor::NoopEvalVisitor, subject::Subject},
-        ufc::{Comparand, ComparisonOperator, Condition, ConditionCheck, RuleWire},
+        ufc::{Comparand, ComparisonOperator, Condition, ConditionCheck, ListOperator, RuleWire},
+        AttributeValue,
     };
 
     #[test]
@@ -164,6 +263,44 @@ mod tests {
         assert!(!false_check.eval(None));
     }
 
+    #[test]
+    fn contains_any_all_none() {
+        let roles = AttributeValue::list(["admin", "editor"]);
+        let check = |operator, values: &[&str]| ConditionCheck::ListMembership {
+            operator,
+            values: values.iter().map(|it| (*it).into()).collect(),
+        };
+
+        let any = check(ListOperator::Any, &["admin", "owner"]);
+        assert!(any.eval(Some(&roles)));
+        assert!(!any.eval(Some(&AttributeValue::list(["viewer"]))));
+        // Scalar attributes are treated as single-item lists.
+        assert!(any.eval(Some(&"owner".into())));
+        assert!(!any.eval(None));
+
+        let all = check(ListOperator::All, &["admin", "editor"]);
+        assert!(all.eval(Some(&roles)));
+        assert!(!all.eval(Some(&AttributeValue::list(["admin"]))));
+
+        let none = check(ListOperator::None, &["owner"]);
+        assert!(none.eval(Some(&roles)));
+        assert!(!none.eval(Some(&AttributeValue::list(["owner"]))));
+        // CONTAINS_NONE fails when attribute is not specified
+        assert!(!none.eval(None));
+        assert!(!none.eval(Some(&AttributeValue::null())));
+
+        // List items are coerced to strings.
+        let numbers = check(ListOperator::Any, &["42"]);
+        assert!(numbers.eval(Some(&AttributeValue::list([42.0, 7.0]))));
+
+        // Scalar operators never match lists.
+        let one_of = ConditionCheck::Membership {
+            expected_membership: true,
+            values: ["admin".into()].into(),
+        };
+        assert!(!one_of.eval(Some(&roles)));
+    }
+
     #[test]
     fn is_null() {
         assert!(ConditionCheck::Null {
@@ -188,6 +325,92 @@ mod tests {
         .eval(Some(&10.0.into())));
     }
 
+    fn check(operator: &str, value: &str) -> ConditionCheck {
+        serde_json::from_value::<Condition>(serde_json::json!({
+            "attribute": "email",
+            "operator": operator,
+            "value": value,
+        }))
+        .unwrap()
+        .check
+    }
+
+    #[test]
+    fn contains() {
+        let check = check("CONTAINS", "@acme");
+        assert!(check.eval(Some(&"alice@acme.com".into())));
+        assert!(!check.eval(Some(&"alice@ACME.com".into())));
+        assert!(!check.eval(None));
+
+        let check = self::check("NOT_CONTAINS", "@acme");
+        assert!(!check.eval(Some(&"alice@acme.com".into())));
+        assert!(check.eval(Some(&"alice@example.com".into())));
+        // NOT_CONTAINS fails when attribute is not specified
+        assert!(!check.eval(None));
+    }
+
+    #[test]
+    fn starts_with_and_ends_with() {
+        let starts_with = check("STARTS_WITH", "alice");
+        assert!(starts_with.eval(Some(&"alice@acme.com".into())));
+        assert!(!starts_with.eval(Some(&"bob.alice@acme.com".into())));
+
+        let ends_with = check("ENDS_WITH", "@acme.com");
+        assert!(ends_with.eval(Some(&"alice@acme.com".into())));
+        assert!(!ends_with.eval(Some(&"alice@acme.com.evil".into())));
+
+        // Non-string attributes are coerced to strings.
+        assert!(check("STARTS_WITH", "4").eval(Some(&42.0.into())));
+    }
+
+    #[test]
+    fn ignore_case() {
+        assert!(check("CONTAINS_IGNORE_CASE", "@Acme").eval(Some(&"alice@ACME.com".into())));
+        assert!(!check("NOT_CONTAINS_IGNORE_CASE", "@Acme").eval(Some(&"alice@ACME.com".into())));
+        assert!(check("STARTS_WITH_IGNORE_CASE", "ALICE").eval(Some(&"Alice@acme.com".into())));
+        assert!(check("ENDS_WITH_IGNORE_CASE", "@ACME.COM").eval(Some(&"alice@acme.com".into())));
+        assert!(!check("ENDS_WITH_IGNORE_CASE", "@ACME.COM").eval(Some(&"alice@acme.org".into())));
+    }
+
+    #[test]
+    fn before_and_after() {
+        let after = check("AFTER", "2026-01-01T00:00:00Z");
+        assert!(after.eval(Some(&"2026-01-01T00:00:01Z".into())));
+        // 2025-12-31T23:00:00Z
+        assert!(!after.eval(Some(&"2026-01-01T01:00:00+02:00".into())));
+        assert!(!after.eval(Some(&"2026-01-01T00:00:00Z".into())));
+        assert!(!after.eval(Some(&"not a date".into())));
+        assert!(!after.eval(Some(&1767225601.0.into())));
+
+        let before = check("BEFORE", "2026-01-01T00:00:00Z");
+        assert!(before.eval(Some(&"2025-12-31T23:59:59Z".into())));
+        assert!(!before.eval(Some(&"2026-01-01T00:00:00Z".into())));
+        assert!(!before.eval(None));
+
+        // Inclusive comparisons accept datetimes too.
+        let gte = check("GTE", "2026-01-01T00:00:00Z");
+        assert!(gte.eval(Some(&"2026-01-01T00:00:00Z".into())));
+    }
+
+    #[test]
+    fn within_last_days() {
+        let check = serde_json::from_value::<Condition>(serde_json::json!({
+            "attribute": "signup",
+            "operator": "WITHIN_LAST_DAYS",
+            "value": 7,
+        }))
+        .unwrap()
+        .check;
+        let now = "2026-01-10T00:00:00Z".parse().unwrap();
+
+        assert!(check.eval_at(Some(&"2026-01-09T12:00:00Z".into()), now));
+        assert!(check.eval_at(Some(&"2026-01-03T00:00:00Z".into()), now));
+        assert!(!check.eval_at(Some(&"2026-01-02T23:59:59Z".into()), now));
+        // Future datetimes are not within the last N days.
+        assert!(!check.eval_at(Some(&"2026-01-11T00:00:00Z".into()), now));
+        assert!(!check.eval_at(None, now));
+    }
+
     #[test]
     fn gte() {
         let check = ConditionCheck::Comparison {
@@ -338,6 +561,38 @@ mod tests {
         .eval(Some(&"0.9.9".into())));
     }
 
+    #[test]
+    fn semver_lenient_attribute() {
+        let gte = ConditionCheck::Comparison {
+            operator: ComparisonOperator::Gte,
+            comparand: Comparand::Version("4.2.0".parse().unwrap()),
+        };
+        assert!(gte.eval(Some(&"v4.2".into())));
+        assert!(gte.eval(Some(&"5".into())));
+        assert!(!gte.eval(Some(&"4.1".into())));
+        assert!(!gte.eval(Some(&"4.2-beta.1".into())));
+        assert!(!gte.eval(Some(&"4.2.0.1".into())));
+        assert!(!gte.eval(Some(&"v".into())));
+    }
+
+    #[test]
+    fn semver_matches() {
+        let caret = check("SEMVER_MATCHES", "^4.2");
+        assert!(caret.eval(Some(&"4.2.0".into())));
+        assert!(caret.eval(Some(&"v4.9".into())));
+        assert!(!caret.eval(Some(&"5.0.0".into())));
+        assert!(!caret.eval(Some(&"4.1.9".into())));
+        assert!(!caret.eval(Some(&"not a version".into())));
+        assert!(!caret.eval(Some(&42.0.into())));
+        assert!(!caret.eval(None));
+
+        let ranges = check("SEMVER_MATCHES", ">=1.2, <2 || ~3.1");
+        assert!(ranges.eval(Some(&"1.5.0".into())));
+        assert!(ranges.eval(Some(&"3.1.7".into())));
+        assert!(!ranges.eval(Some(&"2.0.0".into())));
+        assert!(!ranges.eval(Some(&"3.2.0".into())));
+    }
+
     #[test]
     fn empty_rule() {
         let rule = RuleWire { conditions: vec![] };
@@ -453,4 +708,97 @@ mod tests {
             )
         ));
     }
+
+    #[test]
+    fn nested_attribute_paths() {
+        let subject = Subject::new(
+            "key".into(),
+            Arc::new(
+                serde_json::from_value(serde_json::json!({
+                    "device": {
+                        "os": {"name": "ios", "version": "17.2"},
+                        "screen.size": 6.1,
+                        "a/b~c": "escaped",
+                    },
+                    "device.os.name": "flat wins",
+                    "account": {"plan": {"tier": "pro"}, "roles": ["admin"]},
+                }))
+                .unwrap(),
+            ),
+        );
+        let matches = |attribute: &str, operator: &str, value: serde_json::Value| {
+            let condition = serde_json::from_value::<Condition>(serde_json::json!({
+                "attribute": attribute,
+                "operator": operator,
+                "value": value,
+            }))
+            .unwrap();
+            RuleWire {
+                conditions: vec![condition.into()],
+            }
+            .eval(&mut NoopEvalVisitor, &subject)
+        };
+
+        // Dotted paths.
+        assert!(matches(
+            "account.plan.tier",
+            "ONE_OF",
+            serde_json::json!(["pro"])
+        ));
+        assert!(matches(
+            "device.os.version",
+            "GTE",
+            serde_json::json!("17.0.0")
+        ));
+        assert!(matches(
+            "account.roles",
+            "CONTAINS_ANY",
+            serde_json::json!(["admin"])
+        ));
+        // Exact top-level attribute takes precedence over path.
+        assert!(matches(
+            "device.os.name",
+            "ONE_OF",
+            serde_json::json!(["flat wins"])
+        ));
+
+        // JSON pointers.
+        assert!(matches(
+            "/device/os/name",
+            "ONE_OF",
+            serde_json::json!(["ios"])
+        ));
+        assert!(matches("/device/screen.size", "GT", serde_json::json!(6)));
+        assert!(matches(
+            "/device/a~1b~0c",
+            "ONE_OF",
+            serde_json::json!(["escaped"])
+        ));
+
+        // Missing paths and non-object intermediates are treated as missing attributes.
+        assert!(matches(
+            "account.plan.missing",
+            "IS_NULL",
+            serde_json::json!(true)
+        ));
+        assert!(matches(
+            "account.plan.tier.x",
+            "IS_NULL",
+            serde_json::json!(true)
+        ));
+        assert!(matches(
+            "/account/roles/0",
+            "IS_NULL",
+            serde_json::json!(true)
+        ));
+        assert!(!matches(
+            "/device/screen.size/x",
+            "IS_NULL",
+            serde_json::json!(false)
+        ));
+
+        // Objects never match conditions directly.
+        assert!(!matches("account", "ONE_OF", serde_json::json!(["pro"])));
+        assert!(!matches("account.plan", "MATCHES", serde_json::json!(".*")));
+    }
 }
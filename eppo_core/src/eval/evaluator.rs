@@ -1,11 +1,21 @@
-use std::sync::Arc;
+use std::{
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use arc_swap::Guard;
 
-use crate::hashmap::HashMap;
+use crate::hashmap::*;
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 
 use crate::{
+    assignment_cache::AssignmentCache,
     configuration_store::ConfigurationStore,
+    error::EvaluationFailure,
     events::AssignmentEvent,
     precomputed::PrecomputedConfiguration,
     ufc::{Assignment, AssignmentValue, VariationType},
@@ -14,24 +24,100 @@ use crate::{
 
 use super::{
     eval_details::{EvaluationDetails, EvaluationResultWithDetails},
+    eval_details_builder::EvalDetailsBuilder,
+    eval_visitor::{EvalAssignmentVisitor, EvalBanditVisitor},
     get_assignment, get_assignment_details, get_bandit_action, get_bandit_action_details,
-    get_precomputed_configuration, BanditResult,
+    get_precomputed_configuration,
+    overrides::{FlagOverrides, FlagOverridesStore},
+    BanditResult,
 };
 
 pub struct EvaluatorConfig {
     pub configuration_store: Arc<ConfigurationStore>,
     pub sdk_metadata: SdkMetadata,
+    /// If set, duplicate assignment and bandit events are dropped from evaluation results.
+    pub assignment_cache: Option<Arc<AssignmentCache>>,
+    /// If set, configuration that was last fetched or confirmed unchanged longer than this ago is
+    /// considered stale and is handled according to `stale_configuration_policy`.
+    pub max_configuration_age: Option<Duration>,
+    pub stale_configuration_policy: StaleConfigurationPolicy,
+}
+
+/// Determines what happens when evaluating against configuration that is older than
+/// [`EvaluatorConfig::max_configuration_age`].
+///
+/// Configuration age is counted from the last time configuration was fetched or confirmed
+/// unchanged (see [`ConfigurationStore::configuration_confirmed_at()`]), so configuration only
+/// goes stale when the poller fails to reach the source.
+#[derive(Clone, Default)]
+pub enum StaleConfigurationPolicy {
+    /// Evaluate against stale configuration and log a warning. The warning is logged at most once
+    /// a minute.
+    #[default]
+    Warn,
+    /// Return default values with
+    /// [`FlagEvaluationCode::ConfigurationStale`](super::eval_details::FlagEvaluationCode::ConfigurationStale).
+    /// Local flag overrides still apply.
+    ReturnDefault,
+    /// Evaluate against stale configuration and call the callback with the configuration and its
+    /// age. The callback is called on every evaluation while configuration stays stale, so it
+    /// should be cheap.
+    Callback(Arc<StaleConfigurationCallback>),
+}
+
+/// Callback of [`StaleConfigurationPolicy::Callback`]. Receives stale configuration and its age.
+pub type StaleConfigurationCallback = dyn Fn(&Configuration, Duration) + Send + Sync;
+
+impl std::fmt::Debug for StaleConfigurationPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StaleConfigurationPolicy::Warn => f.write_str("Warn"),
+            StaleConfigurationPolicy::ReturnDefault => f.write_str("ReturnDefault"),
+            StaleConfigurationPolicy::Callback(_) => f.write_str("Callback(..)"),
+        }
+    }
+}
+
+/// Configuration to evaluate against.
+struct Snapshot {
+    configuration: Guard<Option<Arc<Configuration>>>,
+    /// Set if configuration is stale and default values should be returned.
+    stale: bool,
 }
 
 /// Evaluator simplifies calling into evaluation functions and automatically adds necessary metadata
 /// to events (SDK name and version).
+///
+/// Evaluator also holds local [`FlagOverrides`] which take precedence over the configuration.
 pub struct Evaluator {
     config: EvaluatorConfig,
+    overrides: FlagOverridesStore,
+    /// Milliseconds since Unix epoch when stale configuration warning was last logged, or
+    /// `i64::MIN` if it was never logged.
+    last_stale_warning: AtomicI64,
 }
 
+/// Stale configuration is checked on every evaluation, so warnings are rate-limited to avoid
+/// flooding logs.
+const STALE_WARNING_INTERVAL: chrono::TimeDelta = chrono::TimeDelta::minutes(1);
+
 impl Evaluator {
     pub fn new(config: EvaluatorConfig) -> Evaluator {
-        Evaluator { config }
+        Evaluator {
+            config,
+            overrides: FlagOverridesStore::default(),
+            last_stale_warning: AtomicI64::new(i64::MIN),
+        }
+    }
+
+    /// Replace local flag overrides. Takes effect for all subsequent evaluations.
+    pub fn set_overrides(&self, overrides: impl Into<Arc<FlagOverrides>>) {
+        self.overrides.set(overrides.into());
+    }
+
+    /// Return currently active local flag overrides.
+    pub fn get_overrides(&self) -> Arc<FlagOverrides> {
+        self.overrides.get()
     }
 
     pub fn get_assignment(
@@ -41,15 +127,21 @@ impl Evaluator {
         subject_attributes: &Arc<Attributes>,
         expected_type: Option<VariationType>,
     ) -> Result<Option<Assignment>, EvaluationError> {
-        let config = self.get_configuration();
-        get_assignment(
-            config.as_ref().map(AsRef::as_ref),
-            &flag_key,
-            &subject_key,
-            &subject_attributes,
+        let now = Utc::now();
+        let snapshot = self.get_configuration(now);
+        let mut result = self.get_assignment_with_snapshot(
+            &snapshot,
+            &self.overrides.load(),
+            flag_key,
+            subject_key,
+            subject_attributes,
             expected_type,
-            Utc::now(),
-        )
+            now,
+        );
+        if let Ok(Some(assignment)) = &mut result {
+            self.dedup_assignment_event(&mut assignment.event);
+        }
+        result
     }
 
     pub fn get_assignment_details(
@@ -62,15 +154,102 @@ impl Evaluator {
         EvaluationResultWithDetails<AssignmentValue>,
         Option<AssignmentEvent>,
     ) {
-        let config = self.get_configuration();
-        get_assignment_details(
-            config.as_ref().map(AsRef::as_ref),
-            &flag_key,
-            &subject_key,
-            &subject_attributes,
+        let now = Utc::now();
+        let snapshot = self.get_configuration(now);
+        let (result, mut event) = self.get_assignment_details_with_snapshot(
+            &snapshot,
+            &self.overrides.load(),
+            flag_key,
+            subject_key,
+            subject_attributes,
             expected_type,
-            Utc::now(),
-        )
+            now,
+        );
+        self.dedup_assignment_event(&mut event);
+        (result, event)
+    }
+
+    /// Evaluate all enabled and locally overridden flags for the given subject.
+    ///
+    /// All flags are evaluated against the same configuration snapshot, so the result is
+    /// consistent even if configuration is updated concurrently. Flags that evaluate to the default
+    /// value are omitted from the result. Assignment events are returned along with values and
+    /// should be logged by the caller when the assignment is actually used. Events are not
+    /// deduplicated here, as most of them are never logged; use
+    /// [`AssignmentCache::should_log_assignment()`] at logging time instead.
+    pub fn get_all_assignments(
+        &self,
+        subject_key: &Str,
+        subject_attributes: &Arc<Attributes>,
+    ) -> HashMap<Str, Assignment> {
+        let now = Utc::now();
+        let snapshot = self.get_configuration(now);
+        let config = snapshot.configuration.as_deref();
+        if config.is_none() {
+            log::warn!(target: "eppo",
+                       subject_key;
+                       "evaluating flags before Eppo configuration has been fetched");
+        }
+        let overrides = self.overrides.load();
+
+        evaluated_flag_keys(config, &overrides)
+            .filter_map(|flag_key| {
+                let assignment = self
+                    .get_assignment_with_snapshot(
+                        &snapshot,
+                        &overrides,
+                        flag_key,
+                        subject_key,
+                        subject_attributes,
+                        None,
+                        now,
+                    )
+                    .ok()??;
+                Some((flag_key.clone(), assignment))
+            })
+            .collect()
+    }
+
+    /// Evaluate all enabled and locally overridden flags for the given subject and return
+    /// evaluation details.
+    ///
+    /// Same as [`Evaluator::get_all_assignments()`] but every evaluated flag is present in the
+    /// result, even if it evaluated to the default value.
+    pub fn get_all_assignments_details(
+        &self,
+        subject_key: &Str,
+        subject_attributes: &Arc<Attributes>,
+    ) -> HashMap<
+        Str,
+        (
+            EvaluationResultWithDetails<AssignmentValue>,
+            Option<AssignmentEvent>,
+        ),
+    > {
+        let now = Utc::now();
+        let snapshot = self.get_configuration(now);
+        let config = snapshot.configuration.as_deref();
+        if config.is_none() {
+            log::warn!(target: "eppo",
+                       subject_key;
+                       "evaluating flags before Eppo configuration has been fetched");
+        }
+        let overrides = self.overrides.load();
+
+        evaluated_flag_keys(config, &overrides)
+            .map(|flag_key| {
+                let result = self.get_assignment_details_with_snapshot(
+                    &snapshot,
+                    &overrides,
+                    flag_key,
+                    subject_key,
+                    subject_attributes,
+                    None,
+                    now,
+                );
+                (flag_key.clone(), result)
+            })
+            .collect()
     }
 
     pub fn get_bandit_action(
@@ -81,17 +260,23 @@ impl Evaluator {
         actions: &HashMap<Str, ContextAttributes>,
         default_variation: &Str,
     ) -> BanditResult {
-        let configuration = self.get_configuration();
-        get_bandit_action(
-            configuration.as_ref().map(|it| it.as_ref()),
+        let now = Utc::now();
+        let snapshot = self.get_configuration(now);
+        if snapshot.stale {
+            return default_bandit_result(default_variation);
+        }
+        let mut result = get_bandit_action(
+            snapshot.configuration.as_deref(),
             flag_key,
             subject_key,
             subject_attributes,
             actions,
             default_variation,
-            Utc::now(),
+            now,
             &self.config.sdk_metadata,
-        )
+        );
+        self.dedup_bandit_result(&mut result);
+        result
     }
 
     pub fn get_bandit_action_details(
@@ -102,17 +287,38 @@ impl Evaluator {
         actions: &HashMap<Str, ContextAttributes>,
         default_variation: &Str,
     ) -> (BanditResult, EvaluationDetails) {
-        let configuration = self.get_configuration();
-        get_bandit_action_details(
-            configuration.as_ref().map(|it| it.as_ref()),
+        let now = Utc::now();
+        let snapshot = self.get_configuration(now);
+        if snapshot.stale {
+            let result = default_bandit_result(default_variation);
+            let mut builder = EvalDetailsBuilder::new(
+                flag_key.to_owned(),
+                subject_key.clone(),
+                subject_attributes.to_generic_attributes().into(),
+                now,
+            );
+            if let Some(config) = &*snapshot.configuration {
+                EvalBanditVisitor::on_configuration(&mut builder, config);
+            }
+            EvalBanditVisitor::on_result(
+                &mut builder,
+                Err(EvaluationFailure::ConfigurationStale),
+                &result,
+            );
+            return (result, builder.build());
+        }
+        let (mut result, details) = get_bandit_action_details(
+            snapshot.configuration.as_deref(),
             flag_key,
             subject_key,
             subject_attributes,
             actions,
             default_variation,
-            Utc::now(),
+            now,
             &self.config.sdk_metadata,
-        )
+        );
+        self.dedup_bandit_result(&mut result);
+        (result, details)
     }
 
     pub fn get_precomputed_configuration(
@@ -124,17 +330,534 @@ impl Evaluator {
             HashMap</* action_key: */ Str, ContextAttributes>,
         >,
     ) -> PrecomputedConfiguration {
-        let configuration = self.get_configuration();
+        let now = Utc::now();
+        let snapshot = self.get_configuration(now);
+        // Stale configuration is precomputed as if it was missing, so clients get default values.
+        let configuration = if snapshot.stale {
+            None
+        } else {
+            snapshot.configuration.as_deref()
+        };
         get_precomputed_configuration(
-            configuration.as_ref().map(AsRef::as_ref),
+            configuration,
             subject_key,
             subject_attributes,
             flag_actions,
-            Utc::now(),
+            now,
+        )
+    }
+
+    fn get_configuration(&self, now: DateTime<Utc>) -> Snapshot {
+        let configuration = self.config.configuration_store.load_configuration();
+        let stale = match (&*configuration, self.config.max_configuration_age) {
+            (Some(configuration), Some(max_age)) => {
+                self.check_configuration_age(configuration, max_age, now)
+            }
+            _ => false,
+        };
+        Snapshot {
+            configuration,
+            stale,
+        }
+    }
+
+    /// Apply stale configuration policy. Returns `true` if default values should be returned.
+    fn check_configuration_age(
+        &self,
+        configuration: &Configuration,
+        max_age: Duration,
+        now: DateTime<Utc>,
+    ) -> bool {
+        let confirmed_at = self
+            .config
+            .configuration_store
+            .configuration_confirmed_at()
+            .unwrap_or_else(|| configuration.fetched_at());
+        let age = (now - confirmed_at).to_std().unwrap_or(Duration::ZERO);
+        if age <= max_age {
+            return false;
+        }
+        match &self.config.stale_configuration_policy {
+            StaleConfigurationPolicy::Warn => {
+                if self.should_warn_stale(now) {
+                    log::warn!(target: "eppo",
+                               age_seconds = age.as_secs(),
+                               max_age_seconds = max_age.as_secs();
+                               "evaluating flags against stale Eppo configuration");
+                }
+                false
+            }
+            StaleConfigurationPolicy::ReturnDefault => {
+                if self.should_warn_stale(now) {
+                    log::warn!(target: "eppo",
+                               age_seconds = age.as_secs(),
+                               max_age_seconds = max_age.as_secs();
+                               "Eppo configuration is stale, returning default values");
+                }
+                true
+            }
+            StaleConfigurationPolicy::Callback(callback) => {
+                callback(configuration, age);
+                false
+            }
+        }
+    }
+
+    /// Returns `true` if stale configuration warning should be logged now. Only one of concurrent
+    /// callers gets `true` within [`STALE_WARNING_INTERVAL`].
+    fn should_warn_stale(&self, now: DateTime<Utc>) -> bool {
+        let now = now.timestamp_millis();
+        let last = self.last_stale_warning.load(Ordering::Relaxed);
+        if last != i64::MIN && now.saturating_sub(last) < STALE_WARNING_INTERVAL.num_milliseconds()
+        {
+            return false;
+        }
+        self.last_stale_warning
+            .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    /// Drop the event if an identical one was logged recently.
+    fn dedup_assignment_event(&self, event: &mut Option<AssignmentEvent>) {
+        if let (Some(cache), Some(e)) = (&self.config.assignment_cache, &event) {
+            if !cache.should_log_assignment(e) {
+                *event = None;
+            }
+        }
+    }
+
+    fn dedup_bandit_result(&self, result: &mut BanditResult) {
+        self.dedup_assignment_event(&mut result.assignment_event);
+        if let (Some(cache), Some(e)) = (&self.config.assignment_cache, &result.bandit_event) {
+            if !cache.should_log_bandit_action(e) {
+                result.bandit_event = None;
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn get_assignment_with_snapshot(
+        &self,
+        snapshot: &Snapshot,
+        overrides: &FlagOverrides,
+        flag_key: &str,
+        subject_key: &Str,
+        subject_attributes: &Arc<Attributes>,
+        expected_type: Option<VariationType>,
+        now: DateTime<Utc>,
+    ) -> Result<Option<Assignment>, EvaluationError> {
+        let config = snapshot.configuration.as_deref();
+        if let Some(value) = overrides.get_override(
+            config,
+            flag_key,
+            subject_key,
+            subject_attributes,
+            expected_type,
+        ) {
+            // Overridden values are not logged as they are not part of an experiment.
+            return Ok(Some(Assignment { value, event: None }));
+        }
+        if snapshot.stale {
+            return Ok(None);
+        }
+        get_assignment(
+            config,
+            flag_key,
+            subject_key,
+            subject_attributes,
+            expected_type,
+            now,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn get_assignment_details_with_snapshot(
+        &self,
+        snapshot: &Snapshot,
+        overrides: &FlagOverrides,
+        flag_key: &str,
+        subject_key: &Str,
+        subject_attributes: &Arc<Attributes>,
+        expected_type: Option<VariationType>,
+        now: DateTime<Utc>,
+    ) -> (
+        EvaluationResultWithDetails<AssignmentValue>,
+        Option<AssignmentEvent>,
+    ) {
+        let config = snapshot.configuration.as_deref();
+        let new_details_builder = || {
+            let mut builder = EvalDetailsBuilder::new(
+                flag_key.to_owned(),
+                subject_key.clone(),
+                subject_attributes.clone(),
+                now,
+            );
+            if let Some(config) = config {
+                EvalAssignmentVisitor::on_configuration(&mut builder, config);
+            }
+            builder
+        };
+        if let Some(value) = overrides.get_override(
+            config,
+            flag_key,
+            subject_key,
+            subject_attributes,
+            expected_type,
+        ) {
+            let mut builder = new_details_builder();
+            builder.on_override(value.clone());
+            let result = EvaluationResultWithDetails {
+                variation: Some(value),
+                action: None,
+                evaluation_details: Arc::new(builder.build()),
+            };
+            return (result, None);
+        }
+        if snapshot.stale {
+            let mut builder = new_details_builder();
+            EvalAssignmentVisitor::on_result(
+                &mut builder,
+                &Err(EvaluationFailure::ConfigurationStale),
+            );
+            let result = EvaluationResultWithDetails {
+                variation: None,
+                action: None,
+                evaluation_details: Arc::new(builder.build()),
+            };
+            return (result, None);
+        }
+        get_assignment_details(
+            config,
+            flag_key,
+            subject_key,
+            subject_attributes,
+            expected_type,
+            now,
         )
     }
+}
+
+fn default_bandit_result(default_variation: &Str) -> BanditResult {
+    BanditResult {
+        variation: default_variation.clone(),
+        action: None,
+        assignment_event: None,
+        bandit_event: None,
+    }
+}
+
+/// Returns keys of all enabled flags that compiled successfully, followed by keys of overridden
+/// flags that are missing from configuration.
+fn evaluated_flag_keys<'a>(
+    config: Option<&'a Configuration>,
+    overrides: &'a FlagOverrides,
+) -> impl Iterator<Item = &'a Str> {
+    let enabled_flags = config
+        .into_iter()
+        .flat_map(|config| config.flags.compiled.flags.iter())
+        .filter(|(_, flag)| flag.is_ok())
+        .map(|(key, _)| key);
+    let is_enabled = move |key: &str| {
+        config.is_some_and(|config| {
+            config
+                .flags
+                .compiled
+                .flags
+                .get(key)
+                .is_some_and(|flag| flag.is_ok())
+        })
+    };
+    let overridden_flags = overrides.flags.keys().filter(move |key| !is_enabled(key));
+    enabled_flags.chain(overridden_flags)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use chrono::Utc;
+
+    use crate::{
+        assignment_cache::AssignmentCache,
+        configuration_builder::{
+            AllocationBuilder, ConfigurationBuilder, FlagBuilder, SplitBuilder,
+        },
+        configuration_store::ConfigurationStore,
+        eval::{eval_details::FlagEvaluationCode, FlagOverride, FlagOverrides},
+        ufc::{AssignmentValue, VariationType},
+        SdkMetadata, Str,
+    };
+
+    use super::{Evaluator, EvaluatorConfig, StaleConfigurationPolicy};
+
+    #[test]
+    fn get_all_assignments_evaluates_enabled_flags() {
+        let configuration = ConfigurationBuilder::new()
+            .flag(
+                FlagBuilder::new("string-flag", VariationType::String)
+                    .variation("a", "a")
+                    .allocation(AllocationBuilder::new("all").split(SplitBuilder::new("a"))),
+            )
+            .flag(
+                FlagBuilder::new("integer-flag", VariationType::Integer)
+                    .variation("one", 1)
+                    .allocation(AllocationBuilder::new("all").split(SplitBuilder::new("one"))),
+            )
+            .flag(FlagBuilder::new("no-allocations", VariationType::Boolean))
+            .flag(
+                FlagBuilder::new("disabled", VariationType::Boolean)
+                    .enabled(false)
+                    .variation("on", true)
+                    .allocation(AllocationBuilder::new("all").split(SplitBuilder::new("on"))),
+            )
+            .build()
+            .unwrap();
+        let store = Arc::new(ConfigurationStore::new());
+        store.set_configuration(Arc::new(configuration));
+        let evaluator = Evaluator::new(EvaluatorConfig {
+            configuration_store: store,
+            sdk_metadata: SdkMetadata {
+                name: "test",
+                version: "0.1.0",
+            },
+            assignment_cache: None,
+            max_configuration_age: None,
+            stale_configuration_policy: Default::default(),
+        });
+        let mut overrides = FlagOverrides::new();
+        overrides.add("integer-flag", FlagOverride::new(42));
+        evaluator.set_overrides(overrides);
+
+        let subject_key = Str::from("subject");
+        let assignments = evaluator.get_all_assignments(&subject_key, &Arc::default());
+        let mut keys = assignments.keys().map(|it| it.as_str()).collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(keys, ["integer-flag", "string-flag"]);
+        assert_eq!(
+            assignments["string-flag"].value,
+            AssignmentValue::String("a".into())
+        );
+        assert!(assignments["string-flag"].event.is_some());
+        assert_eq!(
+            assignments["integer-flag"].value,
+            AssignmentValue::Integer(42)
+        );
+
+        let details = evaluator.get_all_assignments_details(&subject_key, &Arc::default());
+        let mut keys = details.keys().map(|it| it.as_str()).collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(keys, ["integer-flag", "no-allocations", "string-flag"]);
+        assert_eq!(
+            details["no-allocations"]
+                .0
+                .evaluation_details
+                .flag_evaluation_code,
+            Some(FlagEvaluationCode::DefaultAllocationNull)
+        );
+        assert_eq!(
+            details["integer-flag"]
+                .0
+                .evaluation_details
+                .flag_evaluation_code,
+            Some(FlagEvaluationCode::LocalOverride)
+        );
+    }
+
+    #[test]
+    fn get_all_assignments_includes_override_only_flags() {
+        let configuration = ConfigurationBuilder::new()
+            .flag(
+                FlagBuilder::new("string-flag", VariationType::String)
+                    .variation("a", "a")
+                    .allocation(AllocationBuilder::new("all").split(SplitBuilder::new("a"))),
+            )
+            .build()
+            .unwrap();
+        let store = Arc::new(ConfigurationStore::new());
+        store.set_configuration(Arc::new(configuration));
+        let evaluator = Evaluator::new(EvaluatorConfig {
+            configuration_store: store,
+            sdk_metadata: SdkMetadata {
+                name: "test",
+                version: "0.1.0",
+            },
+            assignment_cache: None,
+            max_configuration_age: None,
+            stale_configuration_policy: Default::default(),
+        });
+        let mut overrides = FlagOverrides::new();
+        overrides.add("local-flag", FlagOverride::new(true));
+        evaluator.set_overrides(overrides);
+
+        let subject_key = Str::from("subject");
+        let assignments = evaluator.get_all_assignments(&subject_key, &Arc::default());
+        let mut keys = assignments.keys().map(|it| it.as_str()).collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(keys, ["local-flag", "string-flag"]);
+        assert_eq!(
+            assignments["local-flag"].value,
+            AssignmentValue::Boolean(true)
+        );
+        assert!(assignments["local-flag"].event.is_none());
+
+        let details = evaluator.get_all_assignments_details(&subject_key, &Arc::default());
+        let mut keys = details.keys().map(|it| it.as_str()).collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(keys, ["local-flag", "string-flag"]);
+        assert_eq!(
+            details["local-flag"]
+                .0
+                .evaluation_details
+                .flag_evaluation_code,
+            Some(FlagEvaluationCode::LocalOverride)
+        );
+    }
+
+    #[test]
+    fn assignment_cache_drops_duplicate_events() {
+        let configuration = ConfigurationBuilder::new()
+            .flag(
+                FlagBuilder::new("flag", VariationType::String)
+                    .variation("a", "a")
+                    .allocation(AllocationBuilder::new("all").split(SplitBuilder::new("a"))),
+            )
+            .build()
+            .unwrap();
+        let store = Arc::new(ConfigurationStore::new());
+        store.set_configuration(Arc::new(configuration));
+        let evaluator = Evaluator::new(EvaluatorConfig {
+            configuration_store: store,
+            sdk_metadata: SdkMetadata {
+                name: "test",
+                version: "0.1.0",
+            },
+            assignment_cache: Some(Arc::new(AssignmentCache::new(100))),
+            max_configuration_age: None,
+            stale_configuration_policy: Default::default(),
+        });
+
+        let get_event = |subject_key: &str| {
+            evaluator
+                .get_assignment("flag", &subject_key.into(), &Arc::default(), None)
+                .unwrap()
+                .unwrap()
+                .event
+        };
+
+        assert!(get_event("alice").is_some());
+        assert!(get_event("alice").is_none());
+        assert!(get_event("bob").is_some());
+
+        let (_, event) =
+            evaluator.get_assignment_details("flag", &"alice".into(), &Arc::default(), None);
+        assert!(event.is_none());
+    }
+
+    fn stale_evaluator(policy: StaleConfigurationPolicy) -> Evaluator {
+        let mut configuration = ConfigurationBuilder::new()
+            .flag(
+                FlagBuilder::new("flag", VariationType::String)
+                    .variation("a", "a")
+                    .allocation(AllocationBuilder::new("all").split(SplitBuilder::new("a"))),
+            )
+            .build()
+            .unwrap();
+        configuration.fetched_at = Utc::now() - chrono::Duration::hours(2);
+        let store = Arc::new(ConfigurationStore::new());
+        store.set_configuration(Arc::new(configuration));
+        Evaluator::new(EvaluatorConfig {
+            configuration_store: store,
+            sdk_metadata: SdkMetadata {
+                name: "test",
+                version: "0.1.0",
+            },
+            assignment_cache: None,
+            max_configuration_age: Some(Duration::from_secs(3600)),
+            stale_configuration_policy: policy,
+        })
+    }
+
+    #[test]
+    fn stale_configuration_returns_defaults() {
+        let evaluator = stale_evaluator(StaleConfigurationPolicy::ReturnDefault);
+        let subject_key = Str::from("subject");
+
+        let assignment = evaluator
+            .get_assignment("flag", &subject_key, &Arc::default(), None)
+            .unwrap();
+        assert!(assignment.is_none());
+
+        let (result, event) =
+            evaluator.get_assignment_details("flag", &subject_key, &Arc::default(), None);
+        assert!(result.variation.is_none());
+        assert!(event.is_none());
+        assert_eq!(
+            result.evaluation_details.flag_evaluation_code,
+            Some(FlagEvaluationCode::ConfigurationStale)
+        );
+        assert!(result.evaluation_details.config_fetched_at.is_some());
+
+        assert!(evaluator
+            .get_all_assignments(&subject_key, &Arc::default())
+            .is_empty());
+
+        // Local overrides don't depend on configuration, so they still apply.
+        let mut overrides = FlagOverrides::new();
+        overrides.add("flag", FlagOverride::new("overridden"));
+        evaluator.set_overrides(overrides);
+        let assignment = evaluator
+            .get_assignment("flag", &subject_key, &Arc::default(), None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            assignment.value,
+            AssignmentValue::String("overridden".into())
+        );
+    }
+
+    #[test]
+    fn confirmed_configuration_is_not_stale() {
+        let evaluator = stale_evaluator(StaleConfigurationPolicy::ReturnDefault);
+        // Server replied that configuration has not changed.
+        evaluator.config.configuration_store.confirm_configuration();
+
+        let assignment = evaluator
+            .get_assignment("flag", &"subject".into(), &Arc::default(), None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(assignment.value, AssignmentValue::String("a".into()));
+    }
+
+    #[test]
+    fn stale_configuration_warning_is_rate_limited() {
+        let evaluator = stale_evaluator(StaleConfigurationPolicy::Warn);
+        let now = Utc::now();
+
+        assert!(evaluator.should_warn_stale(now));
+        assert!(!evaluator.should_warn_stale(now));
+        assert!(!evaluator.should_warn_stale(now + chrono::Duration::seconds(30)));
+        assert!(evaluator.should_warn_stale(now + chrono::Duration::minutes(2)));
+    }
+
+    #[test]
+    fn stale_configuration_calls_callback() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let evaluator = stale_evaluator(StaleConfigurationPolicy::Callback(Arc::new({
+            let calls = calls.clone();
+            move |_configuration, age| calls.lock().unwrap().push(age)
+        })));
+
+        let assignment = evaluator
+            .get_assignment("flag", &"subject".into(), &Arc::default(), None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(assignment.value, AssignmentValue::String("a".into()));
 
-    fn get_configuration(&self) -> Option<Arc<Configuration>> {
-        self.config.configuration_store.get_configuration()
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0] > Duration::from_secs(3600));
     }
 }
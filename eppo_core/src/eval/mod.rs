@@ -5,6 +5,7 @@ mod eval_precomputed;
 mod eval_rules;
 mod eval_visitor;
 mod evaluator;
+mod overrides;
 mod subject;
 
 pub mod eval_details;
@@ -12,4 +13,7 @@ pub mod eval_details;
 pub use eval_assignment::{get_assignment, get_assignment_details};
 pub use eval_bandits::{get_bandit_action, get_bandit_action_details, BanditResult};
 pub use eval_precomputed::get_precomputed_configuration;
-pub use evaluator::{Evaluator, EvaluatorConfig};
+pub use evaluator::{
+    Evaluator, EvaluatorConfig, StaleConfigurationCallback, StaleConfigurationPolicy,
+};
+pub use overrides::{FlagOverride, FlagOverrides};
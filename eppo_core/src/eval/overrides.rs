@@ -0,0 +1,481 @@
+//! Local flag overrides.
+//!
+//! Overrides are consulted by [`Evaluator`](super::Evaluator) before evaluating a flag against the
+//! [`Configuration`]. They are useful to force a value during development, QA, or incident
+//! response without touching the remote configuration.
+//!
+//! # File format
+//!
+//! Overrides can be loaded from JSON or, with `yaml` feature enabled, YAML. The top-level object
+//! maps flag keys to an ordered list of overrides. The first override that matches the subject
+//! wins:
+//!
+//! ```yaml
+//! flags:
+//!   new-checkout:
+//!     # Override for a single subject.
+//!     - subjectKey: alice
+//!       value: true
+//!     # Override for all subjects with matching attributes.
+//!     - attributes:
+//!         country: US
+//!       value: false
+//!     # Override for everyone else.
+//!     - value: true
+//! ```
+use std::{path::Path, sync::Arc};
+
+use arc_swap::{ArcSwap, Guard};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    hashmap::HashMap,
+    ufc::{AssignmentValue, VariationType},
+    AttributeValue, Attributes, Configuration, Error, Result, Str,
+};
+
+/// A set of local flag overrides.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlagOverrides {
+    /// Overrides for each flag key. Overrides are checked in order and the first matching one is
+    /// used.
+    #[serde(default)]
+    pub flags: HashMap<Str, Vec<FlagOverride>>,
+}
+
+/// A single override of a flag value.
+///
+/// An override with neither `subject_key` nor `attributes` applies to all subjects.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlagOverride {
+    /// If set, the override only applies to the subject with this key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject_key: Option<Str>,
+    /// If non-empty, the override only applies to subjects whose attributes are equal to all of
+    /// the specified values. Values are compared the same way as in `ONE_OF` rules.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub attributes: HashMap<Str, AttributeValue>,
+    /// Value to return. It must be compatible with the flag's variation type: booleans for
+    /// `BOOLEAN`, integer numbers for `INTEGER`, numbers for `NUMERIC`, strings for `STRING`, and
+    /// arbitrary JSON values for `JSON`.
+    pub value: serde_json::Value,
+}
+
+impl FlagOverrides {
+    /// Create an empty set of overrides.
+    pub fn new() -> FlagOverrides {
+        FlagOverrides::default()
+    }
+
+    /// Parse overrides from JSON.
+    pub fn from_json(json: &[u8]) -> Result<FlagOverrides> {
+        serde_json::from_slice(json).map_err(|err| Error::InvalidFlagOverrides(err.to_string()))
+    }
+
+    /// Parse overrides from YAML.
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(yaml: &[u8]) -> Result<FlagOverrides> {
+        serde_yaml::from_slice(yaml).map_err(|err| Error::InvalidFlagOverrides(err.to_string()))
+    }
+
+    /// Load overrides from a file. Files with `.yaml` or `.yml` extension are parsed as YAML (this
+    /// requires `yaml` feature), all other files are parsed as JSON.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<FlagOverrides> {
+        let path = path.as_ref();
+        let contents = std::fs::read(path)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            #[cfg(feature = "yaml")]
+            Some("yaml" | "yml") => FlagOverrides::from_yaml(&contents),
+            #[cfg(not(feature = "yaml"))]
+            Some("yaml" | "yml") => Err(Error::InvalidFlagOverrides(
+                "YAML overrides are not supported, enable `yaml` feature of eppo_core".to_owned(),
+            )),
+            _ => FlagOverrides::from_json(&contents),
+        }
+    }
+
+    /// Add an override for the flag. The override is checked after all previously added overrides
+    /// for the same flag.
+    pub fn add(&mut self, flag_key: impl Into<Str>, flag_override: FlagOverride) -> &mut Self {
+        self.flags
+            .entry(flag_key.into())
+            .or_default()
+            .push(flag_override);
+        self
+    }
+
+    /// Remove all overrides for the flag. Returns `true` if the flag had any overrides.
+    pub fn remove(&mut self, flag_key: &str) -> bool {
+        self.flags.remove(flag_key).is_some()
+    }
+
+    /// Returns `true` if there are no overrides.
+    pub fn is_empty(&self) -> bool {
+        self.flags.values().all(|it| it.is_empty())
+    }
+
+    /// Find an override value for the given subject.
+    ///
+    /// The value is converted to the flag's variation type (as found in `configuration`), falling
+    /// back to `expected_type` if the flag is unknown, and to the JSON type of the value if neither
+    /// is known. Returns `None` if no override matches or the override value has incompatible
+    /// type.
+    pub(crate) fn get_override(
+        &self,
+        configuration: Option<&Configuration>,
+        flag_key: &str,
+        subject_key: &Str,
+        subject_attributes: &Attributes,
+        expected_type: Option<VariationType>,
+    ) -> Option<AssignmentValue> {
+        let flag_override = self
+            .flags
+            .get(flag_key)?
+            .iter()
+            .find(|it| it.matches(subject_key, subject_attributes))?;
+
+        let flag_type = configuration
+            .and_then(|config| config.flags.compiled.flags.get(flag_key))
+            .and_then(|flag| flag.as_ref().ok())
+            .map(|flag| flag.variation_type);
+
+        let ty = match (flag_type, expected_type) {
+            (Some(found), Some(expected)) if found != expected => {
+                // Let regular evaluation report the type mismatch.
+                return None;
+            }
+            (Some(ty), _) | (None, Some(ty)) => ty,
+            (None, None) => infer_type(&flag_override.value),
+        };
+
+        let value = to_assignment_value(&flag_override.value, ty);
+        if value.is_none() {
+            log::warn!(target: "eppo",
+                       flag_key,
+                       subject_key;
+                       "ignoring flag override because its value is not compatible with {ty:?}");
+        }
+        value
+    }
+}
+
+impl FlagOverride {
+    /// Create an override that applies to all subjects.
+    pub fn new(value: impl Into<serde_json::Value>) -> FlagOverride {
+        FlagOverride {
+            subject_key: None,
+            attributes: HashMap::default(),
+            value: value.into(),
+        }
+    }
+
+    /// Restrict the override to the subject with the given key.
+    pub fn with_subject_key(mut self, subject_key: impl Into<Str>) -> FlagOverride {
+        self.subject_key = Some(subject_key.into());
+        self
+    }
+
+    /// Restrict the override to subjects with the given attribute value.
+    pub fn with_attribute(
+        mut self,
+        name: impl Into<Str>,
+        value: impl Into<AttributeValue>,
+    ) -> FlagOverride {
+        self.attributes.insert(name.into(), value.into());
+        self
+    }
+
+    fn matches(&self, subject_key: &Str, subject_attributes: &Attributes) -> bool {
+        if self
+            .subject_key
+            .as_ref()
+            .is_some_and(|key| key != subject_key)
+        {
+            return false;
+        }
+
+        self.attributes
+            .iter()
+            .all(|(name, expected)| match subject_attributes.get(name) {
+                None => expected.is_null(),
+                Some(actual) if actual.is_null() || expected.is_null() => {
+                    actual.is_null() && expected.is_null()
+                }
+                Some(actual) => match (actual.coerce_to_string(), expected.coerce_to_string()) {
+                    (Some(actual), Some(expected)) => actual == expected,
+                    // Lists and objects have no string representation, so they are compared
+                    // structurally.
+                    _ => actual == expected,
+                },
+            })
+    }
+}
+
+fn infer_type(value: &serde_json::Value) -> VariationType {
+    match value {
+        serde_json::Value::Bool(_) => VariationType::Boolean,
+        serde_json::Value::Number(n) if n.is_i64() => VariationType::Integer,
+        serde_json::Value::Number(_) => VariationType::Numeric,
+        serde_json::Value::String(_) => VariationType::String,
+        serde_json::Value::Null | serde_json::Value::Array(_) | serde_json::Value::Object(_) => {
+            VariationType::Json
+        }
+    }
+}
+
+fn to_assignment_value(value: &serde_json::Value, ty: VariationType) -> Option<AssignmentValue> {
+    Some(match ty {
+        VariationType::String => AssignmentValue::String(value.as_str()?.into()),
+        VariationType::Integer => AssignmentValue::Integer(value.as_i64()?),
+        VariationType::Numeric => AssignmentValue::Numeric(value.as_f64()?),
+        VariationType::Boolean => AssignmentValue::Boolean(value.as_bool()?),
+        VariationType::Json => AssignmentValue::from_json(value.clone()).ok()?,
+    })
+}
+
+/// Storage for overrides that can be swapped at runtime.
+#[derive(Debug, Default)]
+pub(crate) struct FlagOverridesStore {
+    overrides: ArcSwap<FlagOverrides>,
+}
+
+impl FlagOverridesStore {
+    pub(crate) fn get(&self) -> Arc<FlagOverrides> {
+        self.overrides.load_full()
+    }
+
+    /// Lock-free short-lived access to overrides for evaluation hot path.
+    pub(crate) fn load(&self) -> Guard<Arc<FlagOverrides>> {
+        self.overrides.load()
+    }
+
+    pub(crate) fn set(&self, overrides: Arc<FlagOverrides>) {
+        self.overrides.store(overrides);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        configuration_store::ConfigurationStore,
+        eval::{eval_details::FlagEvaluationCode, Evaluator, EvaluatorConfig},
+        ufc::{AssignmentValue, UniversalFlagConfig, VariationType},
+        Attributes, Configuration, SdkMetadata, Str,
+    };
+
+    use super::{FlagOverride, FlagOverrides};
+
+    fn evaluator() -> Evaluator {
+        let ufc = UniversalFlagConfig::from_json(
+            SdkMetadata {
+                name: "test",
+                version: "0.1.0",
+            },
+            br#"{
+              "createdAt": "2024-07-18T00:00:00Z",
+              "environment": {"name": "Test"},
+              "flags": {
+                "flag": {
+                  "key": "flag",
+                  "enabled": true,
+                  "variationType": "STRING",
+                  "variations": {"control": {"key": "control", "value": "control"}},
+                  "allocations": [{
+                    "key": "everyone",
+                    "splits": [{"variationKey": "control", "shards": []}],
+                    "doLog": false
+                  }],
+                  "totalShards": 10000
+                }
+              }
+            }"#
+            .to_vec(),
+        )
+        .unwrap();
+        let store = Arc::new(ConfigurationStore::new());
+        store.set_configuration(Arc::new(Configuration::from_server_response(ufc, None)));
+        Evaluator::new(EvaluatorConfig {
+            configuration_store: store,
+            sdk_metadata: SdkMetadata {
+                name: "test",
+                version: "0.1.0",
+            },
+            assignment_cache: None,
+            max_configuration_age: None,
+            stale_configuration_policy: Default::default(),
+        })
+    }
+
+    fn get_string(evaluator: &Evaluator, subject_key: &str, attributes: Attributes) -> Option<Str> {
+        evaluator
+            .get_assignment(
+                "flag",
+                &subject_key.into(),
+                &Arc::new(attributes),
+                Some(VariationType::String),
+            )
+            .unwrap()
+            .map(|it| it.value.to_string().unwrap())
+    }
+
+    #[test]
+    fn overrides_are_matched_in_order() {
+        let evaluator = evaluator();
+        let mut overrides = FlagOverrides::new();
+        overrides
+            .add("flag", FlagOverride::new("alice").with_subject_key("alice"))
+            .add(
+                "flag",
+                FlagOverride::new("us").with_attribute("country", "US"),
+            );
+        evaluator.set_overrides(overrides);
+
+        assert_eq!(
+            get_string(
+                &evaluator,
+                "alice",
+                [("country".into(), "US".into())].into_iter().collect()
+            ),
+            Some("alice".into())
+        );
+        assert_eq!(
+            get_string(
+                &evaluator,
+                "bob",
+                [("country".into(), "US".into())].into_iter().collect()
+            ),
+            Some("us".into())
+        );
+        assert_eq!(
+            get_string(&evaluator, "bob", Attributes::default()),
+            Some("control".into())
+        );
+
+        evaluator.set_overrides(FlagOverrides::new());
+        assert_eq!(
+            get_string(&evaluator, "alice", Attributes::default()),
+            Some("control".into())
+        );
+    }
+
+    #[test]
+    fn overrides_match_list_attributes_structurally() {
+        let evaluator = evaluator();
+        let mut overrides = FlagOverrides::new();
+        overrides.add(
+            "flag",
+            FlagOverride::new("admins").with_attribute("roles", vec!["admin"]),
+        );
+        evaluator.set_overrides(overrides);
+
+        let roles = |roles: Vec<&str>| [("roles".into(), roles.into())].into_iter().collect();
+        assert_eq!(
+            get_string(&evaluator, "alice", roles(vec!["admin"])),
+            Some("admins".into())
+        );
+        assert_eq!(
+            get_string(&evaluator, "bob", roles(vec!["viewer"])),
+            Some("control".into())
+        );
+        assert_eq!(
+            get_string(
+                &evaluator,
+                "carol",
+                [("roles".into(), "admin".into())].into_iter().collect()
+            ),
+            Some("control".into())
+        );
+    }
+
+    #[test]
+    fn overrides_are_type_checked() {
+        let evaluator = evaluator();
+        let mut overrides = FlagOverrides::new();
+        overrides.add("flag", FlagOverride::new(42));
+        evaluator.set_overrides(overrides);
+
+        // Integer value is not compatible with STRING flag, so override is ignored.
+        assert_eq!(
+            get_string(&evaluator, "alice", Attributes::default()),
+            Some("control".into())
+        );
+    }
+
+    #[test]
+    fn override_is_reported_in_details() {
+        let evaluator = evaluator();
+        evaluator.set_overrides(
+            FlagOverrides::from_json(
+                br#"{"flags":{"flag":[{"value":"overridden"}],"unknown-flag":[{"value":42}]}}"#,
+            )
+            .unwrap(),
+        );
+
+        let (result, event) = evaluator.get_assignment_details(
+            "flag",
+            &"alice".into(),
+            &Arc::default(),
+            Some(VariationType::String),
+        );
+        assert_eq!(
+            result.variation,
+            Some(AssignmentValue::String("overridden".into()))
+        );
+        assert!(event.is_none());
+        assert_eq!(
+            result.evaluation_details.flag_evaluation_code,
+            Some(FlagEvaluationCode::LocalOverride)
+        );
+
+        // Flags missing from configuration can be overridden too.
+        let result = evaluator
+            .get_assignment("unknown-flag", &"alice".into(), &Arc::default(), None)
+            .unwrap();
+        assert_eq!(
+            result.map(|it| it.value),
+            Some(AssignmentValue::Integer(42))
+        );
+    }
+
+    #[test]
+    fn parse_json_overrides() {
+        let overrides = FlagOverrides::from_json(
+            br#"{"flags":{"flag":[{"subjectKey":"alice","attributes":{"age":30},"value":{"a":1}}]}}"#,
+        )
+        .unwrap();
+        let mut expected = FlagOverrides::new();
+        expected.add(
+            "flag",
+            FlagOverride::new(serde_json::json!({"a": 1}))
+                .with_subject_key("alice")
+                .with_attribute("age", 30.0),
+        );
+        assert_eq!(overrides, expected);
+
+        assert!(FlagOverrides::from_json(br#"{"flags":{"flag":[{}]}}"#).is_err());
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn parse_yaml_overrides() {
+        let overrides = FlagOverrides::from_yaml(
+            b"
+flags:
+  flag:
+    - subjectKey: alice
+      value: overridden
+",
+        )
+        .unwrap();
+        let mut expected = FlagOverrides::new();
+        expected.add(
+            "flag",
+            FlagOverride::new("overridden").with_subject_key("alice"),
+        );
+        assert_eq!(overrides, expected);
+    }
+}
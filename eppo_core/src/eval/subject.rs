@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
 use crate::Str;
 
@@ -30,6 +30,16 @@ impl Subject {
 
     /// Get subject attribute.
     ///
+    /// Attribute `name` is resolved as follows:
+    /// 1. Top-level attribute with exact `name` takes precedence (so flat attributes with dots in
+    ///    their names keep working).
+    /// 2. If `name` starts with `/`, it is a JSON pointer (RFC 6901) into nested object
+    ///    attributes, e.g. `/device/os/version`. `~1` and `~0` escape `/` and `~` respectively.
+    /// 3. If `name` contains `.`, it is a dotted path into nested object attributes, e.g.
+    ///    `device.os.version`. Use a JSON pointer to address keys that contain dots.
+    ///
+    /// Paths only descend into object attributes; list items cannot be addressed.
+    ///
     /// If attribute `name` is `"id"` and there's no explicit attribute with this name, return
     /// subject key instead. This is a standard Eppo behavior when evaluation rules.
     pub fn get_attribute(&self, name: &str) -> Option<&AttributeValue> {
@@ -38,10 +48,34 @@ impl Subject {
             return value;
         }
 
+        if let Some(pointer) = name.strip_prefix('/') {
+            return self.get_path(pointer.split('/').map(|segment| {
+                if segment.contains('~') {
+                    Cow::Owned(segment.replace("~1", "/").replace("~0", "~"))
+                } else {
+                    Cow::Borrowed(segment)
+                }
+            }));
+        }
+
+        if name.contains('.') {
+            return self.get_path(name.split('.').map(Cow::Borrowed));
+        }
+
         if name == "id" {
             return Some(&self.key);
         }
 
         None
     }
+
+    fn get_path<'a>(
+        &self,
+        mut segments: impl Iterator<Item = Cow<'a, str>>,
+    ) -> Option<&AttributeValue> {
+        let first = segments.next()?;
+        segments.try_fold(self.attributes.get(first.as_ref())?, |value, segment| {
+            value.get(&segment)
+        })
+    }
 }
@@ -53,12 +53,20 @@
 #[cfg(feature = "ahash")]
 pub use ahash;
 
+pub mod assignment_cache;
 pub mod attributes;
 pub mod background;
 pub mod bandits;
+pub mod configuration_builder;
+pub mod configuration_cache;
+pub mod configuration_diagnostics;
+pub mod configuration_diff;
 pub mod configuration_fetcher;
+pub mod configuration_file;
 pub mod configuration_poller;
+pub mod configuration_source;
 pub mod configuration_store;
+pub mod configuration_validator;
 pub mod eval;
 #[cfg(feature = "event_ingestion")]
 pub mod event_ingestion;
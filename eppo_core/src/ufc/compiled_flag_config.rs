@@ -52,6 +52,7 @@ pub(crate) struct Allocation {
     pub end_at: Option<Timestamp>,
     pub rules: Box<[RuleWire]>,
     pub splits: Box<[Split]>,
+    pub do_log: bool,
 }
 
 #[derive(Debug)]
@@ -67,6 +68,12 @@ pub(crate) struct Split {
 pub struct Shard {
     #[serde(skip)]
     pub(crate) sharder: PreSaltedSharder,
+    /// Salt and total number of shards the sharder was created with. Kept to compare
+    /// configurations.
+    #[serde(skip)]
+    pub(crate) salt: Str,
+    #[serde(skip)]
+    pub(crate) total_shards: u32,
     pub ranges: Box<[ShardRange]>,
 }
 
@@ -198,6 +205,7 @@ fn compile_allocation(
         end_at: allocation.end_at,
         rules: allocation.rules,
         splits,
+        do_log: allocation.do_log,
     }
 }
 
@@ -259,6 +267,8 @@ fn compile_shard(shard: ShardWire, total_shards: u32) -> Option<Shard> {
     } else {
         Some(Shard {
             sharder: PreSaltedSharder::new(&[shard.salt.as_bytes(), b"-"], total_shards),
+            salt: shard.salt.into(),
+            total_shards,
             ranges: shard.ranges,
         })
     }
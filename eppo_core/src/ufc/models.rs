@@ -1,7 +1,8 @@
 use crate::hashmap::HashMap;
+use chrono::{DateTime, Utc};
 use derive_more::From;
 use regex::Regex;
-use semver::Version;
+use semver::{Version, VersionReq};
 use serde::{Deserialize, Serialize};
 
 use crate::{Error, EvaluationError, Str};
@@ -230,9 +231,47 @@ pub(crate) enum ConditionCheck {
         expected_membership: bool,
         values: Box<[Box<str>]>,
     },
+    ListMembership {
+        operator: ListOperator,
+        values: Box<[Box<str>]>,
+    },
     Null {
         expected_null: bool,
     },
+    /// Attribute is an RFC 3339 datetime within `period` before evaluation time.
+    WithinLast {
+        /// `period` as specified in condition value, for round-tripping.
+        days: f64,
+        period: chrono::TimeDelta,
+    },
+    Substring {
+        operator: SubstringOperator,
+        expected_match: bool,
+        ignore_case: bool,
+        value: Str,
+        /// `value` prepared for matching: lowercased if `ignore_case` is set.
+        needle: Box<str>,
+    },
+    /// Attribute is a version matching any of `requirements`.
+    VersionMatches {
+        /// `requirements` as specified in condition value, for round-tripping.
+        value: Str,
+        requirements: Box<[VersionReq]>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum ListOperator {
+    Any,
+    All,
+    None,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum SubstringOperator {
+    Contains,
+    StartsWith,
+    EndsWith,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -241,6 +280,10 @@ pub(crate) enum ComparisonOperator {
     Gt,
     Lte,
     Lt,
+    /// Same as `Lt` but only produced for datetimes. Kept separate so `BEFORE` round-trips.
+    Before,
+    /// Same as `Gt` but only produced for datetimes. Kept separate so `AFTER` round-trips.
+    After,
 }
 
 impl From<ComparisonOperator> for ConditionOperator {
@@ -250,6 +293,8 @@ impl From<ComparisonOperator> for ConditionOperator {
             ComparisonOperator::Gt => ConditionOperator::Gt,
             ComparisonOperator::Lte => ConditionOperator::Lte,
             ComparisonOperator::Lt => ConditionOperator::Lt,
+            ComparisonOperator::Before => ConditionOperator::Before,
+            ComparisonOperator::After => ConditionOperator::After,
         }
     }
 }
@@ -258,15 +303,23 @@ impl From<ComparisonOperator> for ConditionOperator {
 pub(crate) enum Comparand {
     Version(Version),
     Number(f64),
+    /// Attribute must be an RFC 3339 datetime string. `raw` is the original condition value, kept
+    /// to serialize the condition back unchanged.
+    #[from(ignore)]
+    DateTime {
+        datetime: Timestamp,
+        raw: Str,
+    },
 }
 
 impl From<Comparand> for ConditionValue {
     fn from(value: Comparand) -> ConditionValue {
-        let s = match value {
-            Comparand::Version(v) => v.to_string(),
-            Comparand::Number(n) => n.to_string(),
+        let s: Str = match value {
+            Comparand::Version(v) => v.to_string().into(),
+            Comparand::Number(n) => n.to_string().into(),
+            Comparand::DateTime { raw, .. } => raw,
         };
-        ConditionValue::Single(ValueWire::String(s.into()))
+        ConditionValue::Single(ValueWire::String(s))
     }
 }
 
@@ -309,9 +362,53 @@ impl From<Condition> for ConditionWire {
                 },
                 ConditionValue::Multiple(values),
             ),
+            ConditionCheck::ListMembership { operator, values } => (
+                match operator {
+                    ListOperator::Any => ConditionOperator::ContainsAny,
+                    ListOperator::All => ConditionOperator::ContainsAll,
+                    ListOperator::None => ConditionOperator::ContainsNone,
+                },
+                ConditionValue::Multiple(values),
+            ),
             ConditionCheck::Null { expected_null } => {
                 (ConditionOperator::IsNull, expected_null.into())
             }
+            ConditionCheck::WithinLast { days, period: _ } => (
+                ConditionOperator::WithinLastDays,
+                ConditionValue::Single(ValueWire::Number(days)),
+            ),
+            ConditionCheck::Substring {
+                operator,
+                expected_match,
+                ignore_case,
+                value,
+                needle: _,
+            } => {
+                let operator = match (operator, expected_match, ignore_case) {
+                    (SubstringOperator::Contains, true, false) => ConditionOperator::Contains,
+                    (SubstringOperator::Contains, false, false) => ConditionOperator::NotContains,
+                    (SubstringOperator::StartsWith, _, false) => ConditionOperator::StartsWith,
+                    (SubstringOperator::EndsWith, _, false) => ConditionOperator::EndsWith,
+                    (SubstringOperator::Contains, true, true) => {
+                        ConditionOperator::ContainsIgnoreCase
+                    }
+                    (SubstringOperator::Contains, false, true) => {
+                        ConditionOperator::NotContainsIgnoreCase
+                    }
+                    (SubstringOperator::StartsWith, _, true) => {
+                        ConditionOperator::StartsWithIgnoreCase
+                    }
+                    (SubstringOperator::EndsWith, _, true) => ConditionOperator::EndsWithIgnoreCase,
+                };
+                (operator, ConditionValue::Single(ValueWire::String(value)))
+            }
+            ConditionCheck::VersionMatches {
+                value,
+                requirements: _,
+            } => (
+                ConditionOperator::SemverMatches,
+                ConditionValue::Single(ValueWire::String(value)),
+            ),
         };
         ConditionWire {
             attribute: condition.attribute,
@@ -331,6 +428,16 @@ impl TryFrom<ConditionWire> for Condition {
     type Error = Error;
 
     fn try_from(condition: ConditionWire) -> Result<Self, Self::Error> {
+        Condition::parse(condition).map_err(|reason| {
+            log::warn!(target: "eppo", "failed to parse condition: {reason}");
+            Error::EvaluationError(EvaluationError::UnexpectedConfigurationParseError)
+        })
+    }
+}
+
+impl Condition {
+    /// Parse condition from its wire format, returning a human-readable reason on failure.
+    pub(crate) fn parse(condition: ConditionWire) -> Result<Condition, String> {
         let attribute = condition.attribute;
         let check = match condition.operator {
             ConditionOperator::Matches | ConditionOperator::NotMatches => {
@@ -339,24 +446,14 @@ impl TryFrom<ConditionWire> for Condition {
                 let regex_string = match condition.value {
                     ConditionValue::Single(ValueWire::String(s)) => s,
                     _ => {
-                        log::warn!(
-                            "failed to parse condition: {:?} condition with non-string condition value",
+                        return Err(format!(
+                            "{:?} condition with non-string condition value",
                             condition.operator
-                        );
-                        return Err(Error::EvaluationError(
-                            EvaluationError::UnexpectedConfigurationParseError,
-                        ));
-                    }
-                };
-                let regex = match Regex::new(&regex_string) {
-                    Ok(regex) => regex,
-                    Err(err) => {
-                        log::warn!("failed to parse condition: failed to compile regex {regex_string:?}: {err:?}");
-                        return Err(Error::EvaluationError(
-                            EvaluationError::UnexpectedConfigurationParseError,
                         ));
                     }
                 };
+                let regex = Regex::new(&regex_string)
+                    .map_err(|err| format!("failed to compile regex {regex_string:?}: {err}"))?;
 
                 ConditionCheck::Regex {
                     expected_match,
@@ -392,26 +489,66 @@ impl TryFrom<ConditionWire> for Condition {
                         ConditionValue::Single(ValueWire::String(s)) => s.parse().ok(),
                         _ => None,
                     };
-                    let Some(condition_value) = condition_value else {
-                        log::warn!("failed to parse condition: comparision value is neither regex, nor number: {:?}", condition.value);
-                        return Err(Error::EvaluationError(
-                            EvaluationError::UnexpectedConfigurationParseError,
-                        ));
+                    let comparand = match (condition_value, parse_datetime(&condition.value)) {
+                        (Some(condition_value), _) => Comparand::Number(condition_value),
+                        (None, Some(datetime)) => datetime,
+                        (None, None) => {
+                            return Err(format!(
+                                "comparison value is neither semver, nor number, nor RFC 3339 datetime: {:?}",
+                                condition.value
+                            ));
+                        }
                     };
                     ConditionCheck::Comparison {
                         operator,
-                        comparand: Comparand::Number(condition_value),
+                        comparand,
                     }
                 }
             }
+            ConditionOperator::Before | ConditionOperator::After => {
+                let operator = if condition.operator == ConditionOperator::Before {
+                    ComparisonOperator::Before
+                } else {
+                    ComparisonOperator::After
+                };
+                let Some(comparand) = parse_datetime(&condition.value) else {
+                    return Err(format!(
+                        "{:?} condition value is not an RFC 3339 datetime: {:?}",
+                        condition.operator, condition.value
+                    ));
+                };
+                ConditionCheck::Comparison {
+                    operator,
+                    comparand,
+                }
+            }
+            ConditionOperator::WithinLastDays => {
+                let days = match &condition.value {
+                    ConditionValue::Single(ValueWire::Number(n)) => Some(*n),
+                    ConditionValue::Single(ValueWire::String(s)) => s.parse().ok(),
+                    _ => None,
+                };
+                let period = days
+                    .filter(|days| days.is_finite() && *days >= 0.0)
+                    .and_then(|days| {
+                        chrono::TimeDelta::try_milliseconds((days * 86_400_000.0) as i64)
+                    });
+                let (Some(days), Some(period)) = (days, period) else {
+                    return Err(format!(
+                        "WITHIN_LAST_DAYS condition value is not a non-negative number of days: {:?}",
+                        condition.value
+                    ));
+                };
+                ConditionCheck::WithinLast { days, period }
+            }
             ConditionOperator::OneOf | ConditionOperator::NotOneOf => {
                 let expected_membership = condition.operator == ConditionOperator::OneOf;
                 let values = match condition.value {
                     ConditionValue::Multiple(v) => v,
                     _ => {
-                        log::warn!("failed to parse condition: membership condition with non-array value: {:?}", condition.value);
-                        return Err(Error::EvaluationError(
-                            EvaluationError::UnexpectedConfigurationParseError,
+                        return Err(format!(
+                            "membership condition with non-array value: {:?}",
+                            condition.value
                         ));
                     }
                 };
@@ -420,21 +557,114 @@ impl TryFrom<ConditionWire> for Condition {
                     values,
                 }
             }
+            ConditionOperator::ContainsAny
+            | ConditionOperator::ContainsAll
+            | ConditionOperator::ContainsNone => {
+                let operator = match condition.operator {
+                    ConditionOperator::ContainsAny => ListOperator::Any,
+                    ConditionOperator::ContainsAll => ListOperator::All,
+                    ConditionOperator::ContainsNone => ListOperator::None,
+                    _ => unreachable!(),
+                };
+                let ConditionValue::Multiple(values) = condition.value else {
+                    return Err(format!(
+                        "{:?} condition with non-array value: {:?}",
+                        condition.operator, condition.value
+                    ));
+                };
+                ConditionCheck::ListMembership { operator, values }
+            }
             ConditionOperator::IsNull => {
                 let ConditionValue::Single(ValueWire::Boolean(expected_null)) = condition.value
                 else {
-                    log::warn!("failed to parse condition: IS_NULL condition with non-boolean condition value");
-                    return Err(Error::EvaluationError(
-                        EvaluationError::UnexpectedConfigurationParseError,
-                    ));
+                    return Err("IS_NULL condition with non-boolean condition value".to_owned());
                 };
                 ConditionCheck::Null { expected_null }
             }
+            ConditionOperator::Contains
+            | ConditionOperator::NotContains
+            | ConditionOperator::StartsWith
+            | ConditionOperator::EndsWith
+            | ConditionOperator::ContainsIgnoreCase
+            | ConditionOperator::NotContainsIgnoreCase
+            | ConditionOperator::StartsWithIgnoreCase
+            | ConditionOperator::EndsWithIgnoreCase => {
+                let (operator, expected_match, ignore_case) = match condition.operator {
+                    ConditionOperator::Contains => (SubstringOperator::Contains, true, false),
+                    ConditionOperator::NotContains => (SubstringOperator::Contains, false, false),
+                    ConditionOperator::StartsWith => (SubstringOperator::StartsWith, true, false),
+                    ConditionOperator::EndsWith => (SubstringOperator::EndsWith, true, false),
+                    ConditionOperator::ContainsIgnoreCase => {
+                        (SubstringOperator::Contains, true, true)
+                    }
+                    ConditionOperator::NotContainsIgnoreCase => {
+                        (SubstringOperator::Contains, false, true)
+                    }
+                    ConditionOperator::StartsWithIgnoreCase => {
+                        (SubstringOperator::StartsWith, true, true)
+                    }
+                    ConditionOperator::EndsWithIgnoreCase => {
+                        (SubstringOperator::EndsWith, true, true)
+                    }
+                    _ => unreachable!(),
+                };
+
+                let ConditionValue::Single(ValueWire::String(value)) = condition.value else {
+                    return Err(format!(
+                        "{:?} condition with non-string condition value",
+                        condition.operator
+                    ));
+                };
+                let needle = if ignore_case {
+                    value.to_lowercase().into()
+                } else {
+                    value.as_str().into()
+                };
+
+                ConditionCheck::Substring {
+                    operator,
+                    expected_match,
+                    ignore_case,
+                    value,
+                    needle,
+                }
+            }
+            ConditionOperator::SemverMatches => {
+                let ConditionValue::Single(ValueWire::String(value)) = condition.value else {
+                    return Err(
+                        "SEMVER_MATCHES condition with non-string condition value".to_owned()
+                    );
+                };
+                let requirements = value
+                    .split("||")
+                    .map(|it| {
+                        VersionReq::parse(it.trim()).map_err(|err| {
+                            format!("failed to parse version requirement {value:?}: {err}")
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+
+                ConditionCheck::VersionMatches {
+                    value,
+                    requirements,
+                }
+            }
         };
         Ok(Condition { attribute, check })
     }
 }
 
+fn parse_datetime(value: &ConditionValue) -> Option<Comparand> {
+    let ConditionValue::Single(ValueWire::String(s)) = value else {
+        return None;
+    };
+    let datetime = DateTime::parse_from_rfc3339(s).ok()?.with_timezone(&Utc);
+    Some(Comparand::DateTime {
+        datetime,
+        raw: s.clone(),
+    })
+}
+
 /// Possible condition types.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -443,14 +673,17 @@ pub(crate) enum ConditionOperator {
     Matches,
     /// Regex does not match. Condition value must be a regex string.
     NotMatches,
-    /// Greater than or equal. Attribute and condition value must either be numbers or semver
-    /// string.
+    /// Greater than or equal. Attribute and condition value must either be numbers, semver
+    /// strings, or RFC 3339 datetime strings.
     Gte,
-    /// Greater than. Attribute and condition value must either be numbers or semver string.
+    /// Greater than. Attribute and condition value must either be numbers, semver strings, or RFC
+    /// 3339 datetime strings.
     Gt,
-    /// Less than or equal. Attribute and condition value must either be numbers or semver string.
+    /// Less than or equal. Attribute and condition value must either be numbers, semver strings,
+    /// or RFC 3339 datetime strings.
     Lte,
-    /// Less than. Attribute and condition value must either be numbers or semver string.
+    /// Less than. Attribute and condition value must either be numbers, semver strings, or RFC
+    /// 3339 datetime strings.
     Lt,
     /// One of values. Condition value must be a list of strings. Match is case-sensitive.
     OneOf,
@@ -459,11 +692,57 @@ pub(crate) enum ConditionOperator {
     /// Null/absent attributes fail this condition automatically. (i.e., `null NOT_ONE_OF ["hello"]`
     /// is `false`)
     NotOneOf,
+    /// Attribute contains any of values. Condition value must be a list of strings. Attribute
+    /// should be a list; other non-null attributes are treated as a single-item list. Match is
+    /// case-sensitive.
+    ContainsAny,
+    /// Attribute contains all of values. Condition value must be a list of strings. Match is
+    /// case-sensitive.
+    ContainsAll,
+    /// Attribute contains none of values. Condition value must be a list of strings. Match is
+    /// case-sensitive.
+    ///
+    /// Null/absent attributes fail this condition automatically.
+    ContainsNone,
     /// Null check.
     ///
     /// Condition value must be a boolean. If it's `true`, this is a null check. If it's `false`,
     /// this is a not null check.
     IsNull,
+    /// Attribute contains substring. Condition value must be a string. Match is case-sensitive.
+    Contains,
+    /// Attribute does not contain substring. Condition value must be a string. Match is
+    /// case-sensitive.
+    ///
+    /// Null/absent attributes fail this condition automatically.
+    NotContains,
+    /// Attribute starts with prefix. Condition value must be a string. Match is case-sensitive.
+    StartsWith,
+    /// Attribute ends with suffix. Condition value must be a string. Match is case-sensitive.
+    EndsWith,
+    /// Case-insensitive version of [`ConditionOperator::Contains`].
+    ContainsIgnoreCase,
+    /// Case-insensitive version of [`ConditionOperator::NotContains`].
+    NotContainsIgnoreCase,
+    /// Case-insensitive version of [`ConditionOperator::StartsWith`].
+    StartsWithIgnoreCase,
+    /// Case-insensitive version of [`ConditionOperator::EndsWith`].
+    EndsWithIgnoreCase,
+    /// Attribute is a datetime before condition value. Attribute and condition value must be RFC
+    /// 3339 datetime strings.
+    Before,
+    /// Attribute is a datetime after condition value. Attribute and condition value must be RFC
+    /// 3339 datetime strings.
+    After,
+    /// Attribute is a datetime within the last N days before evaluation time. Attribute must be an
+    /// RFC 3339 datetime string, condition value must be a non-negative number of days. Datetimes
+    /// in the future do not match.
+    WithinLastDays,
+    /// Attribute is a version matching semver requirement. Condition value must be a string of
+    /// one or more comma-separated requirements (e.g., `">=4.2, <5"` or `"^4.2"`), which may be
+    /// combined with `||` to match any of them. Attribute must be a version string; `v` prefix and
+    /// missing minor/patch components are accepted (e.g., `"v4.2"` is treated as `4.2.0`).
+    SemverMatches,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -534,7 +813,7 @@ pub(crate) struct BanditVariationWire {
 mod tests {
     use std::{fs::File, io::BufReader};
 
-    use super::{TryParse, UniversalFlagConfigWire};
+    use super::{Condition, TryParse, UniversalFlagConfigWire};
 
     #[test]
     fn parse_flags_v1() {
@@ -543,6 +822,70 @@ mod tests {
         let _ufc: UniversalFlagConfigWire = serde_json::from_reader(BufReader::new(f)).unwrap();
     }
 
+    #[test]
+    fn string_and_datetime_conditions_round_trip() {
+        for operator in [
+            "CONTAINS",
+            "NOT_CONTAINS",
+            "STARTS_WITH",
+            "ENDS_WITH",
+            "CONTAINS_IGNORE_CASE",
+            "NOT_CONTAINS_IGNORE_CASE",
+            "STARTS_WITH_IGNORE_CASE",
+            "ENDS_WITH_IGNORE_CASE",
+        ] {
+            let wire = serde_json::json!({
+                "attribute": "email",
+                "operator": operator,
+                "value": "@Acme.com",
+            });
+            let condition: Condition = serde_json::from_value(wire.clone()).unwrap();
+            assert_eq!(serde_json::to_value(condition).unwrap(), wire);
+        }
+
+        for (operator, value) in [
+            ("BEFORE", serde_json::json!("2026-01-01T00:00:00Z")),
+            ("AFTER", serde_json::json!("2026-01-01T00:00:00.500Z")),
+            ("GTE", serde_json::json!("2026-01-01T00:00:00Z")),
+            ("GT", serde_json::json!("2026-01-01T00:00:00.000+00:00")),
+            ("LT", serde_json::json!("2026-01-01T02:00:00+02:00")),
+            ("WITHIN_LAST_DAYS", serde_json::json!(7.0)),
+        ] {
+            let wire = serde_json::json!({
+                "attribute": "signup",
+                "operator": operator,
+                "value": value,
+            });
+            let condition: Condition = serde_json::from_value(wire.clone()).unwrap();
+            assert_eq!(serde_json::to_value(condition).unwrap(), wire);
+        }
+
+        let non_string = serde_json::json!({
+            "attribute": "email",
+            "operator": "CONTAINS",
+            "value": ["@acme.com"],
+        });
+        assert!(serde_json::from_value::<Condition>(non_string).is_err());
+    }
+
+    #[test]
+    fn semver_matches_round_trip() {
+        let wire = serde_json::json!({
+            "attribute": "appVersion",
+            "operator": "SEMVER_MATCHES",
+            "value": ">=1.2, <2 || ^4.2",
+        });
+        let condition: Condition = serde_json::from_value(wire.clone()).unwrap();
+        assert_eq!(serde_json::to_value(condition).unwrap(), wire);
+
+        let invalid = serde_json::json!({
+            "attribute": "appVersion",
+            "operator": "SEMVER_MATCHES",
+            "value": ">=1.2 ||",
+        });
+        assert!(serde_json::from_value::<Condition>(invalid).is_err());
+    }
+
     #[test]
     fn parse_partially_if_unexpected() {
         let ufc: UniversalFlagConfigWire = serde_json::from_str(
@@ -1,7 +1,19 @@
-from typing import Dict, Any, Set, Union
+import os
+from datetime import datetime
+from typing import Callable, Dict, Any, List, Literal, Set, Union
 
 __version__: str
 
+AttributeValue = Union[
+    str,
+    int,
+    float,
+    bool,
+    List[Union[str, int, float, bool]],
+    Dict[str, "AttributeValue"],
+    None,
+]
+
 def init(config: ClientConfig) -> EppoClient: ...
 def get_instance() -> EppoClient: ...
 
@@ -13,6 +25,16 @@ class Configuration:
     def get_bandits_configuration(self) -> bytes | None: ...
     def get_flag_keys(self) -> Set[str]: ...
     def get_bandit_keys(self) -> Set[str]: ...
+    def get_diagnostics(self) -> List[ConfigurationDiagnostic]: ...
+
+class ConfigurationDiagnostic:
+    flag_key: str
+    allocation_key: str | None
+    variation_key: str | None
+    rule_index: int | None
+    condition_index: int | None
+    reason: str
+    raw: Any
 
 class ClientConfig:
     api_key: str
@@ -22,6 +44,23 @@ class ClientConfig:
     poll_interval_seconds: int | None
     poll_jitter_seconds: int
     initial_configuration: Configuration | None
+    configuration_cache_directory: str | os.PathLike | None
+    assignment_cache_size: int | None
+    assignment_cache_ttl_seconds: float | None
+    connect_timeout_seconds: float | None
+    read_timeout_seconds: float | None
+    proxy: str | None
+    root_certificates: List[str]
+    headers: Dict[str, str]
+    user_agent: str | None
+    flags_configuration_file: str | os.PathLike | None
+    bandits_configuration_file: str | os.PathLike | None
+    watch_configuration_files: bool
+    max_configuration_age_seconds: float | None
+    stale_configuration_policy: (
+        Literal["warn", "return_default"] | Callable[[float], None] | None
+    )
+    configuration_history_size: int
 
     def __init__(
         self,
@@ -32,7 +71,24 @@ class ClientConfig:
         is_graceful_mode: bool = True,
         poll_interval_seconds: int | None = ...,
         poll_jitter_seconds: int = ...,
-        initial_configuration: Configuration | None = None
+        initial_configuration: Configuration | None = None,
+        configuration_cache_directory: str | os.PathLike | None = None,
+        assignment_cache_size: int | None = None,
+        assignment_cache_ttl_seconds: float | None = None,
+        connect_timeout_seconds: float | None = ...,
+        read_timeout_seconds: float | None = ...,
+        proxy: str | None = None,
+        root_certificates: List[str] = [],
+        headers: Dict[str, str] = {},
+        user_agent: str | None = None,
+        flags_configuration_file: str | os.PathLike | None = None,
+        bandits_configuration_file: str | os.PathLike | None = None,
+        watch_configuration_files: bool = False,
+        max_configuration_age_seconds: float | None = None,
+        stale_configuration_policy: (
+            Literal["warn", "return_default"] | Callable[[float], None] | None
+        ) = None,
+        configuration_history_size: int = 5
     ): ...
 
 class AssignmentLogger:
@@ -44,70 +100,70 @@ class EppoClient:
         self,
         flag_key: str,
         subject_key: str,
-        subject_attributes: Dict[str, Union[str, int, float, bool, None]],
+        subject_attributes: Dict[str, AttributeValue],
         default: str,
     ) -> str: ...
     def get_integer_assignment(
         self,
         flag_key: str,
         subject_key: str,
-        subject_attributes: Dict[str, Union[str, int, float, bool, None]],
+        subject_attributes: Dict[str, AttributeValue],
         default: int,
     ) -> int: ...
     def get_numeric_assignment(
         self,
         flag_key: str,
         subject_key: str,
-        subject_attributes: Dict[str, Union[str, int, float, bool, None]],
+        subject_attributes: Dict[str, AttributeValue],
         default: float,
     ) -> float: ...
     def get_boolean_assignment(
         self,
         flag_key: str,
         subject_key: str,
-        subject_attributes: Dict[str, Union[str, int, float, bool, None]],
+        subject_attributes: Dict[str, AttributeValue],
         default: bool,
     ) -> bool: ...
     def get_json_assignment(
         self,
         flag_key: str,
         subject_key: str,
-        subject_attributes: Dict[str, Union[str, int, float, bool, None]],
+        subject_attributes: Dict[str, AttributeValue],
         default: Any,
     ) -> Any: ...
     def get_string_assignment_details(
         self,
         flag_key: str,
         subject_key: str,
-        subject_attributes: Dict[str, Union[str, int, float, bool, None]],
+        subject_attributes: Dict[str, AttributeValue],
         default: str,
     ) -> EvaluationResult: ...
     def get_integer_assignment_details(
         self,
         flag_key: str,
         subject_key: str,
-        subject_attributes: Dict[str, Union[str, int, float, bool, None]],
+        subject_attributes: Dict[str, AttributeValue],
         default: int,
     ) -> EvaluationResult: ...
     def get_numeric_assignment_details(
         self,
         flag_key: str,
         subject_key: str,
-        subject_attributes: Dict[str, Union[str, int, float, bool, None]],
+        subject_attributes: Dict[str, AttributeValue],
         default: float,
     ) -> EvaluationResult: ...
     def get_boolean_assignment_details(
         self,
         flag_key: str,
         subject_key: str,
-        subject_attributes: Dict[str, Union[str, int, float, bool, None]],
+        subject_attributes: Dict[str, AttributeValue],
         default: bool,
     ) -> EvaluationResult: ...
     def get_json_assignment_details(
         self,
         flag_key: str,
         subject_key: str,
-        subject_attributes: Dict[str, Union[str, int, float, bool, None]],
+        subject_attributes: Dict[str, AttributeValue],
         default: Any,
     ) -> EvaluationResult: ...
     def get_bandit_action(
@@ -143,8 +199,31 @@ class EppoClient:
     def set_is_graceful_mode(self, is_graceful_mode: bool): ...
     def is_initialized(self) -> bool: ...
     def wait_for_initialization(self) -> None: ...
+    def get_poller_status(self) -> PollerStatus | None: ...
+    def get_configuration_history(self) -> List[ConfigurationHistoryEntry]: ...
+    def pin_configuration(self) -> None: ...
+    def rollback_configuration(self, version: int) -> None: ...
+    def unpin_configuration(self) -> None: ...
+    def is_configuration_pinned(self) -> bool: ...
     def shutdown(self) -> None: ...
 
+class ConfigurationHistoryEntry:
+    version: int
+    active: bool
+    fetched_at: datetime
+    created_at: datetime
+    configuration: Configuration
+
+class PollerStatus:
+    running: bool
+    last_success_at: datetime | None
+    last_error: str | None
+    last_error_at: datetime | None
+    consecutive_failures: int
+    configuration_fetched_at: datetime | None
+    configuration_created_at: datetime | None
+    configuration_age_seconds: float | None
+
 class ContextAttributes:
     def __new__(
         cls,
@@ -8,26 +8,33 @@ use std::{
 };
 
 use pyo3::{
-    exceptions::{PyRuntimeError, PyTypeError},
+    exceptions::{PyRuntimeError, PyTypeError, PyValueError},
     intern,
     prelude::*,
-    types::{PyBool, PyFloat, PyInt, PySet, PyString},
+    types::{PyBool, PyDateTime, PyFloat, PyInt, PySet, PyString, PyTzInfo},
     BoundObject, PyTraverseError, PyVisit,
 };
 
 use eppo_core::{
     ahash::HashMap,
+    assignment_cache::AssignmentCache,
     background::BackgroundThread,
+    configuration_cache::ConfigurationCache,
     configuration_fetcher::ConfigurationFetcher,
+    configuration_file::{start_configuration_file_watcher, ConfigurationFileWatcherConfig},
     configuration_poller::{
         start_configuration_poller, ConfigurationPoller, ConfigurationPollerConfig,
+        ConfigurationPollerStatus,
+    },
+    configuration_store::{
+        ConfigurationHistoryEntry as CoreConfigurationHistoryEntry, ConfigurationStore,
     },
-    configuration_store::ConfigurationStore,
     eval::{
         eval_details::{EvaluationDetails, EvaluationResultWithDetails},
         BanditResult, Evaluator, EvaluatorConfig,
     },
     events::{AssignmentEvent, BanditEvent},
+    timestamp::Timestamp,
     ufc::VariationType,
     Attributes, ContextAttributes, Str,
 };
@@ -145,6 +152,71 @@ impl EvaluationResult {
     }
 }
 
+/// Configuration poller health returned by `EppoClient.get_poller_status()`.
+#[pyclass(frozen, get_all, module = "eppo_client")]
+pub struct PollerStatus {
+    running: bool,
+    last_success_at: Option<Py<PyDateTime>>,
+    last_error: Option<String>,
+    last_error_at: Option<Py<PyDateTime>>,
+    consecutive_failures: u32,
+    configuration_fetched_at: Option<Py<PyDateTime>>,
+    configuration_created_at: Option<Py<PyDateTime>>,
+    configuration_age_seconds: Option<f64>,
+}
+impl PollerStatus {
+    fn new(py: Python, status: ConfigurationPollerStatus) -> PyResult<PollerStatus> {
+        let datetime = |timestamp: Option<Timestamp>| {
+            timestamp
+                .map(|timestamp| to_py_datetime(py, timestamp))
+                .transpose()
+        };
+
+        Ok(PollerStatus {
+            running: status.running,
+            last_success_at: datetime(status.last_success_at)?,
+            last_error: status.last_error.as_ref().map(ToString::to_string),
+            last_error_at: datetime(status.last_error_at)?,
+            consecutive_failures: status.consecutive_failures,
+            configuration_fetched_at: datetime(status.configuration_fetched_at)?,
+            configuration_created_at: datetime(status.configuration_created_at)?,
+            configuration_age_seconds: status.configuration_age().map(|age| age.as_secs_f64()),
+        })
+    }
+}
+
+/// A configuration recorded in client history, returned by
+/// `EppoClient.get_configuration_history()`.
+#[pyclass(frozen, get_all, module = "eppo_client")]
+pub struct ConfigurationHistoryEntry {
+    version: u64,
+    active: bool,
+    fetched_at: Py<PyDateTime>,
+    created_at: Py<PyDateTime>,
+    configuration: Py<Configuration>,
+}
+
+impl ConfigurationHistoryEntry {
+    fn new(
+        py: Python,
+        entry: CoreConfigurationHistoryEntry,
+    ) -> PyResult<ConfigurationHistoryEntry> {
+        Ok(ConfigurationHistoryEntry {
+            version: entry.version,
+            active: entry.active,
+            fetched_at: to_py_datetime(py, entry.configuration.fetched_at())?,
+            created_at: to_py_datetime(py, entry.configuration.created_at())?,
+            configuration: Py::new(py, Configuration::new(entry.configuration))?,
+        })
+    }
+}
+
+fn to_py_datetime(py: Python, timestamp: Timestamp) -> PyResult<Py<PyDateTime>> {
+    let utc = PyTzInfo::utc(py)?;
+    let seconds = timestamp.timestamp_micros() as f64 / 1_000_000.0;
+    PyDateTime::from_timestamp(py, seconds, Some(&utc)).map(Bound::unbind)
+}
+
 #[pyclass(frozen, module = "eppo_client")]
 pub struct EppoClient {
     configuration_store: Arc<ConfigurationStore>,
@@ -444,6 +516,41 @@ impl EppoClient {
             .set_configuration(Arc::clone(&configuration.configuration));
     }
 
+    /// Returns recently fetched or loaded configurations, newest first.
+    fn get_configuration_history(&self, py: Python) -> PyResult<Vec<ConfigurationHistoryEntry>> {
+        self.configuration_store
+            .history()
+            .into_iter()
+            .map(|entry| ConfigurationHistoryEntry::new(py, entry))
+            .collect()
+    }
+
+    /// Pin the active configuration. While pinned, new configurations are recorded in history but
+    /// not applied until `unpin_configuration()` is called.
+    fn pin_configuration(&self) {
+        self.configuration_store.pin();
+    }
+
+    /// Roll back to configuration `version` from `get_configuration_history()` and pin it.
+    fn rollback_configuration(&self, version: u64) -> PyResult<()> {
+        if self.configuration_store.rollback(version) {
+            Ok(())
+        } else {
+            Err(PyValueError::new_err(format!(
+                "configuration version {version} is not in history"
+            )))
+        }
+    }
+
+    /// Unpin configuration and apply the most recently fetched one.
+    fn unpin_configuration(&self) {
+        self.configuration_store.unpin();
+    }
+
+    fn is_configuration_pinned(&self) -> bool {
+        self.configuration_store.is_pinned()
+    }
+
     fn set_is_graceful_mode(&self, is_graceful_mode: bool) {
         self.is_graceful_mode
             .store(is_graceful_mode, Ordering::Release);
@@ -473,6 +580,14 @@ impl EppoClient {
         }
     }
 
+    /// Returns configuration poller health, or None if the poller is disabled.
+    fn get_poller_status(&self, py: Python) -> PyResult<Option<PollerStatus>> {
+        self.poller
+            .as_ref()
+            .map(|poller| PollerStatus::new(py, poller.status()))
+            .transpose()
+    }
+
     /// Shutdown the client and wait for the background thread to exit.
     ///
     /// It is recommended to call this method before exiting the program so the background thread
@@ -583,45 +698,98 @@ fn actions_from_py(obj: &Bound<PyAny>) -> PyResult<HashMap<Str, ContextAttribute
 // Rust-only methods
 impl EppoClient {
     pub fn new(py: Python, config: &ClientConfig) -> PyResult<EppoClient> {
-        let configuration_store = Arc::new(ConfigurationStore::new());
-        if let Some(configuration) = &config.initial_configuration {
+        let configuration_cache = config
+            .configuration_cache_directory
+            .as_ref()
+            .map(ConfigurationCache::new);
+
+        let configuration_files = config.configuration_files();
+
+        let configuration_store = Arc::new(ConfigurationStore::with_history_size(
+            config.configuration_history_size,
+        ));
+        if let Some(files) = &configuration_files {
+            let configuration = files.load(SDK_METADATA).map_err(|err| {
+                PyValueError::new_err(format!("unable to load configuration files: {err}"))
+            })?;
+            configuration_store.set_configuration(Arc::new(configuration));
+        } else if let Some(configuration) = &config.initial_configuration {
             let configuration = Arc::clone(&configuration.get().configuration);
             configuration_store.set_configuration(configuration);
+        } else if let Some(cache) = &configuration_cache {
+            if let Ok(Some(configuration)) = cache.load(SDK_METADATA) {
+                configuration_store.set_configuration(Arc::new(configuration));
+            }
         }
 
+        let assignment_cache_ttl = config.assignment_cache_ttl()?;
+        let assignment_cache = config.assignment_cache_size.map(|capacity| {
+            let cache = AssignmentCache::new(capacity);
+            let cache = match assignment_cache_ttl {
+                Some(ttl) => cache.with_ttl(ttl),
+                None => cache,
+            };
+            Arc::new(cache)
+        });
+
         let evaluator = Evaluator::new(EvaluatorConfig {
             configuration_store: configuration_store.clone(),
             sdk_metadata: SDK_METADATA,
+            assignment_cache,
+            max_configuration_age: config.max_configuration_age()?,
+            stale_configuration_policy: config.stale_configuration_policy(py)?,
         });
 
+        let start_background_thread = || {
+            BackgroundThread::start().map_err(|err| {
+                // This should normally never happen.
+                PyRuntimeError::new_err(format!("unable to start background thread: {err}"))
+            })
+        };
+
         let poller_thread = config
             .poll_interval_seconds
+            // Offline mode never fetches configuration from the server.
+            .filter(|_| configuration_files.is_none())
             .map(|poll_interval_seconds| {
-                let thread = BackgroundThread::start()?;
+                let fetcher = ConfigurationFetcher::new(
+                    eppo_core::configuration_fetcher::ConfigurationFetcherConfig {
+                        base_url: config.base_url.clone(),
+                        api_key: config.api_key.clone(),
+                        sdk_metadata: SDK_METADATA,
+                        http_client: config.http_client_config()?,
+                    },
+                )
+                .map_err(|err| PyValueError::new_err(err.to_string()))?;
+                let thread = start_background_thread()?;
                 let poller = start_configuration_poller(
                     thread.runtime(),
-                    ConfigurationFetcher::new(
-                        eppo_core::configuration_fetcher::ConfigurationFetcherConfig {
-                            base_url: config.base_url.clone(),
-                            api_key: config.api_key.clone(),
-                            sdk_metadata: SDK_METADATA,
-                        },
-                    ),
+                    fetcher,
                     configuration_store.clone(),
                     ConfigurationPollerConfig {
                         interval: Duration::from_secs(poll_interval_seconds.into()),
                         jitter: Duration::from_secs(config.poll_jitter_seconds),
+                        cache: configuration_cache.clone(),
+                        ..ConfigurationPollerConfig::default()
                     },
                 );
-                Ok((thread, poller))
+                Ok::<_, PyErr>((thread, poller))
             })
-            .transpose()
-            .map_err(|err: std::io::Error| {
-                // This should normally never happen.
-                PyRuntimeError::new_err(format!("unable to start background thread: {err}"))
-            })?;
-
-        let (background_thread, poller) = poller_thread.unzip();
+            .transpose()?;
+
+        let (mut background_thread, poller) = poller_thread.unzip();
+
+        if let Some(files) = configuration_files.filter(|_| config.watch_configuration_files) {
+            let thread = start_background_thread()?;
+            start_configuration_file_watcher(
+                thread.runtime(),
+                files,
+                configuration_store.clone(),
+                SDK_METADATA,
+                ConfigurationFileWatcherConfig::default(),
+            );
+            background_thread = Some(thread);
+        }
 
         Ok(EppoClient {
             configuration_store,
@@ -1,9 +1,13 @@
-use std::num::NonZeroU64;
+use std::{collections::HashMap, num::NonZeroU64, path::PathBuf, sync::Arc, time::Duration};
 
 use pyo3::{exceptions::PyValueError, prelude::*, PyTraverseError, PyVisit};
 
 use eppo_core::{
-    configuration_fetcher::DEFAULT_BASE_URL, configuration_poller::ConfigurationPollerConfig,
+    configuration_fetcher::{HttpClientConfig, DEFAULT_BASE_URL},
+    configuration_file::ConfigurationFiles,
+    configuration_poller::ConfigurationPollerConfig,
+    configuration_store::ConfigurationStore,
+    eval::StaleConfigurationPolicy,
 };
 
 use crate::{assignment_logger::AssignmentLogger, configuration::Configuration};
@@ -17,6 +21,21 @@ pub struct ClientConfig {
     pub(crate) poll_interval_seconds: Option<NonZeroU64>,
     pub(crate) poll_jitter_seconds: u64,
     pub(crate) initial_configuration: Option<Py<Configuration>>,
+    pub(crate) configuration_cache_directory: Option<PathBuf>,
+    pub(crate) assignment_cache_size: Option<usize>,
+    pub(crate) assignment_cache_ttl_seconds: Option<f64>,
+    pub(crate) connect_timeout_seconds: Option<f64>,
+    pub(crate) read_timeout_seconds: Option<f64>,
+    pub(crate) proxy: Option<String>,
+    pub(crate) root_certificates: Vec<String>,
+    pub(crate) headers: HashMap<String, String>,
+    pub(crate) user_agent: Option<String>,
+    pub(crate) flags_configuration_file: Option<PathBuf>,
+    pub(crate) bandits_configuration_file: Option<PathBuf>,
+    pub(crate) watch_configuration_files: bool,
+    pub(crate) max_configuration_age_seconds: Option<f64>,
+    pub(crate) stale_configuration_policy: Option<Py<PyAny>>,
+    pub(crate) configuration_history_size: usize,
 }
 
 #[pymethods]
@@ -30,8 +49,24 @@ impl ClientConfig {
             is_graceful_mode=true,
             poll_interval_seconds=Some(NonZeroU64::new(ConfigurationPollerConfig::DEFAULT_POLL_INTERVAL.as_secs()).unwrap()),
             poll_jitter_seconds=ConfigurationPollerConfig::DEFAULT_POLL_JITTER.as_secs(),
-            initial_configuration=None
+            initial_configuration=None,
+            configuration_cache_directory=None,
+            assignment_cache_size=None,
+            assignment_cache_ttl_seconds=None,
+            connect_timeout_seconds=Some(HttpClientConfig::DEFAULT_CONNECT_TIMEOUT.as_secs_f64()),
+            read_timeout_seconds=Some(HttpClientConfig::DEFAULT_READ_TIMEOUT.as_secs_f64()),
+            proxy=None,
+            root_certificates=Vec::new(),
+            headers=HashMap::new(),
+            user_agent=None,
+            flags_configuration_file=None,
+            bandits_configuration_file=None,
+            watch_configuration_files=false,
+            max_configuration_age_seconds=None,
+            stale_configuration_policy=None,
+            configuration_history_size=ConfigurationStore::DEFAULT_HISTORY_SIZE
         ))]
+    #[allow(clippy::too_many_arguments)] // mirrors Python keyword arguments
     fn new(
         api_key: String,
         base_url: String,
@@ -40,12 +75,60 @@ impl ClientConfig {
         poll_interval_seconds: Option<NonZeroU64>,
         poll_jitter_seconds: u64,
         initial_configuration: Option<Py<Configuration>>,
+        configuration_cache_directory: Option<PathBuf>,
+        assignment_cache_size: Option<usize>,
+        assignment_cache_ttl_seconds: Option<f64>,
+        connect_timeout_seconds: Option<f64>,
+        read_timeout_seconds: Option<f64>,
+        proxy: Option<String>,
+        root_certificates: Vec<String>,
+        headers: HashMap<String, String>,
+        user_agent: Option<String>,
+        flags_configuration_file: Option<PathBuf>,
+        bandits_configuration_file: Option<PathBuf>,
+        watch_configuration_files: bool,
+        max_configuration_age_seconds: Option<f64>,
+        stale_configuration_policy: Option<Bound<PyAny>>,
+        configuration_history_size: usize,
     ) -> PyResult<ClientConfig> {
         if api_key.is_empty() {
             return Err(PyValueError::new_err(
                 "Invalid value for api_key: cannot be blank",
             ));
         }
+        if assignment_cache_ttl_seconds.is_some_and(|ttl| !ttl.is_finite() || ttl < 0.0) {
+            return Err(PyValueError::new_err(
+                "Invalid value for assignment_cache_ttl_seconds: must be a non-negative number",
+            ));
+        }
+
+        if max_configuration_age_seconds.is_some_and(|age| !age.is_finite() || age < 0.0) {
+            return Err(PyValueError::new_err(
+                "Invalid value for max_configuration_age_seconds: must be a non-negative number",
+            ));
+        }
+        if let Some(policy) = &stale_configuration_policy {
+            parse_stale_configuration_policy(policy)?;
+        }
+
+        for (name, timeout) in [
+            ("connect_timeout_seconds", connect_timeout_seconds),
+            ("read_timeout_seconds", read_timeout_seconds),
+        ] {
+            if timeout.is_some_and(|timeout| !timeout.is_finite() || timeout <= 0.0) {
+                return Err(PyValueError::new_err(format!(
+                    "Invalid value for {name}: must be a positive number"
+                )));
+            }
+        }
+
+        if flags_configuration_file.is_none()
+            && (bandits_configuration_file.is_some() || watch_configuration_files)
+        {
+            return Err(PyValueError::new_err(
+                "flags_configuration_file is required for bandits_configuration_file and watch_configuration_files",
+            ));
+        }
 
         Ok(ClientConfig {
             api_key,
@@ -55,6 +138,21 @@ impl ClientConfig {
             poll_interval_seconds,
             poll_jitter_seconds,
             initial_configuration,
+            configuration_cache_directory,
+            assignment_cache_size,
+            assignment_cache_ttl_seconds,
+            connect_timeout_seconds,
+            read_timeout_seconds,
+            proxy,
+            root_certificates,
+            headers,
+            user_agent,
+            flags_configuration_file,
+            bandits_configuration_file,
+            watch_configuration_files,
+            max_configuration_age_seconds,
+            stale_configuration_policy: stale_configuration_policy.map(Bound::unbind),
+            configuration_history_size,
         })
     }
 
@@ -64,6 +162,15 @@ impl ClientConfig {
         self.assignment_logger = Some(assignment_logger);
     }
 
+    #[setter]
+    fn set_stale_configuration_policy(&mut self, policy: Option<Bound<PyAny>>) -> PyResult<()> {
+        if let Some(policy) = &policy {
+            parse_stale_configuration_policy(policy)?;
+        }
+        self.stale_configuration_policy = policy.map(Bound::unbind);
+        Ok(())
+    }
+
     // Implementing [Garbage Collector integration][1] in case user's `AssignmentLogger` holds a
     // reference to `Config`. This will allow the GC to detect this cycle and break it.
     //
@@ -72,9 +179,114 @@ impl ClientConfig {
         if let Some(assignment_logger) = &self.assignment_logger {
             visit.call(assignment_logger)?;
         }
+        if let Some(policy) = &self.stale_configuration_policy {
+            visit.call(policy)?;
+        }
         Ok(())
     }
     fn __clear__(&mut self) {
         self.assignment_logger = None;
+        self.stale_configuration_policy = None;
+    }
+}
+
+impl ClientConfig {
+    /// How long to remember logged assignments for.
+    pub(crate) fn assignment_cache_ttl(&self) -> PyResult<Option<Duration>> {
+        seconds_to_duration(
+            "assignment_cache_ttl_seconds",
+            self.assignment_cache_ttl_seconds,
+        )
+    }
+
+    /// Age after which configuration is considered stale.
+    pub(crate) fn max_configuration_age(&self) -> PyResult<Option<Duration>> {
+        seconds_to_duration(
+            "max_configuration_age_seconds",
+            self.max_configuration_age_seconds,
+        )
+    }
+
+    /// HTTP client settings for fetching configuration.
+    pub(crate) fn http_client_config(&self) -> PyResult<HttpClientConfig> {
+        Ok(HttpClientConfig {
+            connect_timeout: seconds_to_duration(
+                "connect_timeout_seconds",
+                self.connect_timeout_seconds,
+            )?,
+            read_timeout: seconds_to_duration("read_timeout_seconds", self.read_timeout_seconds)?,
+            proxy: self.proxy.clone(),
+            root_certificates: self
+                .root_certificates
+                .iter()
+                .map(|pem| pem.as_bytes().to_vec())
+                .collect(),
+            headers: self
+                .headers
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect(),
+            user_agent: self.user_agent.clone(),
+        })
+    }
+    /// Configuration files for offline mode.
+    pub(crate) fn configuration_files(&self) -> Option<ConfigurationFiles> {
+        let files = ConfigurationFiles::new(self.flags_configuration_file.as_ref()?);
+        Some(match &self.bandits_configuration_file {
+            Some(bandits) => files.with_bandits(bandits),
+            None => files,
+        })
+    }
+
+    /// What to do when evaluating against configuration older than
+    /// `max_configuration_age_seconds`.
+    pub(crate) fn stale_configuration_policy(
+        &self,
+        py: Python<'_>,
+    ) -> PyResult<StaleConfigurationPolicy> {
+        match &self.stale_configuration_policy {
+            Some(policy) => parse_stale_configuration_policy(policy.bind(py)),
+            None => Ok(StaleConfigurationPolicy::default()),
+        }
+    }
+}
+
+/// Convert optional `seconds` setting called `name` into `Duration`. Raises `ValueError` if the
+/// value is negative, not finite, or too large.
+fn seconds_to_duration(name: &str, seconds: Option<f64>) -> PyResult<Option<Duration>> {
+    seconds
+        .map(|seconds| {
+            Duration::try_from_secs_f64(seconds)
+                .map_err(|err| PyValueError::new_err(format!("Invalid value for {name}: {err}")))
+        })
+        .transpose()
+}
+
+/// Convert `"warn"`, `"return_default"`, or a callable accepting configuration age in seconds into
+/// [`StaleConfigurationPolicy`].
+fn parse_stale_configuration_policy(policy: &Bound<PyAny>) -> PyResult<StaleConfigurationPolicy> {
+    if let Ok(name) = policy.extract::<&str>() {
+        return match name {
+            "warn" => Ok(StaleConfigurationPolicy::Warn),
+            "return_default" => Ok(StaleConfigurationPolicy::ReturnDefault),
+            _ => Err(PyValueError::new_err(format!(
+                "Invalid value for stale_configuration_policy: {name:?} (expected \"warn\", \"return_default\", or a callable)"
+            ))),
+        };
+    }
+    if !policy.is_callable() {
+        return Err(PyValueError::new_err(
+            "Invalid value for stale_configuration_policy: expected \"warn\", \"return_default\", or a callable",
+        ));
     }
+    let callback = policy.clone().unbind();
+    Ok(StaleConfigurationPolicy::Callback(Arc::new(
+        move |_configuration, age| {
+            Python::attach(|py| {
+                if let Err(err) = callback.call1(py, (age.as_secs_f64(),)) {
+                    log::warn!(target: "eppo", "error calling stale_configuration_policy callback: {err}");
+                }
+            })
+        },
+    )))
 }
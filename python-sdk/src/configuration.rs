@@ -4,7 +4,10 @@ use std::{borrow::Cow, sync::Arc};
 
 use pyo3::{exceptions::PyValueError, prelude::*, types::PySet};
 
-use eppo_core::{ufc::UniversalFlagConfig, Configuration as CoreConfiguration};
+use eppo_core::{
+    configuration_diagnostics::ConfigurationDiagnostic as CoreConfigurationDiagnostic,
+    pyo3::serde_to_pyobject, ufc::UniversalFlagConfig, Configuration as CoreConfiguration,
+};
 
 use crate::SDK_METADATA;
 
@@ -69,6 +72,47 @@ impl Configuration {
     fn get_bandits_configuration(&self) -> Option<Cow<'_, [u8]>> {
         self.configuration.get_bandits_configuration()
     }
+
+    /// Return a list of flags, allocations, variations, and conditions that failed to parse or
+    /// compile. An empty list means that all flags are valid.
+    fn get_diagnostics(&self, py: Python) -> PyResult<Vec<ConfigurationDiagnostic>> {
+        self.configuration
+            .diagnostics()
+            .into_iter()
+            .map(|diagnostic| ConfigurationDiagnostic::new(py, diagnostic))
+            .collect()
+    }
+}
+
+/// A part of flags configuration that failed to parse or compile, returned by
+/// `Configuration.get_diagnostics()`.
+#[pyclass(frozen, get_all, module = "eppo_client")]
+pub struct ConfigurationDiagnostic {
+    flag_key: String,
+    allocation_key: Option<String>,
+    variation_key: Option<String>,
+    rule_index: Option<usize>,
+    condition_index: Option<usize>,
+    reason: String,
+    /// JSON fragment of the affected part of configuration.
+    raw: Py<PyAny>,
+}
+
+impl ConfigurationDiagnostic {
+    fn new(
+        py: Python,
+        diagnostic: CoreConfigurationDiagnostic,
+    ) -> PyResult<ConfigurationDiagnostic> {
+        Ok(ConfigurationDiagnostic {
+            flag_key: diagnostic.flag_key.to_string(),
+            allocation_key: diagnostic.allocation_key.map(|it| it.to_string()),
+            variation_key: diagnostic.variation_key.map(|it| it.to_string()),
+            rule_index: diagnostic.rule_index,
+            condition_index: diagnostic.condition_index,
+            reason: diagnostic.reason,
+            raw: serde_to_pyobject(&diagnostic.raw, py)?.unbind(),
+        })
+    }
 }
 
 impl Configuration {
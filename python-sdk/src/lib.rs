@@ -20,9 +20,9 @@ mod eppo_client {
     #[pymodule_export]
     use crate::{
         assignment_logger::AssignmentLogger,
-        client::{EppoClient, EvaluationResult},
+        client::{ConfigurationHistoryEntry, EppoClient, EvaluationResult, PollerStatus},
         client_config::ClientConfig,
-        configuration::Configuration,
+        configuration::{Configuration, ConfigurationDiagnostic},
         init::{get_instance, init},
     };
 
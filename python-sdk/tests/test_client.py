@@ -33,6 +33,22 @@ def test_shutdown():
     client.shutdown()
 
 
+@pytest.mark.rust_only
+def test_get_poller_status():
+    client = init("ufc", wait_for_init=True)
+
+    status = client.get_poller_status()
+    assert status is not None
+    assert status.running
+    assert status.consecutive_failures == 0
+    assert status.last_success_at is not None
+    assert status.configuration_created_at is not None
+    assert status.configuration_age_seconds < 60
+
+    client.shutdown()
+    assert not client.get_poller_status().running
+
+
 def test_get_flag_keys_none():
     client = init("ufc", wait_for_init=False)
     assert client.get_flag_keys() == set()
@@ -61,3 +61,84 @@ class TestConfig:
                 assignment_logger=AssignmentLogger(),
                 poll_interval_seconds=0,
             )
+
+    @pytest.mark.rust_only
+    def test_assignment_cache_options(self):
+        config = Config(
+            api_key="test-key",
+            assignment_logger=AssignmentLogger(),
+            assignment_cache_size=1000,
+            assignment_cache_ttl_seconds=60.0,
+        )
+        assert config.assignment_cache_size == 1000
+        assert config.assignment_cache_ttl_seconds == 60.0
+
+    @pytest.mark.rust_only
+    def test_assignment_cache_ttl_must_be_non_negative(self):
+        with pytest.raises(ValueError):
+            Config(
+                api_key="test-key",
+                assignment_logger=AssignmentLogger(),
+                assignment_cache_size=1000,
+                assignment_cache_ttl_seconds=-1.0,
+            )
+
+    @pytest.mark.rust_only
+    def test_http_client_options(self):
+        config = Config(
+            api_key="test-key",
+            assignment_logger=AssignmentLogger(),
+            connect_timeout_seconds=5.0,
+            read_timeout_seconds=None,
+            proxy="http://proxy.internal:3128",
+            headers={"X-Egress-Token": "token"},
+            user_agent="my-service/1.0",
+        )
+        assert config.connect_timeout_seconds == 5.0
+        assert config.read_timeout_seconds is None
+        assert config.proxy == "http://proxy.internal:3128"
+        assert config.root_certificates == []
+        assert config.headers == {"X-Egress-Token": "token"}
+        assert config.user_agent == "my-service/1.0"
+
+    @pytest.mark.rust_only
+    def test_timeouts_must_be_positive(self):
+        with pytest.raises(ValueError):
+            Config(
+                api_key="test-key",
+                assignment_logger=AssignmentLogger(),
+                connect_timeout_seconds=0.0,
+            )
+
+    @pytest.mark.rust_only
+    def test_configuration_files_require_flags_file(self):
+        with pytest.raises(ValueError):
+            Config(
+                api_key="test-key",
+                assignment_logger=AssignmentLogger(),
+                watch_configuration_files=True,
+            )
+
+    @pytest.mark.rust_only
+    def test_stale_configuration_policy(self):
+        config = Config(
+            api_key="test-key",
+            assignment_logger=AssignmentLogger(),
+            max_configuration_age_seconds=3600.0,
+            stale_configuration_policy="return_default",
+        )
+        assert config.max_configuration_age_seconds == 3600.0
+        assert config.stale_configuration_policy == "return_default"
+
+        config.stale_configuration_policy = lambda age_seconds: None
+        with pytest.raises(ValueError):
+            config.stale_configuration_policy = "ignore"
+
+    @pytest.mark.rust_only
+    def test_invalid_stale_configuration_policy(self):
+        with pytest.raises(ValueError):
+            Config(
+                api_key="test-key",
+                assignment_logger=AssignmentLogger(),
+                stale_configuration_policy="ignore",
+            )
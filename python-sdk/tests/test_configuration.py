@@ -131,6 +131,50 @@ class TestConfiguration:
         assert json.loads(bandits_configuration) == json.loads(BANDITS_MODEL_CONFIG)
 
 
+@pytest.mark.rust_only
+def test_get_diagnostics_empty():
+    configuration = Configuration(flags_configuration=FLAGS_CONFIG)
+    assert configuration.get_diagnostics() == []
+
+
+@pytest.mark.rust_only
+def test_get_diagnostics():
+    condition = {"attribute": "email", "operator": "MATCHES", "value": "(unclosed"}
+    flags_configuration = json.dumps(
+        {
+            "createdAt": "2024-09-09T10:18:15.988Z",
+            "format": "SERVER",
+            "environment": {"name": "test"},
+            "flags": {
+                "flag": {
+                    "key": "flag",
+                    "enabled": True,
+                    "variationType": "BOOLEAN",
+                    "variations": {"on": {"key": "on", "value": True}},
+                    "allocations": [
+                        {
+                            "key": "allocation",
+                            "rules": [{"conditions": [condition]}],
+                            "splits": [{"shards": [], "variationKey": "on"}],
+                        }
+                    ],
+                    "totalShards": 10000,
+                }
+            },
+        }
+    ).encode("utf-8")
+
+    [diagnostic] = Configuration(flags_configuration=flags_configuration).get_diagnostics()
+
+    assert diagnostic.flag_key == "flag"
+    assert diagnostic.allocation_key == "allocation"
+    assert diagnostic.variation_key is None
+    assert diagnostic.rule_index == 0
+    assert diagnostic.condition_index == 0
+    assert "regex" in diagnostic.reason
+    assert diagnostic.raw == condition
+
+
 @pytest.mark.rust_only
 def test_configuration_none():
     client = init("ufc", wait_for_init=False)
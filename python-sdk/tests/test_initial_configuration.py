@@ -1,3 +1,5 @@
+import pytest
+
 import eppo_client
 from eppo_client import Configuration
 from eppo_client.config import Config
@@ -45,3 +47,104 @@ def test_update_configuration():
     )
 
     assert client.is_initialized()
+
+
+def test_with_configuration_cache_directory(tmp_path):
+    (tmp_path / "eppo-configuration.json").write_text(
+        '{"fetchedAt":"2024-09-09T10:20:00Z","flags":{"createdAt":"2024-09-09T10:18:15.988Z","format":"SERVER","environment":{"name":"test"},"flags":{}},"bandits":null}'
+    )
+
+    client = eppo_client.init(
+        Config(
+            api_key="test",
+            poll_interval_seconds=None,
+            assignment_logger=AssignmentLogger(),
+            configuration_cache_directory=tmp_path,
+        )
+    )
+
+    assert client.is_initialized()
+
+
+def test_with_configuration_files(tmp_path):
+    flags_file = tmp_path / "flags.json"
+    flags_file.write_text(
+        '{"createdAt":"2024-09-09T10:18:15.988Z","format":"SERVER","environment":{"name":"test"},"flags":{}}'
+    )
+
+    client = eppo_client.init(
+        Config(
+            api_key="test",
+            # Unreachable server to make sure configuration is not fetched.
+            base_url="http://localhost:8378/api",
+            assignment_logger=AssignmentLogger(),
+            flags_configuration_file=flags_file,
+            watch_configuration_files=True,
+        )
+    )
+
+    assert client.is_initialized()
+    client.shutdown()
+
+
+@pytest.mark.rust_only
+def test_stale_configuration_returns_defaults():
+    stale_ages = []
+    config = Config(
+        api_key="test",
+        poll_interval_seconds=None,
+        assignment_logger=AssignmentLogger(),
+        initial_configuration=Configuration(
+            flags_configuration=b'{"createdAt":"2024-09-09T10:18:15.988Z","format":"SERVER","environment":{"name":"test"},"flags":{}}'
+        ),
+        max_configuration_age_seconds=0.0,
+        stale_configuration_policy=stale_ages.append,
+    )
+    client = eppo_client.init(config)
+
+    assert client.get_string_assignment("flag", "subject", {}, "default") == "default"
+    assert len(stale_ages) == 1
+
+    config.stale_configuration_policy = "return_default"
+    client = eppo_client.init(config)
+    result = client.get_string_assignment_details("flag", "subject", {}, "default")
+    assert result.variation == "default"
+    assert result.evaluation_details["flagEvaluationCode"] == "CONFIGURATION_STALE"
+
+
+@pytest.mark.rust_only
+def test_rollback_configuration():
+    def configuration(created_at):
+        return Configuration(
+            flags_configuration=(
+                '{"createdAt":"%s","format":"SERVER","environment":{"name":"test"},"flags":{}}'
+                % created_at
+            ).encode("utf-8")
+        )
+
+    client = eppo_client.init(
+        Config(
+            api_key="test",
+            poll_interval_seconds=None,
+            assignment_logger=AssignmentLogger(),
+            configuration_history_size=2,
+        )
+    )
+    client.set_configuration(configuration("2024-09-09T10:00:00Z"))
+    client.set_configuration(configuration("2024-09-09T11:00:00Z"))
+
+    [latest, previous] = client.get_configuration_history()
+    assert latest.active and not previous.active
+    assert previous.created_at.hour == 10
+
+    client.rollback_configuration(previous.version)
+    assert client.is_configuration_pinned()
+    client.set_configuration(configuration("2024-09-09T12:00:00Z"))
+    assert client.get_configuration_history()[1].active
+
+    with pytest.raises(ValueError):
+        client.rollback_configuration(42)
+
+    client.unpin_configuration()
+    assert not client.is_configuration_pinned()
+    assert client.get_configuration_history()[0].active
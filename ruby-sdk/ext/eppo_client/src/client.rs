@@ -1,19 +1,28 @@
-use std::{cell::RefCell, str::FromStr, sync::Arc, time::Duration};
+use std::{
+    cell::RefCell, collections::HashMap, path::PathBuf, str::FromStr, sync::Arc, time::Duration,
+};
 
 use crate::{configuration::Configuration, SDK_METADATA};
 use eppo_core::{
+    assignment_cache::AssignmentCache,
     background::BackgroundThread,
-    configuration_fetcher::{ConfigurationFetcher, ConfigurationFetcherConfig},
+    configuration_cache::ConfigurationCache,
+    configuration_fetcher::{ConfigurationFetcher, ConfigurationFetcherConfig, HttpClientConfig},
+    configuration_file::{
+        start_configuration_file_watcher, ConfigurationFileWatcherConfig, ConfigurationFiles,
+    },
     configuration_poller::{
         start_configuration_poller, ConfigurationPoller, ConfigurationPollerConfig,
+        ConfigurationPollerStatus,
     },
     configuration_store::ConfigurationStore,
-    eval::{Evaluator, EvaluatorConfig},
+    eval::{Evaluator, EvaluatorConfig, StaleConfigurationPolicy},
     event_ingestion::{EventIngestion, EventIngestionConfig},
+    timestamp::Timestamp,
     ufc::VariationType,
     Attributes, ContextAttributes, SdkKey,
 };
-use magnus::{error::Result, prelude::*, Error, IntoValue, Ruby, TryConvert, Value};
+use magnus::{error::Result, prelude::*, Error, IntoValue, RArray, Ruby, TryConvert, Value};
 
 #[derive(Debug)]
 #[magnus::wrap(class = "EppoClient::Core::Config", size, free_immediately)]
@@ -23,6 +32,15 @@ pub struct Config {
     poll_interval: Option<Duration>,
     poll_jitter: Duration,
     log_level: Option<log::LevelFilter>,
+    configuration_cache_directory: Option<PathBuf>,
+    assignment_cache_size: Option<usize>,
+    assignment_cache_ttl: Option<Duration>,
+    http_client: HttpClientConfig,
+    configuration_files: Option<ConfigurationFiles>,
+    watch_configuration_files: bool,
+    max_configuration_age: Option<Duration>,
+    stale_configuration_policy: StaleConfigurationPolicy,
+    configuration_history_size: usize,
     event_ingestion_config: Option<EventIngestionConfig>,
 }
 
@@ -43,6 +61,67 @@ impl TryConvert for Config {
             })
             .transpose()?
         };
+        let configuration_cache_directory =
+            Option::<String>::try_convert(val.funcall("configuration_cache_directory", ())?)?
+                .map(PathBuf::from);
+        let assignment_cache_size =
+            Option::<usize>::try_convert(val.funcall("assignment_cache_size", ())?)?;
+        let assignment_cache_ttl =
+            Option::<f64>::try_convert(val.funcall("assignment_cache_ttl_seconds", ())?)?
+                .map(|ttl| {
+                    Duration::try_from_secs_f64(ttl)
+                        .map_err(|err| Error::new(ruby.exception_arg_error(), err.to_string()))
+                })
+                .transpose()?;
+        let timeout = |name: &str| -> Result<Option<Duration>> {
+            Option::<f64>::try_convert(val.funcall(name, ())?)?
+                .map(|timeout| {
+                    Duration::try_from_secs_f64(timeout)
+                        .map_err(|err| Error::new(ruby.exception_arg_error(), err.to_string()))
+                })
+                .transpose()
+        };
+        let http_client = HttpClientConfig {
+            connect_timeout: timeout("connect_timeout_seconds")?,
+            read_timeout: timeout("read_timeout_seconds")?,
+            proxy: Option::<String>::try_convert(val.funcall("proxy", ())?)?,
+            root_certificates: Vec::<String>::try_convert(val.funcall("root_certificates", ())?)?
+                .into_iter()
+                .map(String::into_bytes)
+                .collect(),
+            headers: HashMap::<String, String>::try_convert(val.funcall("headers", ())?)?
+                .into_iter()
+                .collect(),
+            user_agent: Option::<String>::try_convert(val.funcall("user_agent", ())?)?,
+        };
+        let bandits_configuration_file =
+            Option::<String>::try_convert(val.funcall("bandits_configuration_file", ())?)?;
+        let configuration_files = Option::<String>::try_convert(
+            val.funcall("flags_configuration_file", ())?,
+        )?
+        .map(|flags| {
+            let files = ConfigurationFiles::new(flags);
+            match bandits_configuration_file {
+                Some(bandits) => files.with_bandits(bandits),
+                None => files,
+            }
+        });
+        let watch_configuration_files =
+            bool::try_convert(val.funcall("watch_configuration_files", ())?)?;
+        let max_configuration_age = timeout("max_configuration_age_seconds")?;
+        let stale_configuration_policy =
+            match String::try_convert(val.funcall("stale_configuration_policy", ())?)?.as_str() {
+                "warn" => StaleConfigurationPolicy::Warn,
+                "return_default" => StaleConfigurationPolicy::ReturnDefault,
+                other => {
+                    return Err(Error::new(
+                        ruby.exception_arg_error(),
+                        format!("invalid stale_configuration_policy: {other:?}"),
+                    ))
+                }
+            };
+        let configuration_history_size =
+            usize::try_convert(val.funcall("configuration_history_size", ())?)?;
 
         let event_ingestion_config = EventIngestionConfig::new(SdkKey::new(sdk_key.clone().into()));
         Ok(Config {
@@ -51,11 +130,48 @@ impl TryConvert for Config {
             poll_interval: poll_interval_seconds.map(Duration::from_secs),
             poll_jitter: Duration::from_secs(poll_jitter_seconds),
             log_level,
+            configuration_cache_directory,
+            assignment_cache_size,
+            assignment_cache_ttl,
+            http_client,
+            configuration_files,
+            watch_configuration_files,
+            max_configuration_age,
+            stale_configuration_policy,
+            configuration_history_size,
             event_ingestion_config,
         })
     }
 }
 
+/// Configuration poller health, converted to a Ruby Hash.
+#[derive(serde::Serialize)]
+struct PollerStatus {
+    running: bool,
+    last_success_at: Option<Timestamp>,
+    last_error: Option<String>,
+    last_error_at: Option<Timestamp>,
+    consecutive_failures: u32,
+    configuration_fetched_at: Option<Timestamp>,
+    configuration_created_at: Option<Timestamp>,
+    configuration_age_seconds: Option<f64>,
+}
+
+impl From<ConfigurationPollerStatus> for PollerStatus {
+    fn from(status: ConfigurationPollerStatus) -> PollerStatus {
+        PollerStatus {
+            running: status.running,
+            last_success_at: status.last_success_at,
+            last_error: status.last_error.as_ref().map(ToString::to_string),
+            last_error_at: status.last_error_at,
+            consecutive_failures: status.consecutive_failures,
+            configuration_fetched_at: status.configuration_fetched_at,
+            configuration_created_at: status.configuration_created_at,
+            configuration_age_seconds: status.configuration_age().map(|age| age.as_secs_f64()),
+        }
+    }
+}
+
 #[magnus::wrap(class = "EppoClient::Core::Client")]
 pub struct Client {
     configuration_store: Arc<ConfigurationStore>,
@@ -72,7 +188,7 @@ pub struct Client {
 }
 
 impl Client {
-    pub fn new(config: Config) -> Client {
+    pub fn new(ruby: &Ruby, config: Config) -> Result<Client> {
         // Initialize logger
         {
             let mut builder = env_logger::Builder::from_env(
@@ -90,28 +206,68 @@ impl Client {
             let _ = builder.try_init();
         };
 
-        let configuration_store = Arc::new(ConfigurationStore::new());
+        let configuration_cache = config
+            .configuration_cache_directory
+            .map(ConfigurationCache::new);
+
+        let configuration_store = Arc::new(ConfigurationStore::with_history_size(
+            config.configuration_history_size,
+        ));
+        if let Some(files) = &config.configuration_files {
+            let configuration = files.load(SDK_METADATA).map_err(|err| {
+                Error::new(
+                    ruby.exception_arg_error(),
+                    format!("unable to load configuration files: {err}"),
+                )
+            })?;
+            configuration_store.set_configuration(Arc::new(configuration));
+        } else if let Some(cache) = &configuration_cache {
+            if let Ok(Some(configuration)) = cache.load(SDK_METADATA) {
+                configuration_store.set_configuration(Arc::new(configuration));
+            }
+        }
+
+        let assignment_cache = config.assignment_cache_size.map(|capacity| {
+            let cache = AssignmentCache::new(capacity);
+            let cache = match config.assignment_cache_ttl {
+                Some(ttl) => cache.with_ttl(ttl),
+                None => cache,
+            };
+            Arc::new(cache)
+        });
 
         let evaluator = Evaluator::new(EvaluatorConfig {
             configuration_store: configuration_store.clone(),
             sdk_metadata: SDK_METADATA,
+            assignment_cache,
+            max_configuration_age: config.max_configuration_age,
+            stale_configuration_policy: config.stale_configuration_policy.clone(),
         });
 
         let background_thread =
             BackgroundThread::start().expect("should be able to start background thread");
 
-        let configuration_poller = if let Some(poll_interval) = config.poll_interval {
+        // Offline mode never fetches configuration from the server.
+        let poll_interval = config
+            .poll_interval
+            .filter(|_| config.configuration_files.is_none());
+        let configuration_poller = if let Some(poll_interval) = poll_interval {
+            let fetcher = ConfigurationFetcher::new(ConfigurationFetcherConfig {
+                base_url: config.base_url,
+                api_key: config.api_key,
+                sdk_metadata: SDK_METADATA,
+                http_client: config.http_client,
+            })
+            .map_err(|err| Error::new(ruby.exception_arg_error(), err.to_string()))?;
             let poller = start_configuration_poller(
                 background_thread.runtime(),
-                ConfigurationFetcher::new(ConfigurationFetcherConfig {
-                    base_url: config.base_url,
-                    api_key: config.api_key,
-                    sdk_metadata: SDK_METADATA,
-                }),
+                fetcher,
                 configuration_store.clone(),
                 ConfigurationPollerConfig {
                     interval: poll_interval,
                     jitter: config.poll_jitter,
+                    cache: configuration_cache,
+                    ..ConfigurationPollerConfig::default()
                 },
             );
             Some(poller)
@@ -119,17 +275,30 @@ impl Client {
             None
         };
 
+        if let Some(files) = config
+            .configuration_files
+            .filter(|_| config.watch_configuration_files)
+        {
+            start_configuration_file_watcher(
+                background_thread.runtime(),
+                files,
+                configuration_store.clone(),
+                SDK_METADATA,
+                ConfigurationFileWatcherConfig::default(),
+            );
+        }
+
         let event_ingestion = config
             .event_ingestion_config
             .map(|config| config.spawn(background_thread.runtime()));
 
-        Client {
+        Ok(Client {
             configuration_store,
             evaluator,
             background_thread: RefCell::new(Some(background_thread)),
             configuration_poller,
             event_ingestion,
-        }
+        })
     }
 
     pub fn get_assignment(
@@ -267,6 +436,15 @@ impl Client {
             });
     }
 
+    /// Returns poller health as a Hash, or nil if the poller is disabled.
+    pub fn poller_status(ruby: &Ruby, rb_self: &Self) -> Result<Value> {
+        let status = rb_self
+            .configuration_poller
+            .as_ref()
+            .map(|poller| PollerStatus::from(poller.status()));
+        serde_magnus::serialize(ruby, &status)
+    }
+
     pub fn get_configuration(&self) -> Option<Configuration> {
         self.configuration_store
             .get_configuration()
@@ -278,6 +456,53 @@ impl Client {
             .set_configuration(configuration.clone().into())
     }
 
+    /// Returns recent configurations as an Array of Hashes, newest first.
+    pub fn configuration_history(ruby: &Ruby, rb_self: &Self) -> Result<RArray> {
+        let history = ruby.ary_new();
+        for entry in rb_self.configuration_store.history() {
+            let hash = ruby.hash_new();
+            hash.aset(ruby.to_symbol("version"), entry.version)?;
+            hash.aset(ruby.to_symbol("active"), entry.active)?;
+            hash.aset(
+                ruby.to_symbol("fetched_at"),
+                entry.configuration.fetched_at().to_rfc3339(),
+            )?;
+            hash.aset(
+                ruby.to_symbol("created_at"),
+                entry.configuration.created_at().to_rfc3339(),
+            )?;
+            hash.aset(
+                ruby.to_symbol("configuration"),
+                Configuration::from(entry.configuration),
+            )?;
+            history.push(hash)?;
+        }
+        Ok(history)
+    }
+
+    pub fn pin_configuration(&self) {
+        self.configuration_store.pin();
+    }
+
+    pub fn rollback_configuration(ruby: &Ruby, rb_self: &Self, version: u64) -> Result<()> {
+        if rb_self.configuration_store.rollback(version) {
+            Ok(())
+        } else {
+            Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("configuration version {version} is not in history"),
+            ))
+        }
+    }
+
+    pub fn unpin_configuration(&self) {
+        self.configuration_store.unpin();
+    }
+
+    pub fn is_configuration_pinned(&self) -> bool {
+        self.configuration_store.is_pinned()
+    }
+
     pub fn shutdown(&self) {
         if let Some(thread) = self.background_thread.take() {
             thread.shutdown();
@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
-use magnus::{function, method, prelude::*, scan_args::get_kwargs, Error, RHash, RString, Ruby};
+use magnus::{
+    function, method, prelude::*, scan_args::get_kwargs, Error, RHash, RString, Ruby, Value,
+};
 
 use eppo_core::{ufc::UniversalFlagConfig, Configuration as CoreConfiguration};
 
@@ -19,6 +21,7 @@ pub(crate) fn init(ruby: &Ruby) -> Result<(), Error> {
         "bandits_configuration",
         method!(Configuration::bandits_configuration, 0),
     )?;
+    configuration.define_method("diagnostics", method!(Configuration::diagnostics, 0))?;
 
     Ok(())
 }
@@ -96,6 +99,12 @@ impl Configuration {
             .map(|s| ruby.str_from_slice(s.as_ref()));
         Ok(result)
     }
+
+    /// Returns an Array of Hashes describing flags, allocations, variations, and conditions that
+    /// failed to parse or compile.
+    fn diagnostics(ruby: &Ruby, rb_self: &Self) -> Result<Value, Error> {
+        serde_magnus::serialize(ruby, &rb_self.inner.diagnostics())
+    }
 }
 
 impl From<Arc<CoreConfiguration>> for Configuration {
@@ -33,8 +33,26 @@ fn init(ruby: &Ruby) -> Result<(), Error> {
         "wait_for_initialization",
         method!(Client::wait_for_initialization, 1),
     )?;
+    core_client.define_method("poller_status", method!(Client::poller_status, 0))?;
     core_client.define_method("configuration", method!(Client::get_configuration, 0))?;
     core_client.define_method("configuration=", method!(Client::set_configuration, 1))?;
+    core_client.define_method(
+        "configuration_history",
+        method!(Client::configuration_history, 0),
+    )?;
+    core_client.define_method("pin_configuration", method!(Client::pin_configuration, 0))?;
+    core_client.define_method(
+        "rollback_configuration",
+        method!(Client::rollback_configuration, 1),
+    )?;
+    core_client.define_method(
+        "unpin_configuration",
+        method!(Client::unpin_configuration, 0),
+    )?;
+    core_client.define_method(
+        "configuration_pinned?",
+        method!(Client::is_configuration_pinned, 0),
+    )?;
     core_client.define_method("shutdown", method!(Client::shutdown, 0))?;
 
     core.const_set(
@@ -50,6 +68,15 @@ fn init(ruby: &Ruby) -> Result<(), Error> {
         eppo_core::configuration_poller::ConfigurationPollerConfig::DEFAULT_POLL_JITTER.as_secs(),
     )?;
 
+    core.const_set(
+        "DEFAULT_CONNECT_TIMEOUT_SECONDS",
+        eppo_core::configuration_fetcher::HttpClientConfig::DEFAULT_CONNECT_TIMEOUT.as_secs_f64(),
+    )?;
+    core.const_set(
+        "DEFAULT_READ_TIMEOUT_SECONDS",
+        eppo_core::configuration_fetcher::HttpClientConfig::DEFAULT_READ_TIMEOUT.as_secs_f64(),
+    )?;
+
     configuration::init(ruby)?;
 
     Ok(())
@@ -2,6 +2,7 @@
 
 require "singleton"
 require "logger"
+require "time"
 
 require_relative "config"
 
@@ -48,6 +49,29 @@ module EppoClient
       @core.wait_for_initialization(timeout)
     end
 
+    ##
+    # Returns configuration poller health, e.g., for readiness probes.
+    #
+    # Keys:
+    # - +:running+ — whether the poller is running.
+    # - +:last_success_at+ — when configuration was last fetched successfully.
+    # - +:last_error+, +:last_error_at+ — the most recent error and when it occurred.
+    # - +:consecutive_failures+ — failed fetches since the last successful one.
+    # - +:configuration_fetched_at+ — when the active configuration was fetched.
+    # - +:configuration_created_at+ — when the active configuration was created on the server.
+    # - +:configuration_age_seconds+ — seconds since configuration was last fetched or confirmed unchanged.
+    #
+    # @return [Hash, nil] poller status with +Time+ timestamps, or +nil+ if the poller is disabled.
+    def poller_status
+      status = @core&.poller_status
+      return nil unless status
+
+      %i[last_success_at last_error_at configuration_fetched_at configuration_created_at].each do |key|
+        status[key] = Time.iso8601(status[key]) if status[key]
+      end
+      status
+    end
+
     ##
     # Returns the currently active configuration.
     def configuration
@@ -60,6 +84,53 @@ module EppoClient
       @core.configuration = configuration
     end
 
+    ##
+    # Returns recently activated configurations, newest first.
+    #
+    # Keys:
+    # - +:version+ — version number to pass to +rollback_configuration+.
+    # - +:active+ — whether this configuration is currently active.
+    # - +:fetched_at+ — when the configuration was fetched.
+    # - +:created_at+ — when the configuration was created on the server.
+    # - +:configuration+ — the +EppoClient::Configuration+ itself.
+    #
+    # The number of kept configurations is controlled by +Config#configuration_history_size+.
+    #
+    # @return [Array<Hash>]
+    def configuration_history
+      @core.configuration_history.each do |entry|
+        entry[:fetched_at] = Time.iso8601(entry[:fetched_at])
+        entry[:created_at] = Time.iso8601(entry[:created_at])
+      end
+    end
+
+    ##
+    # Keeps the currently active configuration, ignoring new configurations until
+    # +unpin_configuration+ is called. New configurations are still recorded in history.
+    def pin_configuration
+      @core.pin_configuration
+    end
+
+    ##
+    # Activates configuration +version+ from +configuration_history+ and pins it.
+    #
+    # @raise [ArgumentError] if the version is no longer in history.
+    def rollback_configuration(version)
+      @core.rollback_configuration(version)
+    end
+
+    ##
+    # Resumes applying new configurations and activates the latest one.
+    def unpin_configuration
+      @core.unpin_configuration
+    end
+
+    ##
+    # Whether configuration is pinned by +pin_configuration+ or +rollback_configuration+.
+    def configuration_pinned?
+      @core.configuration_pinned?
+    end
+
     ##
     # Prepare the client for shutdown.
     #
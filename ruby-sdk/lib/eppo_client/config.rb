@@ -6,19 +6,43 @@ require_relative "assignment_logger"
 module EppoClient
   # The class for configuring the Eppo client singleton
   class Config
-    attr_reader :api_key, :assignment_logger, :base_url, :poll_interval_seconds, :poll_jitter_seconds, :log_level
+    attr_reader :api_key, :assignment_logger, :base_url, :poll_interval_seconds, :poll_jitter_seconds, :log_level, :configuration_cache_directory, :assignment_cache_size, :assignment_cache_ttl_seconds, :connect_timeout_seconds, :read_timeout_seconds, :proxy, :root_certificates, :headers, :user_agent, :flags_configuration_file, :bandits_configuration_file, :watch_configuration_files, :max_configuration_age_seconds, :stale_configuration_policy, :configuration_history_size
 
-    def initialize(api_key, assignment_logger: AssignmentLogger.new, base_url: EppoClient::Core::DEFAULT_BASE_URL, poll_interval_seconds: EppoClient::Core::DEFAULT_POLL_INTERVAL_SECONDS, poll_jitter_seconds: EppoClient::Core::DEFAULT_POLL_JITTER_SECONDS, initial_configuration: nil, log_level: nil)
+    def initialize(api_key, assignment_logger: AssignmentLogger.new, base_url: EppoClient::Core::DEFAULT_BASE_URL, poll_interval_seconds: EppoClient::Core::DEFAULT_POLL_INTERVAL_SECONDS, poll_jitter_seconds: EppoClient::Core::DEFAULT_POLL_JITTER_SECONDS, initial_configuration: nil, log_level: nil, configuration_cache_directory: nil, assignment_cache_size: nil, assignment_cache_ttl_seconds: nil, connect_timeout_seconds: EppoClient::Core::DEFAULT_CONNECT_TIMEOUT_SECONDS, read_timeout_seconds: EppoClient::Core::DEFAULT_READ_TIMEOUT_SECONDS, proxy: nil, root_certificates: [], headers: {}, user_agent: nil, flags_configuration_file: nil, bandits_configuration_file: nil, watch_configuration_files: false, max_configuration_age_seconds: nil, stale_configuration_policy: :warn, configuration_history_size: 5)
       @api_key = api_key
       @assignment_logger = assignment_logger
       @base_url = base_url
       @poll_interval_seconds = poll_interval_seconds
       @poll_jitter_seconds = poll_jitter_seconds
       @log_level = log_level
+      @configuration_cache_directory = configuration_cache_directory&.to_s
+      @assignment_cache_size = assignment_cache_size
+      @assignment_cache_ttl_seconds = assignment_cache_ttl_seconds
+      @connect_timeout_seconds = connect_timeout_seconds
+      @read_timeout_seconds = read_timeout_seconds
+      @proxy = proxy
+      @root_certificates = root_certificates
+      @headers = headers
+      @user_agent = user_agent
+      @flags_configuration_file = flags_configuration_file&.to_s
+      @bandits_configuration_file = bandits_configuration_file&.to_s
+      @watch_configuration_files = watch_configuration_files
+      @max_configuration_age_seconds = max_configuration_age_seconds
+      @stale_configuration_policy = stale_configuration_policy.to_s
+      @configuration_history_size = configuration_history_size
     end
 
     def validate
       EppoClient.validate_not_blank("api_key", @api_key)
+      if @flags_configuration_file.nil? && (@bandits_configuration_file || @watch_configuration_files)
+        raise InvalidValueError, "flags_configuration_file is required for bandits_configuration_file and watch_configuration_files"
+      end
+      unless %w[warn return_default].include?(@stale_configuration_policy)
+        raise InvalidValueError, "stale_configuration_policy must be :warn or :return_default"
+      end
+      unless @configuration_history_size.is_a?(Integer) && @configuration_history_size.positive?
+        raise InvalidValueError, "configuration_history_size must be a positive integer"
+      end
     end
 
     # Hide instance variables (specifically api_key) from logs
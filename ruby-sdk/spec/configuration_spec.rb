@@ -63,6 +63,47 @@ RSpec.describe EppoClient::Configuration do
     end
   end
 
+  describe "diagnostics()" do
+    it "returns empty array for valid configuration" do
+      configuration = EppoClient::Configuration.new(flags_configuration: flags_config)
+
+      expect(configuration.diagnostics).to eq([])
+    end
+
+    it "reports invalid conditions" do
+      condition = { "attribute" => "email", "operator" => "MATCHES", "value" => "(unclosed" }
+      flags_config = JSON.generate({
+        "createdAt" => "2024-09-09T10:18:15.988Z",
+        "environment" => { "name" => "test" },
+        "flags" => {
+          "flag" => {
+            "key" => "flag",
+            "enabled" => true,
+            "variationType" => "BOOLEAN",
+            "variations" => { "on" => { "key" => "on", "value" => true } },
+            "allocations" => [{
+              "key" => "allocation",
+              "rules" => [{ "conditions" => [condition] }],
+              "splits" => [{ "shards" => [], "variationKey" => "on" }]
+            }],
+            "totalShards" => 10000
+          }
+        }
+      })
+      configuration = EppoClient::Configuration.new(flags_configuration: flags_config)
+
+      diagnostics = configuration.diagnostics
+
+      expect(diagnostics.length).to eq(1)
+      expect(diagnostics[0][:flag_key]).to eq("flag")
+      expect(diagnostics[0][:allocation_key]).to eq("allocation")
+      expect(diagnostics[0][:rule_index]).to eq(0)
+      expect(diagnostics[0][:condition_index]).to eq(0)
+      expect(diagnostics[0][:reason]).to include("regex")
+      expect(diagnostics[0][:raw]).to eq(condition)
+    end
+  end
+
   it "can be reinstantiated from own configuration" do
     config1 = EppoClient::Configuration.new(flags_configuration: flags_config)
 
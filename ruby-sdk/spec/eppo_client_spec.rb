@@ -47,6 +47,20 @@ RSpec.describe EppoClient do
     end
   end
 
+  describe "poller_status()" do
+    it "reports poller health" do
+      init_client_for "ufc"
+
+      status = EppoClient::Client.instance.poller_status
+
+      expect(status[:running]).to be true
+      expect(status[:consecutive_failures]).to eq 0
+      expect(status[:last_success_at]).to be_a(Time)
+      expect(status[:configuration_created_at]).to be_a(Time)
+      expect(status[:configuration_age_seconds]).to be < 60
+    end
+  end
+
   describe "configuration()" do
     it "allows getting configuration" do
       init_client_for "ufc"
@@ -77,6 +91,55 @@ RSpec.describe EppoClient do
     end
   end
 
+  describe "stale configuration" do
+    it "returns default values with return_default policy" do
+      config = EppoClient::Config.new("test-api-key", poll_interval_seconds: nil, max_configuration_age_seconds: 0.0, stale_configuration_policy: :return_default)
+      EppoClient::Client.instance.init(config)
+      EppoClient::Client.instance.configuration = EppoClient::Configuration.new(flags_configuration: File.read("../sdk-test-data/ufc/flags-v1.json"))
+
+      result = EppoClient::Client.instance.get_string_assignment_details("new-user-onboarding", "alice", {}, "default")
+
+      expect(result[:variation]).to eq("default")
+      expect(result[:evaluationDetails][:flagEvaluationCode]).to eq("CONFIGURATION_STALE")
+    end
+
+    it "rejects unknown policies" do
+      expect {
+        EppoClient::Config.new("test-api-key", stale_configuration_policy: :ignore).validate
+      }.to raise_error(EppoClient::InvalidValueError)
+    end
+  end
+
+  describe "configuration history" do
+    it "rolls back to a previous configuration" do
+      init_client_for "offline"
+      client = EppoClient::Client.instance
+      client.configuration = EppoClient::Configuration.new(flags_configuration: File.read("../sdk-test-data/ufc/flags-v1.json"))
+      client.configuration = EppoClient::Configuration.new(flags_configuration: File.read("../sdk-test-data/ufc/flags-v1-obfuscated.json"))
+
+      history = client.configuration_history
+      expect(history.length).to eq(2)
+      expect(history[0][:active]).to be true
+      expect(history[1][:fetched_at]).to be_a(Time)
+
+      client.rollback_configuration(history[1][:version])
+      expect(client.configuration_pinned?).to be true
+      expect(client.configuration_history[1][:active]).to be true
+
+      client.unpin_configuration
+      expect(client.configuration_pinned?).to be false
+      expect(client.configuration_history[0][:active]).to be true
+    end
+
+    it "raises on unknown versions" do
+      init_client_for "offline"
+
+      expect {
+        EppoClient::Client.instance.rollback_configuration(1_000_000)
+      }.to raise_error(ArgumentError)
+    end
+  end
+
   describe "UFC flag evaluation", :flags do
     before :all do
       init_client_for "ufc"
@@ -0,0 +1,47 @@
+use crate::BanditEvent;
+
+/// A trait for logging bandit events to your storage system. Implementations should handle
+/// persisting bandit action events for analytics and model training purposes.
+pub trait BanditLogger {
+    /// Logs the bandit event to the storage system.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - A [`BanditEvent`] to be logged.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use eppo::{BanditLogger, BanditEvent};
+    /// struct MyBanditLogger;
+    ///
+    /// impl BanditLogger for MyBanditLogger {
+    ///     fn log_bandit_action(&self, event: BanditEvent) {
+    ///         // Implement bandit logging logic here
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This method should not return errors and should not panic.
+    /// Errors that occur during logging should be handled internally within the implementation.
+    ///
+    /// # Notes
+    ///
+    /// This method is called before returning bandit action to the caller, so it is important
+    /// that `log_bandit_action` does not block the calling thread to prevent performance
+    /// implications and delays in returning actions.
+    fn log_bandit_action(&self, event: BanditEvent);
+}
+
+pub(crate) struct NoopBanditLogger;
+impl BanditLogger for NoopBanditLogger {
+    fn log_bandit_action(&self, _event: BanditEvent) {}
+}
+
+impl<T: Fn(BanditEvent)> BanditLogger for T {
+    fn log_bandit_action(&self, event: BanditEvent) {
+        self(event);
+    }
+}
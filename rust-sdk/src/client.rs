@@ -1,18 +1,34 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use crate::{
+    file_watcher::FileWatcherThread,
     poller::{PollerThread, PollerThreadConfig},
-    AssignmentValue, Attributes, ClientConfig, Error, EvaluationError, EvaluationResultWithDetails,
-    SDK_METADATA,
+    AssignmentValue, Attributes, ClientConfig, Configuration, ContextAttributes, Error,
+    EvaluationError, EvaluationResultWithDetails, SDK_METADATA,
 };
 
 use eppo_core::{
-    configuration_store::ConfigurationStore,
-    eval::{Evaluator, EvaluatorConfig},
+    configuration_fetcher::{ConfigurationFetcher, ConfigurationFetcherConfig},
+    configuration_file::ConfigurationFiles,
+    configuration_poller::ConfigurationPollerConfig,
+    configuration_source::{ConfigurationSource, FileConfigurationSource},
+    configuration_store::{
+        ConfigurationChange, ConfigurationHistoryEntry, ConfigurationStore, ListenerId,
+    },
+    eval::{Evaluator, EvaluatorConfig, FlagOverrides},
     ufc::{Assignment, VariationType},
     Str,
 };
 
+/// Result of [`Client::get_bandit_action()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BanditResult {
+    /// Selected variation from the feature flag.
+    pub variation: Str,
+    /// Selected action if variation is a bandit and an action was selected.
+    pub action: Option<Str>,
+}
+
 /// A client for Eppo API.
 ///
 /// In order to create a client instance, first create [`ClientConfig`].
@@ -27,6 +43,12 @@ use eppo_core::{
 /// The reason the poller thread is not started automatically is to allow SDK extension to support
 /// `async` configuration fetching in the future (using async Rust runtimes).
 ///
+/// # Offline Mode
+///
+/// Instead of fetching configuration from the server, the client can be initialized from local
+/// files with [`Client::load_configuration_files()`] (or [`Client::start_file_watcher_thread()`]
+/// to also reload the files when they change). No network requests are made in this case.
+///
 /// # Examples
 /// ```no_run
 /// # use eppo::{Client, ClientConfig};
@@ -42,21 +64,36 @@ pub struct Client<'a> {
 impl<'a> Client<'a> {
     /// Create a new `Client` using the specified configuration.
     ///
+    /// If [`ClientConfig::configuration_cache_directory()`] is set, the client is initialized
+    /// with the cached configuration (if any).
+    ///
     /// ```
     /// # use eppo::{ClientConfig, Client};
     /// let client = Client::new(ClientConfig::from_api_key("api-key"));
     /// ```
     pub fn new(config: ClientConfig<'a>) -> Self {
-        Client::new_with_configuration_store(config, Arc::new(ConfigurationStore::new()))
+        let configuration_store = Arc::new(ConfigurationStore::with_history_size(
+            config.configuration_history_size,
+        ));
+        Client::new_with_configuration_store(config, configuration_store)
     }
 
     fn new_with_configuration_store(
         config: ClientConfig<'a>,
         configuration_store: Arc<ConfigurationStore>,
     ) -> Self {
+        if let Some(cache) = config.configuration_cache() {
+            if let Ok(Some(configuration)) = cache.load(SDK_METADATA) {
+                configuration_store.set_configuration(Arc::new(configuration));
+            }
+        }
+
         let evaluator = Evaluator::new(EvaluatorConfig {
             configuration_store: configuration_store.clone(),
             sdk_metadata: SDK_METADATA.clone(),
+            assignment_cache: config.assignment_cache.clone(),
+            max_configuration_age: config.max_configuration_age,
+            stale_configuration_policy: config.stale_configuration_policy.clone(),
         });
         Self {
             configuration_store,
@@ -531,22 +568,476 @@ impl<'a> Client<'a> {
         result
     }
 
+    /// Evaluate a bandit flag for the given subject, selecting a variation and, if that variation
+    /// is a bandit, one of the supplied `actions`.
+    ///
+    /// Both the assignment event and the bandit event are logged through the configured
+    /// [`AssignmentLogger`][crate::AssignmentLogger] and [`BanditLogger`][crate::BanditLogger].
+    ///
+    /// If the configuration has not been fetched yet, or the flag cannot be evaluated,
+    /// `default_variation` is returned with no action.
+    ///
+    /// Generic [`Attributes`] can be converted to [`ContextAttributes`] with `.into()`: numeric
+    /// attributes are treated as numeric and all others as categorical.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::collections::HashMap;
+    /// # use eppo::ContextAttributes;
+    /// # fn test(client: &eppo::Client) {
+    /// let subject_attributes = ContextAttributes::from_iter([("age", 42.0), ("height", 180.0)]);
+    /// let actions = HashMap::from([
+    ///     ("nike".into(), ContextAttributes::from_iter([("brand_affinity", 0.4)])),
+    ///     ("adidas".into(), ContextAttributes::from_iter([("brand_affinity", 0.2)])),
+    /// ]);
+    /// let result = client.get_bandit_action(
+    ///     "a-bandit-flag",
+    ///     &"user-id".into(),
+    ///     &subject_attributes,
+    ///     &actions,
+    ///     &"default".into(),
+    /// );
+    /// if let Some(action) = result.action {
+    ///     println!("Selected action: {action}");
+    /// }
+    /// # }
+    /// ```
+    pub fn get_bandit_action(
+        &self,
+        flag_key: &str,
+        subject_key: &Str,
+        subject_attributes: &ContextAttributes,
+        actions: &HashMap<Str, ContextAttributes>,
+        default_variation: &Str,
+    ) -> BanditResult {
+        let result = self.evaluator.get_bandit_action(
+            flag_key,
+            subject_key,
+            subject_attributes,
+            &convert_actions(actions),
+            default_variation,
+        );
+
+        self.log_bandit_result(result)
+    }
+
+    /// Evaluate a bandit flag for the given subject, along with details of why this variation and
+    /// action were selected.
+    ///
+    /// *NOTE:* It is a debug function and is slower due to the need to collect all the
+    /// details. Prefer using [`Client::get_bandit_action()`] in production.
+    pub fn get_bandit_action_details(
+        &self,
+        flag_key: &str,
+        subject_key: &Str,
+        subject_attributes: &ContextAttributes,
+        actions: &HashMap<Str, ContextAttributes>,
+        default_variation: &Str,
+    ) -> EvaluationResultWithDetails<Str> {
+        let (result, details) = self.evaluator.get_bandit_action_details(
+            flag_key,
+            subject_key,
+            subject_attributes,
+            &convert_actions(actions),
+            default_variation,
+        );
+
+        let BanditResult { variation, action } = self.log_bandit_result(result);
+
+        EvaluationResultWithDetails {
+            variation: Some(variation),
+            action: action.map(|it| it.to_string()),
+            evaluation_details: Arc::new(details),
+        }
+    }
+
+    fn log_bandit_result(&self, result: eppo_core::eval::BanditResult) -> BanditResult {
+        if let Some(event) = result.assignment_event {
+            log::trace!(target: "eppo",
+                        event:serde;
+                        "logging assignment");
+            self.config.assignment_logger.log_assignment(event);
+        }
+
+        if let Some(event) = result.bandit_event {
+            log::trace!(target: "eppo",
+                        event:serde;
+                        "logging bandit action");
+            self.config.bandit_logger.log_bandit_action(event);
+        }
+
+        BanditResult {
+            variation: result.variation,
+            action: result.action,
+        }
+    }
+
+    /// Register a callback to be called every time a new configuration is received.
+    ///
+    /// The callback is called synchronously on the poller thread, so it should return quickly.
+    /// [`ConfigurationChange::flag_changes()`] can be used to find out which flags were added,
+    /// removed, or changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn test(client: &eppo::Client) {
+    /// client.add_configuration_listener(|change| {
+    ///     let changes = change.flag_changes();
+    ///     println!("configuration updated, changed flags: {:?}", changes.changed);
+    /// });
+    /// # }
+    /// ```
+    pub fn add_configuration_listener(
+        &self,
+        listener: impl Fn(&ConfigurationChange) + Send + Sync + 'static,
+    ) -> ListenerId {
+        self.configuration_store.add_listener(listener)
+    }
+
+    /// Remove a listener previously registered with [`Client::add_configuration_listener()`].
+    ///
+    /// Returns `false` if listener was not found.
+    pub fn remove_configuration_listener(&self, id: ListenerId) -> bool {
+        self.configuration_store.remove_listener(id)
+    }
+
+    /// Subscribe to configuration updates.
+    ///
+    /// The returned receiver always holds the currently active configuration and can be awaited
+    /// for changes from async code.
+    pub fn subscribe_to_configuration(
+        &self,
+    ) -> tokio::sync::watch::Receiver<Option<Arc<Configuration>>> {
+        self.configuration_store.subscribe()
+    }
+
+    /// Replace local flag overrides.
+    ///
+    /// Overrides take precedence over the configuration received from the server and are not
+    /// logged as assignments. They can be changed at any time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn test(client: &eppo::Client) {
+    /// use eppo::{FlagOverride, FlagOverrides};
+    ///
+    /// let mut overrides = FlagOverrides::new();
+    /// overrides.add("new-checkout", FlagOverride::new(true).with_subject_key("alice"));
+    /// client.set_flag_overrides(overrides);
+    /// # }
+    /// ```
+    pub fn set_flag_overrides(&self, overrides: FlagOverrides) {
+        self.evaluator.set_overrides(overrides);
+    }
+
+    /// Return currently active local flag overrides.
+    pub fn get_flag_overrides(&self) -> Arc<FlagOverrides> {
+        self.evaluator.get_overrides()
+    }
+
+    /// Load configuration from local files, replacing the active configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn test(client: &eppo::Client) -> eppo::Result<()> {
+    /// use eppo::ConfigurationFiles;
+    ///
+    /// client.load_configuration_files(
+    ///     &ConfigurationFiles::new("/etc/eppo/flags.json").with_bandits("/etc/eppo/bandits.json"),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn load_configuration_files(&self, files: &ConfigurationFiles) -> Result<(), Error> {
+        let configuration = files.load(SDK_METADATA)?;
+        self.configuration_store
+            .set_configuration(Arc::new(configuration));
+        Ok(())
+    }
+
+    /// Load configuration from flags and (optional) bandits JSON in the format served by Eppo
+    /// servers, replacing the active configuration.
+    pub fn load_configuration_json(
+        &self,
+        flags: impl Into<Vec<u8>>,
+        bandits: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        let configuration = Configuration::from_json(SDK_METADATA, flags.into(), bandits)?;
+        self.configuration_store
+            .set_configuration(Arc::new(configuration));
+        Ok(())
+    }
+
+    /// Return the currently active configuration, or `None` if configuration has not been
+    /// fetched or loaded yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn test(client: &eppo::Client) {
+    /// if let Some(configuration) = client.get_configuration() {
+    ///     for diagnostic in configuration.diagnostics() {
+    ///         eprintln!("invalid flag {}: {}", diagnostic.flag_key, diagnostic.reason);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn get_configuration(&self) -> Option<Arc<Configuration>> {
+        self.configuration_store.get_configuration()
+    }
+
+    /// Return recently fetched or loaded configurations, newest first.
+    ///
+    /// The number of configurations kept is set by [`ClientConfig::configuration_history_size()`].
+    pub fn get_configuration_history(&self) -> Vec<ConfigurationHistoryEntry> {
+        self.configuration_store.history()
+    }
+
+    /// Pin the active configuration. While pinned, configurations fetched by the poller thread
+    /// (or loaded otherwise) are recorded in history but not applied until
+    /// [`Client::unpin_configuration()`] is called.
+    pub fn pin_configuration(&self) {
+        self.configuration_store.pin();
+    }
+
+    /// Roll back to a configuration from [`Client::get_configuration_history()`] and pin it.
+    ///
+    /// Returns `false` if `version` is no longer in history.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn test(client: &eppo::Client) {
+    /// // Revert to the configuration that was active before the latest update.
+    /// if let Some(previous) = client.get_configuration_history().get(1) {
+    ///     client.rollback_configuration(previous.version);
+    /// }
+    /// # }
+    /// ```
+    pub fn rollback_configuration(&self, version: u64) -> bool {
+        self.configuration_store.rollback(version)
+    }
+
+    /// Unpin configuration and apply the most recently fetched one.
+    pub fn unpin_configuration(&self) {
+        self.configuration_store.unpin();
+    }
+
+    /// Returns `true` if configuration is pinned with [`Client::pin_configuration()`] or
+    /// [`Client::rollback_configuration()`].
+    pub fn is_configuration_pinned(&self) -> bool {
+        self.configuration_store.is_pinned()
+    }
+
+    /// Load configuration from local files and start a thread that reloads them whenever they
+    /// change.
+    ///
+    /// Returns an error if the files cannot be loaded initially. If a later reload fails, the
+    /// client keeps serving the previous configuration.
+    pub fn start_file_watcher_thread(
+        &mut self,
+        files: ConfigurationFiles,
+    ) -> Result<FileWatcherThread, Error> {
+        self.load_configuration_files(&files)?;
+        FileWatcherThread::start(files, self.configuration_store.clone())
+    }
+
     /// Start a poller thread to fetch configuration from the server.
     pub fn start_poller_thread(&mut self) -> Result<PollerThread, Error> {
         PollerThread::start(PollerThreadConfig {
             store: self.configuration_store.clone(),
             base_url: self.config.base_url.clone(),
             api_key: self.config.api_key.clone(),
+            cache: self.config.configuration_cache(),
+            streaming_url: self.config.streaming_url.clone(),
+            validator: self.config.configuration_validator.clone(),
+            http_client: self.config.http_client.clone(),
+        })
+    }
+
+    /// Start a poller thread to fetch configuration from a custom `source`.
+    ///
+    /// Sources can be combined with [`ChainConfigurationSource`][crate::ChainConfigurationSource]
+    /// to fall back to another source when one fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn test(mut client: eppo::Client) -> eppo::Result<()> {
+    /// use eppo::{ChainConfigurationSource, ConfigurationFiles};
+    ///
+    /// let source = ChainConfigurationSource::new(
+    ///     client.http_configuration_source()?,
+    ///     client.file_configuration_source(ConfigurationFiles::new("/mnt/s3/eppo/flags.json")),
+    /// );
+    /// let poller = client.start_poller_thread_with_source(source)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn start_poller_thread_with_source(
+        &mut self,
+        source: impl ConfigurationSource + Send + 'static,
+    ) -> Result<PollerThread, Error> {
+        PollerThread::start_with_source(
+            source,
+            self.configuration_store.clone(),
+            ConfigurationPollerConfig {
+                cache: self.config.configuration_cache(),
+                streaming_url: self.config.streaming_url.clone(),
+                validator: self.config.configuration_validator.clone(),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Create a configuration source that fetches configuration from Eppo servers, as configured
+    /// by [`ClientConfig`].
+    pub fn http_configuration_source(&self) -> Result<ConfigurationFetcher, Error> {
+        ConfigurationFetcher::new(ConfigurationFetcherConfig {
+            base_url: self.config.base_url.clone(),
+            api_key: self.config.api_key.clone(),
+            sdk_metadata: SDK_METADATA,
+            http_client: self.config.http_client.clone(),
         })
     }
+
+    /// Create a configuration source that loads configuration from local `files`.
+    pub fn file_configuration_source(&self, files: ConfigurationFiles) -> FileConfigurationSource {
+        FileConfigurationSource::new(files, SDK_METADATA)
+    }
+}
+
+/// Convert actions to the map type used by `eppo_core`.
+///
+/// `eppo_core` may use a different hasher depending on enabled features, so we cannot pass the
+/// map through as is. Cloning is cheap as both `Str` and `ContextAttributes` are
+/// reference-counted.
+fn convert_actions<M: FromIterator<(Str, ContextAttributes)>>(
+    actions: &HashMap<Str, ContextAttributes>,
+) -> M {
+    actions
+        .iter()
+        .map(|(key, attributes)| (key.clone(), attributes.clone()))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
+    use std::{sync::Arc, time::Duration};
+
+    use crate::{BanditResult, Client, ClientConfig, InMemoryConfigurationSource};
+    use eppo_core::{
+        configuration_builder::{
+            AllocationBuilder, ConfigurationBuilder, FlagBuilder, SplitBuilder,
+        },
+        configuration_store::ConfigurationStore,
+        ufc::VariationType,
+    };
+
+    #[test]
+    fn loads_configuration_from_json() {
+        let client = Client::new(ClientConfig::from_api_key("api-key"));
+
+        client
+            .load_configuration_json(
+                ConfigurationBuilder::new()
+                    .flag(
+                        FlagBuilder::new("flag", VariationType::Boolean)
+                            .variation("on", true)
+                            .allocation(
+                                AllocationBuilder::new("everyone").split(SplitBuilder::new("on")),
+                            ),
+                    )
+                    .to_flags_json(),
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(
+            client
+                .get_boolean_assignment("flag", &"subject".into(), &Default::default())
+                .unwrap(),
+            Some(true)
+        );
+        assert!(client.load_configuration_json("not json", None).is_err());
+        assert!(client
+            .get_configuration()
+            .is_some_and(|configuration| configuration.diagnostics().is_empty()));
+    }
+
+    #[test]
+    fn rolls_back_configuration() {
+        let client = Client::new(ClientConfig::from_api_key("api-key"));
+        let flags = |value| {
+            ConfigurationBuilder::new()
+                .flag(
+                    FlagBuilder::new("flag", VariationType::Boolean)
+                        .variation("value", value)
+                        .allocation(
+                            AllocationBuilder::new("everyone").split(SplitBuilder::new("value")),
+                        ),
+                )
+                .to_flags_json()
+        };
+        let assignment = || {
+            client
+                .get_boolean_assignment("flag", &"subject".into(), &Default::default())
+                .unwrap()
+        };
+
+        client.load_configuration_json(flags(true), None).unwrap();
+        client.load_configuration_json(flags(false), None).unwrap();
+        assert_eq!(assignment(), Some(false));
+
+        let history = client.get_configuration_history();
+        assert_eq!(history.len(), 2);
+        assert!(client.rollback_configuration(history[1].version));
+        assert!(client.is_configuration_pinned());
+        assert_eq!(assignment(), Some(true));
+
+        client.load_configuration_json(flags(false), None).unwrap();
+        assert_eq!(assignment(), Some(true));
 
-    use crate::{Client, ClientConfig};
-    use eppo_core::configuration_store::ConfigurationStore;
+        client.unpin_configuration();
+        assert!(!client.is_configuration_pinned());
+        assert_eq!(assignment(), Some(false));
+    }
+
+    #[test]
+    fn polls_custom_configuration_source() {
+        let mut client = Client::new(ClientConfig::from_api_key("api-key"));
+        let source = InMemoryConfigurationSource::new(
+            ConfigurationBuilder::new()
+                .flag(
+                    FlagBuilder::new("flag", VariationType::Boolean)
+                        .variation("on", true)
+                        .allocation(
+                            AllocationBuilder::new("everyone").split(SplitBuilder::new("on")),
+                        ),
+                )
+                .build()
+                .unwrap(),
+        );
+
+        let poller = client.start_poller_thread_with_source(source).unwrap();
+        poller
+            .wait_for_configuration_timeout(Duration::from_secs(5))
+            .unwrap();
+        assert!(poller.status().running);
+        assert_eq!(poller.status().consecutive_failures, 0);
+
+        assert_eq!(
+            client
+                .get_boolean_assignment("flag", &"subject".into(), &Default::default())
+                .unwrap(),
+            Some(true)
+        );
+        poller.shutdown().unwrap();
+    }
 
     #[test]
     fn returns_none_while_no_configuration() {
@@ -563,4 +1054,31 @@ mod tests {
             None
         );
     }
+
+    #[test]
+    fn returns_default_bandit_variation_while_no_configuration() {
+        let configuration_store = Arc::new(ConfigurationStore::new());
+        let client = Client::new_with_configuration_store(
+            ClientConfig::from_api_key("api-key")
+                .assignment_logger(|_| panic!("assignment should not be logged"))
+                .bandit_logger(|_| panic!("bandit action should not be logged")),
+            configuration_store.clone(),
+        );
+
+        let result = client.get_bandit_action(
+            "flag",
+            &"subject".into(),
+            &Default::default(),
+            &Default::default(),
+            &"default".into(),
+        );
+
+        assert_eq!(
+            result,
+            BanditResult {
+                variation: "default".into(),
+                action: None
+            }
+        );
+    }
 }
@@ -1,4 +1,15 @@
-use crate::{assignment_logger::NoopAssignmentLogger, AssignmentLogger, Client};
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use eppo_core::{
+    assignment_cache::AssignmentCache, configuration_cache::ConfigurationCache,
+    configuration_fetcher::HttpClientConfig, configuration_store::ConfigurationStore,
+    configuration_validator::ConfigurationValidator, eval::StaleConfigurationPolicy,
+};
+
+use crate::{
+    assignment_logger::NoopAssignmentLogger, bandit_logger::NoopBanditLogger, AssignmentLogger,
+    BanditLogger, Client,
+};
 
 /// Configuration for [`Client`].
 ///
@@ -15,6 +26,15 @@ pub struct ClientConfig<'a> {
     pub(crate) api_key: String,
     pub(crate) base_url: String,
     pub(crate) assignment_logger: Box<dyn AssignmentLogger + Send + Sync + 'a>,
+    pub(crate) bandit_logger: Box<dyn BanditLogger + Send + Sync + 'a>,
+    pub(crate) configuration_cache_directory: Option<PathBuf>,
+    pub(crate) assignment_cache: Option<Arc<AssignmentCache>>,
+    pub(crate) streaming_url: Option<String>,
+    pub(crate) http_client: HttpClientConfig,
+    pub(crate) max_configuration_age: Option<Duration>,
+    pub(crate) stale_configuration_policy: StaleConfigurationPolicy,
+    pub(crate) configuration_validator: Option<ConfigurationValidator>,
+    pub(crate) configuration_history_size: usize,
 }
 
 impl<'a> ClientConfig<'a> {
@@ -29,6 +49,15 @@ impl<'a> ClientConfig<'a> {
             api_key: api_key.into(),
             base_url: ClientConfig::DEFAULT_BASE_URL.to_owned(),
             assignment_logger: Box::new(NoopAssignmentLogger),
+            bandit_logger: Box::new(NoopBanditLogger),
+            configuration_cache_directory: None,
+            assignment_cache: None,
+            streaming_url: None,
+            http_client: HttpClientConfig::default(),
+            max_configuration_age: None,
+            stale_configuration_policy: StaleConfigurationPolicy::default(),
+            configuration_validator: None,
+            configuration_history_size: ConfigurationStore::DEFAULT_HISTORY_SIZE,
         }
     }
 
@@ -48,6 +77,19 @@ impl<'a> ClientConfig<'a> {
         self
     }
 
+    /// Set bandit logger to store bandit actions to your data warehouse.
+    ///
+    /// ```
+    /// # use eppo::ClientConfig;
+    /// let config = ClientConfig::from_api_key("api-key").bandit_logger(|event| {
+    ///   println!("{:?}", event);
+    /// });
+    /// ```
+    pub fn bandit_logger(mut self, bandit_logger: impl BanditLogger + Send + Sync + 'a) -> Self {
+        self.bandit_logger = Box::new(bandit_logger);
+        self
+    }
+
     /// Default base URL for API calls.
     pub const DEFAULT_BASE_URL: &'static str = "https://fscdn.eppo.cloud/api";
 
@@ -57,6 +99,159 @@ impl<'a> ClientConfig<'a> {
         self
     }
 
+    /// Persist fetched configuration to `directory` and load it on client creation.
+    ///
+    /// This allows the client to serve the last known configuration right after start, even if
+    /// Eppo servers cannot be reached. The directory should not be shared between clients that
+    /// use different API keys.
+    ///
+    /// ```
+    /// # use eppo::ClientConfig;
+    /// let config = ClientConfig::from_api_key("api-key")
+    ///     .configuration_cache_directory("/var/cache/my-app/eppo");
+    /// ```
+    pub fn configuration_cache_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.configuration_cache_directory = Some(directory.into());
+        self
+    }
+
+    /// Deduplicate assignment and bandit events using `cache`.
+    ///
+    /// Repeated evaluations that produce the same variation (or bandit action) for the same subject
+    /// are only logged once while they stay in the cache.
+    ///
+    /// ```
+    /// # use eppo::{AssignmentCache, ClientConfig};
+    /// # use std::time::Duration;
+    /// let config = ClientConfig::from_api_key("api-key").assignment_cache(
+    ///     AssignmentCache::new(AssignmentCache::DEFAULT_CAPACITY).with_ttl(Duration::from_secs(3600)),
+    /// );
+    /// ```
+    pub fn assignment_cache(mut self, cache: AssignmentCache) -> Self {
+        self.assignment_cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Receive configuration updates pushed from the server-sent events stream at `url`.
+    ///
+    /// Updates are applied as soon as they are received, so flag changes propagate without waiting
+    /// for the next poll. The poller thread falls back to polling while the stream is
+    /// disconnected.
+    ///
+    /// ```
+    /// # use eppo::ClientConfig;
+    /// let config = ClientConfig::from_api_key("api-key")
+    ///     .streaming_url("https://example.com/flag-config/v1/stream");
+    /// ```
+    pub fn streaming_url(mut self, url: impl Into<String>) -> Self {
+        self.streaming_url = Some(url.into());
+        self
+    }
+
+    /// Set timeout for establishing a connection to Eppo servers.
+    ///
+    /// Defaults to 10 seconds.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.http_client.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set timeout for each read from the connection to Eppo servers.
+    ///
+    /// Defaults to 60 seconds.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.http_client.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Send all requests through the proxy at `url`. Credentials may be included in the URL.
+    ///
+    /// ```
+    /// # use eppo::ClientConfig;
+    /// let config = ClientConfig::from_api_key("api-key").proxy("http://proxy.internal:3128");
+    /// ```
+    pub fn proxy(mut self, url: impl Into<String>) -> Self {
+        self.http_client.proxy = Some(url.into());
+        self
+    }
+
+    /// Trust additional PEM-encoded root certificate(s), e.g. a private CA of a corporate proxy.
+    ///
+    /// ```no_run
+    /// # use eppo::ClientConfig;
+    /// let config = ClientConfig::from_api_key("api-key")
+    ///     .add_root_certificate(std::fs::read("/etc/ssl/certs/corporate-ca.pem").unwrap());
+    /// ```
+    pub fn add_root_certificate(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.http_client.root_certificates.push(pem.into());
+        self
+    }
+
+    /// Send an additional header with every request to Eppo servers.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.http_client.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Set `User-Agent` header for requests to Eppo servers.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.http_client.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Consider configuration stale once it was last fetched or confirmed unchanged longer than
+    /// `max_age` ago.
+    ///
+    /// What happens when evaluating against stale configuration is determined by
+    /// [`ClientConfig::stale_configuration_policy()`]. By default, a warning is logged.
+    ///
+    /// ```
+    /// # use eppo::{ClientConfig, StaleConfigurationPolicy};
+    /// # use std::time::Duration;
+    /// let config = ClientConfig::from_api_key("api-key")
+    ///     .max_configuration_age(Duration::from_secs(24 * 3600))
+    ///     .stale_configuration_policy(StaleConfigurationPolicy::ReturnDefault);
+    /// ```
+    pub fn max_configuration_age(mut self, max_age: Duration) -> Self {
+        self.max_configuration_age = Some(max_age);
+        self
+    }
+
+    /// Set what happens when evaluating against configuration older than
+    /// [`ClientConfig::max_configuration_age()`].
+    pub fn stale_configuration_policy(mut self, policy: StaleConfigurationPolicy) -> Self {
+        self.stale_configuration_policy = policy;
+        self
+    }
+
+    /// Validate configuration fetched by the poller thread before applying it.
+    ///
+    /// Configuration that fails validation is discarded and the previous configuration is kept.
+    /// Rejections are reported in [`PollerThread::status()`][crate::PollerThread::status] as
+    /// [`Error::ConfigurationRejected`][crate::Error::ConfigurationRejected].
+    ///
+    /// ```
+    /// # use eppo::{ClientConfig, ConfigurationValidator};
+    /// let config = ClientConfig::from_api_key("api-key").configuration_validator(
+    ///     ConfigurationValidator::new()
+    ///         .with_required_flag("kill-switch")
+    ///         .with_min_flag_count_ratio(0.5),
+    /// );
+    /// ```
+    pub fn configuration_validator(mut self, validator: ConfigurationValidator) -> Self {
+        self.configuration_validator = Some(validator);
+        self
+    }
+
+    /// Set the number of recent configurations kept for
+    /// [`Client::rollback_configuration()`][crate::Client::rollback_configuration].
+    ///
+    /// Defaults to 5.
+    pub fn configuration_history_size(mut self, size: usize) -> Self {
+        self.configuration_history_size = size;
+        self
+    }
+
     /// Create a new [`Client`] using the specified configuration.
     ///
     /// ```
@@ -66,4 +261,10 @@ impl<'a> ClientConfig<'a> {
     pub fn to_client(self) -> Client<'a> {
         Client::new(self)
     }
+
+    pub(crate) fn configuration_cache(&self) -> Option<ConfigurationCache> {
+        self.configuration_cache_directory
+            .as_ref()
+            .map(ConfigurationCache::new)
+    }
 }
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use crate::{Result, SDK_METADATA};
+use eppo_core::background::BackgroundThread;
+use eppo_core::configuration_file::{
+    start_configuration_file_watcher, ConfigurationFileWatcher, ConfigurationFileWatcherConfig,
+    ConfigurationFiles,
+};
+use eppo_core::configuration_store::ConfigurationStore;
+
+/// A thread that watches configuration files for changes and reloads them.
+///
+/// Use [`Client::start_file_watcher_thread`][crate::Client::start_file_watcher_thread] to get an
+/// instance.
+pub struct FileWatcherThread {
+    thread: BackgroundThread,
+    // Kept for the lifetime of the thread. Watcher stops when the thread is killed.
+    _watcher: ConfigurationFileWatcher,
+}
+
+impl FileWatcherThread {
+    pub(crate) fn start(
+        files: ConfigurationFiles,
+        store: Arc<ConfigurationStore>,
+    ) -> Result<FileWatcherThread> {
+        let thread = BackgroundThread::start()?;
+        let watcher = start_configuration_file_watcher(
+            thread.runtime(),
+            files,
+            store,
+            SDK_METADATA,
+            ConfigurationFileWatcherConfig::default(),
+        );
+        Ok(FileWatcherThread {
+            thread,
+            _watcher: watcher,
+        })
+    }
+
+    /// Stop the file watcher thread.
+    ///
+    /// This function does not wait for the thread to actually stop.
+    pub fn stop(&self) {
+        self.thread.kill();
+    }
+
+    /// Stop the file watcher thread and block waiting for it to exit.
+    pub fn shutdown(self) -> Result<()> {
+        self.thread.shutdown();
+        Ok(())
+    }
+}
@@ -33,6 +33,19 @@
 //! });
 //! ```
 //!
+//! # Contextual bandits
+//!
+//! Bandit flags are evaluated with [`Client::get_bandit_action()`], which selects a variation and
+//! (if the variation is a bandit) an action out of the supplied actions. A [`BanditLogger`] should
+//! be provided to save bandit events to your storage:
+//!
+//! ```
+//! # use eppo::ClientConfig;
+//! let config = ClientConfig::from_api_key("api-key").bandit_logger(|event| {
+//!   println!("{:?}", event);
+//! });
+//! ```
+//!
 //! # Error Handling
 //!
 //! Errors are represented by the [`Error`] enum.
@@ -56,20 +69,40 @@
 #![warn(missing_docs)]
 
 mod assignment_logger;
+mod bandit_logger;
 mod client;
 mod config;
+mod file_watcher;
 mod poller;
 
 use eppo_core::SdkMetadata;
 #[doc(inline)]
 pub use eppo_core::{
-    eval::eval_details::*, events::AssignmentEvent, ufc::AssignmentValue, AttributeValue,
-    Attributes, CategoricalAttribute, Error, EvaluationError, NumericAttribute, Result,
+    assignment_cache::AssignmentCache,
+    configuration_diagnostics::ConfigurationDiagnostic,
+    configuration_fetcher::ConfigurationFetcher,
+    configuration_file::ConfigurationFiles,
+    configuration_poller::ConfigurationPollerStatus,
+    configuration_source::{
+        ChainConfigurationSource, ConfigurationSource, FileConfigurationSource,
+        InMemoryConfigurationSource,
+    },
+    configuration_store::{
+        ConfigurationChange, ConfigurationHistoryEntry, FlagChanges, ListenerId,
+    },
+    configuration_validator::ConfigurationValidator,
+    eval::{eval_details::*, FlagOverride, FlagOverrides, StaleConfigurationPolicy},
+    events::{AssignmentEvent, BanditEvent},
+    ufc::AssignmentValue,
+    AttributeValue, Attributes, CategoricalAttribute, Configuration, ContextAttributes, Error,
+    EvaluationError, NumericAttribute, Result,
 };
 
 pub use assignment_logger::AssignmentLogger;
-pub use client::Client;
+pub use bandit_logger::BanditLogger;
+pub use client::{BanditResult, Client};
 pub use config::ClientConfig;
+pub use file_watcher::FileWatcherThread;
 pub use poller::PollerThread;
 
 pub(crate) const SDK_METADATA: SdkMetadata = SdkMetadata {
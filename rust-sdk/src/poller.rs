@@ -3,9 +3,16 @@ use std::time::Duration;
 
 use crate::{Result, SDK_METADATA};
 use eppo_core::background::BackgroundThread;
-use eppo_core::configuration_fetcher::{ConfigurationFetcher, ConfigurationFetcherConfig};
-use eppo_core::configuration_poller::start_configuration_poller;
+use eppo_core::configuration_cache::ConfigurationCache;
+use eppo_core::configuration_fetcher::{
+    ConfigurationFetcher, ConfigurationFetcherConfig, HttpClientConfig,
+};
+use eppo_core::configuration_poller::{
+    start_configuration_poller, ConfigurationPollerConfig, ConfigurationPollerStatus,
+};
+use eppo_core::configuration_source::ConfigurationSource;
 use eppo_core::configuration_store::ConfigurationStore;
+use eppo_core::configuration_validator::ConfigurationValidator;
 #[cfg(doc)]
 use eppo_core::Error;
 
@@ -13,6 +20,10 @@ pub(crate) struct PollerThreadConfig {
     pub(crate) store: Arc<ConfigurationStore>,
     pub(crate) base_url: String,
     pub(crate) api_key: String,
+    pub(crate) cache: Option<ConfigurationCache>,
+    pub(crate) streaming_url: Option<String>,
+    pub(crate) validator: Option<ConfigurationValidator>,
+    pub(crate) http_client: HttpClientConfig,
 }
 
 /// A configuration poller thread.
@@ -44,6 +55,7 @@ impl PollerThread {
     /// This method can return the following errors:
     ///
     /// - [`Error::InvalidBaseUrl`] if the base URL configuration is invalid.
+    /// - [`Error::InvalidHttpClientConfig`] if HTTP client settings are invalid.
     /// - [`Error::Unauthorized`] if the request is unauthorized, possibly due to an invalid API key.
     /// - [`Error::PollerThreadPanicked`] if an unexpected panic occurs in the poller thread.
     /// - [`Error::Io`] for any I/O related errors.
@@ -52,14 +64,25 @@ impl PollerThread {
             base_url: config.base_url,
             api_key: config.api_key,
             sdk_metadata: SDK_METADATA.clone(),
-        });
+            http_client: config.http_client,
+        })?;
+        let poller_config = ConfigurationPollerConfig {
+            cache: config.cache,
+            streaming_url: config.streaming_url,
+            validator: config.validator,
+            ..Default::default()
+        };
+        PollerThread::start_with_source(fetcher, config.store, poller_config)
+    }
+
+    /// Starts the configuration poller thread that polls `source`.
+    pub(crate) fn start_with_source(
+        source: impl ConfigurationSource + Send + 'static,
+        store: Arc<ConfigurationStore>,
+        poller_config: ConfigurationPollerConfig,
+    ) -> Result<PollerThread> {
         let thread = BackgroundThread::start()?;
-        let poller = start_configuration_poller(
-            thread.runtime(),
-            fetcher,
-            config.store,
-            eppo_core::configuration_poller::ConfigurationPollerConfig::default(),
-        );
+        let poller = start_configuration_poller(thread.runtime(), source, store, poller_config);
         Ok(PollerThread { thread, poller })
     }
 
@@ -140,6 +163,24 @@ impl PollerThread {
         })
     }
 
+    /// Returns the current poller health, e.g., for readiness probes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # fn test(poller: eppo::PollerThread) {
+    /// let status = poller.status();
+    /// let ready = status.running
+    ///     && status
+    ///         .configuration_age()
+    ///         .is_some_and(|age| age < Duration::from_secs(10 * 60));
+    /// # }
+    /// ```
+    pub fn status(&self) -> ConfigurationPollerStatus {
+        self.poller.status()
+    }
+
     /// Stop the poller thread.
     ///
     /// This function does not wait for the thread to actually stop.